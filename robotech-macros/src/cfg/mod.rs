@@ -27,12 +27,70 @@ impl Parse for WatchCfgFileArgs {
     }
 }
 
+pub(super) struct WatchCfgRemoteArgs {
+    title: String,
+    remote_cfg_source: Expr,
+    poll_interval: Expr,
+    on_files_changed: Block,
+}
+
+impl Parse for WatchCfgRemoteArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let title = input.parse::<syn::LitStr>()?.value();
+        let _: Token![,] = input.parse()?;
+
+        let remote_cfg_source = input.parse::<Expr>()?;
+        let _: Token![,] = input.parse()?;
+
+        let poll_interval = input.parse::<Expr>()?;
+        let _: Token![,] = input.parse()?;
+
+        let on_files_changed = input.parse()?;
+
+        Ok(WatchCfgRemoteArgs {
+            title,
+            remote_cfg_source,
+            poll_interval,
+            on_files_changed,
+        })
+    }
+}
+
 pub(super) fn watch_cfg_file_macro(args: WatchCfgFileArgs) -> TokenStream {
     let WatchCfgFileArgs {
         title,
         files,
         on_files_changed,
     } = args;
+
+    let create_watcher = quote! {
+        let (_watcher, receiver) = watch_cfg_file(#files).expect(&format!("watch {} cfg file error: {:?}", #title, #files));
+    };
+    watch_cfg_expand(&title, &files, create_watcher, &on_files_changed)
+}
+
+pub(super) fn watch_cfg_remote_macro(args: WatchCfgRemoteArgs) -> TokenStream {
+    let WatchCfgRemoteArgs {
+        title,
+        remote_cfg_source,
+        poll_interval,
+        on_files_changed,
+    } = args;
+
+    let files: Expr = syn::parse_quote! { (#remote_cfg_source).url };
+    let create_watcher = quote! {
+        let (_watcher, receiver) = watch_cfg_remote(#remote_cfg_source.clone(), #poll_interval);
+    };
+    watch_cfg_expand(&title, &files, create_watcher, &on_files_changed)
+}
+
+/// 生成监控配置的代码，配置文件与远程配置共用同一套变更通知的处理逻辑
+fn watch_cfg_expand(
+    title: &str,
+    files: &Expr,
+    create_watcher: TokenStream,
+    on_files_changed: &Block,
+) -> TokenStream {
     let on_files_changed = &on_files_changed.stmts;
 
     let expanded = quote! {
@@ -42,7 +100,7 @@ pub(super) fn watch_cfg_file_macro(args: WatchCfgFileArgs) -> TokenStream {
         tracing::debug!("watch {} cfg file: {:?} ...", #title, #files);
         tokio::spawn({
             async move {
-                let (_watcher, receiver) = watch_cfg_file(#files).expect(&format!("watch {} cfg file error: {:?}", #title, #files));
+                #create_watcher
 
                 // 创建一个1秒间隔的定时器
                 let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
@@ -1,9 +1,12 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
 use syn::parse::{Parse, ParseStream};
-use syn::{Expr, ItemStruct, Lit, LitStr, Token, bracketed, parenthesized};
+use syn::{Expr, ImplItemFn, ItemStruct, Lit, LitInt, LitStr, Token, bracketed, parenthesized};
 use wheel_rs::str_utils::{CamelFormat, split_camel_case};
 
+/// 批量插入时每批的默认记录数
+const BATCH_INSERT_SIZE_DEFAULT: usize = 1000;
+
 /// 唯一键字段配置项
 #[derive(Debug)]
 struct UniqueKeyArgs {
@@ -72,6 +75,12 @@ pub(super) struct DaoArgs {
     like_columns: Vec<Expr>,
     /// 关联表
     related_tables: Vec<Expr>,
+    /// 是否记录方法耗时
+    timing: bool,
+    /// 软删除(保留参数名用于编译错误定位)
+    soft_delete: Option<Ident>,
+    /// 批量插入时每批的记录数
+    batch_insert_size: usize,
 }
 
 impl Parse for DaoArgs {
@@ -80,11 +89,28 @@ impl Parse for DaoArgs {
         let mut foreign_keys = vec![];
         let mut like_columns = vec![];
         let mut related_tables = vec![];
+        let mut timing = false;
+        let mut soft_delete = None;
+        let mut batch_insert_size = BATCH_INSERT_SIZE_DEFAULT;
 
         // 解析可选的参数列表
         while !input.is_empty() {
             // 解析标识符（参数名）
             let ident: Ident = input.parse()?;
+
+            // 开关类参数，没有冒号及值
+            if ident == "timing" || ident == "soft_delete" {
+                if ident == "timing" {
+                    timing = true;
+                } else {
+                    soft_delete = Some(ident);
+                }
+                if !input.is_empty() {
+                    let _comma: Token![,] = input.parse()?;
+                }
+                continue;
+            }
+
             // 解析冒号
             let _colon: Token![:] = input.parse()?;
 
@@ -115,6 +141,15 @@ impl Parse for DaoArgs {
                 // 解析逗号分隔的列表
                 let parsed_args = content.parse_terminated(Expr::parse, Token![,])?;
                 related_tables = parsed_args.into_iter().collect();
+            } else if ident == "batch_insert_size" {
+                let value: LitInt = input.parse()?;
+                batch_insert_size = value.base10_parse()?;
+                if batch_insert_size == 0 {
+                    return Err(syn::Error::new_spanned(
+                        value,
+                        "batch_insert_size 必须大于0",
+                    ));
+                }
             } else {
                 let error_msg = format!("未知的参数：{}", ident);
                 return Err(syn::Error::new_spanned(&ident, error_msg));
@@ -131,6 +166,9 @@ impl Parse for DaoArgs {
             foreign_keys,
             like_columns,
             related_tables,
+            timing,
+            soft_delete,
+            batch_insert_size,
         })
     }
 }
@@ -141,6 +179,9 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
         foreign_keys,
         like_columns,
         related_tables,
+        timing,
+        soft_delete,
+        batch_insert_size,
     } = args;
 
     let struct_name = &input.ident;
@@ -186,7 +227,7 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
     // 生成 use 导入
     let generated_use_linkme = if !unique_keys.is_empty() || !foreign_keys.is_empty() {
         quote! {
-            use linkme::distributed_slice;
+            use ::robotech::__private::linkme::distributed_slice;
         }
     } else {
         quote! {}
@@ -206,13 +247,14 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
                 let item_name = Ident::new(&format!("UNIQUE_FIELD_{}", i), Span::call_site());
                 quote! {
                     #[distributed_slice(UNIQUE_KEYS_SLICE)]
+                    #[linkme(crate = ::robotech::__private::linkme)]
                     static #item_name: (&str, &str, &str) = (#table_name, #fields_str, #name_str);
                 }
             })
             .collect();
 
         quote! {
-            use robotech::dao::UNIQUE_KEYS_SLICE;
+            use ::robotech::dao::UNIQUE_KEYS_SLICE;
             #(#field_inits)*
         }
     };
@@ -244,6 +286,7 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
                 let item_name = Ident::new(&format!("FOREIGN_KEY_{}", i), Span::call_site());
                 quote! {
                     #[distributed_slice(FOREIGN_KEYS_SLICE)]
+                    #[linkme(crate = ::robotech::__private::linkme)]
                     static #item_name: (&str, &str, &str, &str, &str) =
                         (#fk_table, #fk_table_remark, #fk_column, #pk_table, #pk_table_comment);
                 }
@@ -251,11 +294,36 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
             .collect();
 
         quote! {
-            use robotech::dao::FOREIGN_KEYS_SLICE;
+            use ::robotech::dao::FOREIGN_KEYS_SLICE;
             #(#field_inits)*
         }
     };
 
+    // 软删除时，查询方法过滤掉已删除(删除时间不为空)的记录
+    let not_deleted = if soft_delete.is_some() {
+        quote! {
+            .filter(::robotech::__private::sea_orm::ColumnTrait::is_null(Column::DeleteTimestamp))
+        }
+    } else {
+        quote! {}
+    };
+    // 软删除要求Model中有 delete_timestamp: Option<i64> 字段，编译错误定位到 soft_delete 参数:
+    // 没有该字段时提示 `Model` 没有 `delete_timestamp` 字段，类型不对时提示 soft_delete 要求的类型
+    let generated_soft_delete_check = if let Some(soft_delete) = &soft_delete {
+        quote_spanned! {soft_delete.span()=>
+            const _: () = {
+                fn soft_delete_requires_delete_timestamp<T: ::robotech::dao::SoftDeleteTimestamp>(_: &T) {}
+                #[allow(dead_code)]
+                fn check_soft_delete(model: &Model) {
+                    let Model { delete_timestamp, .. } = model;
+                    soft_delete_requires_delete_timestamp(delete_timestamp);
+                }
+            };
+        }
+    } else {
+        quote! {}
+    };
+
     // 生成成员变量
     let mut generated_members = Vec::new();
 
@@ -274,6 +342,32 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
         })
     }
 
+    // 生成fill_insert_fields方法
+    generated_members.push(quote! {
+        /// # 填充插入记录时自动设置的字段
+        ///
+        /// - 如果记录 ID 未设置（默认值），则生成一个新的唯一 ID
+        /// - 如果创建时间戳未设置，则设置当前时间为创建和更新时间
+        /// - 将修改者 ID 设置为创建者 ID（因为是新建记录）
+        fn fill_insert_fields(active_model: &mut ActiveModel) -> Result<(), DaoError> {
+            // 当前请求之后从主库读取，避免读不到刚写入的数据
+            ::robotech::ctx::mark_read_primary();
+            // 当id为默认值(0)时生成ID
+            if active_model.id == ActiveValue::NotSet {
+                active_model.id = ActiveValue::set(::robotech::__private::idworker::get_id_worker()?.next_id()? as i64);
+            }
+            // 当创建时间未设置时，设置创建时间和修改时间
+            if active_model.create_timestamp == ActiveValue::NotSet {
+                let now = ActiveValue::set(::robotech::__private::wheel_rs::time_utils::now_ts()? as i64);
+                active_model.create_timestamp = now.clone();
+                active_model.update_timestamp = now;
+            }
+            // 添加时修改者就是创建者
+            active_model.updator_id = active_model.creator_id.clone();
+            Ok(())
+        }
+    });
+
     // 生成insert方法
     generated_members.push(quote! {
         /// # 插入记录
@@ -293,18 +387,7 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
         where
             C: ConnectionTrait,
         {
-            // 当id为默认值(0)时生成ID
-            if active_model.id == ActiveValue::NotSet {
-                active_model.id = ActiveValue::set(idworker::get_id_worker()?.next_id()? as i64);
-            }
-            // 当创建时间未设置时，设置创建时间和修改时间
-            if active_model.create_timestamp == ActiveValue::NotSet {
-                let now = ActiveValue::set(wheel_rs::time_utils::now_ts()? as i64);
-                active_model.create_timestamp = now.clone();
-                active_model.update_timestamp = now;
-            }
-            // 添加时修改者就是创建者
-            active_model.updator_id = active_model.creator_id.clone();
+            Self::fill_insert_fields(&mut active_model)?;
             // 执行数据库插入操作
             active_model
                 .insert(db)
@@ -313,90 +396,284 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
         }
     });
 
-    // 生成update方法
+    // 生成batch_insert方法
     generated_members.push(quote! {
-        /// # 更新记录
+        /// # 批量插入记录
         ///
-        /// 此函数负责更新数据库中的现有记录。它会自动处理以下逻辑：
-        /// - 如果更新时间戳未设置，则设置当前时间为更新时间
-        /// - 更新完成后，重新查询并返回更新后的完整记录
+        /// 与 `insert` 一样自动设置ID、创建时间、修改时间及修改者，
+        /// 然后按 `dao` 宏的 `batch_insert_size` 参数(默认1000条)分批执行插入(避免超过数据库单条语句的参数个数限制)，
+        /// 记录为空时不执行任何操作
+        ///
+        /// 分批插入不是原子操作，需要全部成功或全部失败时请传入事务
         ///
         /// ## 参数
-        /// * `active_model` - 包含待更新数据的 ActiveModel 实例
+        /// * `active_models` - 包含待插入数据的 ActiveModel 实例列表
         /// * `db` - 数据库连接 trait 对象
         ///
         /// ## 返回值
-        /// 返回更新后的完整 Model 实例，如果更新失败则返回相应的错误信息
-        pub async fn update<C>(mut active_model: ActiveModel, db: &C) -> Result<Model, DaoError>
+        /// 全部插入成功返回 Ok(())，如果插入失败则返回相应的错误信息
+        pub async fn batch_insert<C>(mut active_models: Vec<ActiveModel>, db: &C) -> Result<(), DaoError>
         where
             C: ConnectionTrait,
         {
+            for active_model in active_models.iter_mut() {
+                Self::fill_insert_fields(active_model)?;
+            }
+            let mut active_models = active_models.into_iter();
+            loop {
+                let batch: Vec<ActiveModel> = active_models.by_ref().take(#batch_insert_size).collect();
+                if batch.is_empty() {
+                    return Ok(());
+                }
+                Entity::insert_many(batch)
+                    .exec_without_returning(db)
+                    .await
+                    .map_err(|e| DaoError::parse_db_err(e))?;
+            }
+        }
+    });
+
+    // 生成fill_update_fields方法
+    generated_members.push(quote! {
+        /// # 填充更新记录时自动设置的字段
+        ///
+        /// - 保护创建者信息不能被修改
+        /// - 如果更新时间戳未设置且 `keep_timestamp` 为false，则设置当前时间为更新时间
+        fn fill_update_fields(active_model: &mut ActiveModel, keep_timestamp: bool) -> Result<(), DaoError> {
+            // 当前请求之后从主库读取，避免读不到刚写入的数据
+            ::robotech::ctx::mark_read_primary();
             // 保护创建者信息不能被修改
             active_model.creator_id = ActiveValue::NotSet;
             active_model.create_timestamp = ActiveValue::NotSet;
             // 当修改时间未设置时，设置修改时间
-            if active_model.update_timestamp == ActiveValue::NotSet {
-                let now = ActiveValue::set(wheel_rs::time_utils::now_ts()? as i64);
+            if !keep_timestamp && active_model.update_timestamp == ActiveValue::NotSet {
+                let now = ActiveValue::set(::robotech::__private::wheel_rs::time_utils::now_ts()? as i64);
                 active_model.update_timestamp = now;
             }
-            // 执行数据库更新操作
-            active_model
-                .update(db)
-                .await
-                .map_err(|e| DaoError::parse_db_err(e))
+            Ok(())
         }
     });
 
-    // 生成delete方法
+    // 生成update方法
     generated_members.push(quote! {
-        /// # 删除记录
+        /// # 更新记录
         ///
-        /// 此函数负责根据关键字段删除相应的记录
+        /// 此函数负责更新数据库中的现有记录。它会自动处理以下逻辑：
+        /// - 如果更新时间戳未设置，则设置当前时间为更新时间
+        /// - 更新完成后，重新查询并返回更新后的完整记录
+        ///
+        /// 如果不希望自动设置修改时间，请使用 `update_keep_timestamp`
         ///
         /// ## 参数
-        /// * `active_model` - 包含待删除数据的 ActiveModel 实例
+        /// * `active_model` - 包含待更新数据的 ActiveModel 实例
         /// * `db` - 数据库连接 trait 对象
         ///
         /// ## 返回值
-        /// 如果删除成功则返回 Ok(())，如果删除失败则返回相应的错误信息
-        pub async fn delete<C>(active_model: ActiveModel, db: &C) -> Result<sea_orm::DeleteResult, DaoError>
+        /// 返回更新后的完整 Model 实例，如果更新失败则返回相应的错误信息
+        pub async fn update<C>(mut active_model: ActiveModel, db: &C) -> Result<Model, DaoError>
         where
             C: ConnectionTrait,
         {
+            Self::fill_update_fields(&mut active_model, false)?;
+            // 执行数据库更新操作
             active_model
-                .delete(db)
+                .update(db)
                 .await
                 .map_err(|e| DaoError::parse_db_err(e))
         }
     });
 
-    // 生成delete_by_condition方法
+    // 生成update_keep_timestamp方法
     generated_members.push(quote! {
-        /// # 删除记录
+        /// # 更新记录(不自动设置修改时间)
         ///
-        /// 根据提供的查询参数删除数据库中的记录
+        /// 与 `update` 相同，但修改时间未设置时不会自动设置为当前时间，
+        /// 适用于不应被视为用户修改的系统修正等场景(如需指定修改时间，可在 `active_model` 中设置)
         ///
         /// ## 参数
-        /// - `condition`: 查询条件
-        /// - `db`: 数据库连接，如果未提供则使用全局数据库连接
+        /// * `active_model` - 包含待更新数据的 ActiveModel 实例
+        /// * `db` - 数据库连接 trait 对象
         ///
         /// ## 返回值
-        /// - `Result<DeleteResult, DaoError>` - 删除结果
-        pub async fn delete_by_condition<C>(
-            condition: Condition,
-            db: &C,
-        ) -> Result<DeleteResult, DaoError>
+        /// 返回更新后的完整 Model 实例，如果更新失败则返回相应的错误信息
+        pub async fn update_keep_timestamp<C>(mut active_model: ActiveModel, db: &C) -> Result<Model, DaoError>
         where
             C: ConnectionTrait,
         {
-            Entity::delete_many()
-                .filter(condition)
-                .exec(db)
+            Self::fill_update_fields(&mut active_model, true)?;
+            // 执行数据库更新操作
+            active_model
+                .update(db)
                 .await
                 .map_err(|e| DaoError::parse_db_err(e))
         }
     });
 
+    // 生成delete方法及delete_by_condition方法
+    if soft_delete.is_some() {
+        generated_members.push(quote! {
+            /// # 删除记录(软删除)
+            ///
+            /// 此函数负责根据ID软删除相应的记录，设置删除时间和修改时间为当前时间，
+            /// 修改者取自 `active_model` 中的 `updator_id`，已删除的记录不会被再次删除
+            ///
+            /// ## 参数
+            /// * `active_model` - 包含待删除数据的 ActiveModel 实例(必须设置id)
+            /// * `db` - 数据库连接 trait 对象
+            ///
+            /// ## 返回值
+            /// 如果删除成功则返回删除的记录数，如果删除失败则返回相应的错误信息
+            pub async fn delete<C>(mut active_model: ActiveModel, db: &C) -> Result<DeleteResult, DaoError>
+            where
+                C: ConnectionTrait,
+            {
+                // 当前请求之后从主库读取，避免读不到刚写入的数据
+                ::robotech::ctx::mark_read_primary();
+                let id = match &active_model.id {
+                    ActiveValue::Set(id) | ActiveValue::Unchanged(id) => *id,
+                    ActiveValue::NotSet => return Err(DaoError::NotFound("软删除记录时未设置id".to_string())),
+                };
+                // 保护创建者信息不能被修改
+                active_model.creator_id = ActiveValue::NotSet;
+                active_model.create_timestamp = ActiveValue::NotSet;
+                let now = ::robotech::__private::wheel_rs::time_utils::now_ts()? as i64;
+                active_model.delete_timestamp = ActiveValue::set(Some(now));
+                active_model.update_timestamp = ActiveValue::set(now);
+                Entity::update_many()
+                    .set(active_model)
+                    .filter(::robotech::__private::sea_orm::ColumnTrait::eq(&Column::Id, id))
+                    #not_deleted
+                    .exec(db)
+                    .await
+                    .map(|result| DeleteResult { rows_affected: result.rows_affected })
+                    .map_err(|e| DaoError::parse_db_err(e))
+            }
+        });
+
+        generated_members.push(quote! {
+            /// # 删除记录(软删除)
+            ///
+            /// 根据提供的查询条件软删除数据库中的记录，设置删除时间和修改时间为当前时间，并记录修改者
+            ///
+            /// ## 参数
+            /// - `condition`: 查询条件
+            /// - `updator_id`: 修改者(执行删除的用户)ID
+            /// - `db`: 数据库连接，如果未提供则使用全局数据库连接
+            ///
+            /// ## 返回值
+            /// - `Result<DeleteResult, DaoError>` - 删除结果
+            pub async fn delete_by_condition<C>(
+                condition: Condition,
+                updator_id: u64,
+                db: &C,
+            ) -> Result<DeleteResult, DaoError>
+            where
+                C: ConnectionTrait,
+            {
+                // 当前请求之后从主库读取，避免读不到刚写入的数据
+                ::robotech::ctx::mark_read_primary();
+                let now = ::robotech::__private::wheel_rs::time_utils::now_ts()? as i64;
+                Entity::update_many()
+                    .col_expr(Column::DeleteTimestamp, ::robotech::__private::sea_orm::sea_query::Expr::value(now))
+                    .col_expr(Column::UpdateTimestamp, ::robotech::__private::sea_orm::sea_query::Expr::value(now))
+                    .col_expr(Column::UpdatorId, ::robotech::__private::sea_orm::sea_query::Expr::value(updator_id as i64))
+                    .filter(condition)
+                    #not_deleted
+                    .exec(db)
+                    .await
+                    .map(|result| DeleteResult { rows_affected: result.rows_affected })
+                    .map_err(|e| DaoError::parse_db_err(e))
+            }
+        });
+
+        generated_members.push(quote! {
+            /// # 删除记录并记录修改者(供 `svc` 宏生成的方法调用)
+            ///
+            /// 软删除时调用 `delete_by_condition` 并记录修改者
+            #[doc(hidden)]
+            pub async fn delete_by_condition_with_updator<C>(
+                condition: Condition,
+                updator_id: u64,
+                db: &C,
+            ) -> Result<DeleteResult, DaoError>
+            where
+                C: ConnectionTrait,
+            {
+                Self::delete_by_condition(condition, updator_id, db).await
+            }
+        });
+    } else {
+        generated_members.push(quote! {
+            /// # 删除记录
+            ///
+            /// 此函数负责根据关键字段删除相应的记录
+            ///
+            /// ## 参数
+            /// * `active_model` - 包含待删除数据的 ActiveModel 实例
+            /// * `db` - 数据库连接 trait 对象
+            ///
+            /// ## 返回值
+            /// 如果删除成功则返回 Ok(())，如果删除失败则返回相应的错误信息
+            pub async fn delete<C>(active_model: ActiveModel, db: &C) -> Result<::robotech::__private::sea_orm::DeleteResult, DaoError>
+            where
+                C: ConnectionTrait,
+            {
+                // 当前请求之后从主库读取，避免读不到刚写入的数据
+                ::robotech::ctx::mark_read_primary();
+                active_model
+                    .delete(db)
+                    .await
+                    .map_err(|e| DaoError::parse_db_err(e))
+            }
+        });
+
+        // 生成delete_by_condition方法
+        generated_members.push(quote! {
+            /// # 删除记录
+            ///
+            /// 根据提供的查询参数删除数据库中的记录
+            ///
+            /// ## 参数
+            /// - `condition`: 查询条件
+            /// - `db`: 数据库连接，如果未提供则使用全局数据库连接
+            ///
+            /// ## 返回值
+            /// - `Result<DeleteResult, DaoError>` - 删除结果
+            pub async fn delete_by_condition<C>(
+                condition: Condition,
+                db: &C,
+            ) -> Result<DeleteResult, DaoError>
+            where
+                C: ConnectionTrait,
+            {
+                // 当前请求之后从主库读取，避免读不到刚写入的数据
+                ::robotech::ctx::mark_read_primary();
+                Entity::delete_many()
+                    .filter(condition)
+                    .exec(db)
+                    .await
+                    .map_err(|e| DaoError::parse_db_err(e))
+            }
+        });
+
+        generated_members.push(quote! {
+            /// # 删除记录并记录修改者(供 `svc` 宏生成的方法调用)
+            ///
+            /// 物理删除时不记录修改者，直接调用 `delete_by_condition`
+            #[doc(hidden)]
+            pub async fn delete_by_condition_with_updator<C>(
+                condition: Condition,
+                _updator_id: u64,
+                db: &C,
+            ) -> Result<DeleteResult, DaoError>
+            where
+                C: ConnectionTrait,
+            {
+                Self::delete_by_condition(condition, db).await
+            }
+        });
+    }
+
     // 生成get_by_id方法
     generated_members.push(quote! {
         /// # 根据ID查询相应记录
@@ -414,6 +691,7 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
             C: ConnectionTrait,
         {
             Entity::find_by_id(id as i64)
+                #not_deleted
                 .one(db)
                 .await
                 .map_err(|e| DaoError::parse_db_err(e))
@@ -437,6 +715,7 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
             C: ConnectionTrait,
         {
             Entity::find()
+                #not_deleted
                 .filter(condition)
                 .one(db)
                 .await
@@ -461,13 +740,41 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
         where
             C: ConnectionTrait,
         {
-            add_order_by(Entity::find().filter(condition), order_by)?
+            add_order_by(Entity::find()#not_deleted.filter(condition), order_by)?
                 .all(db)
                 .await
                 .map_err(DaoError::from)
         }
     });
 
+    // 生成stream_by_condition方法
+    generated_members.push(quote! {
+        /// # 以流的方式查询记录
+        ///
+        /// 根据提供的查询条件逐条读取数据库中的记录，不会一次性加载到内存中，用于导出等需要遍历所有记录的场景
+        ///
+        /// ## 参数
+        /// - `condition`: 查询条件
+        /// - `order_by`: 排序字段
+        /// - `db`: 数据库连接(流在读取完之前会一直占用该连接)
+        ///
+        /// ## 返回值
+        /// - `Result<impl ModelStream<Model>, DaoError>` - 逐条返回Model的流
+        pub async fn stream_by_condition<'a, C>(
+            condition: Condition,
+            order_by: &Option<String>,
+            db: &'a C
+        ) -> Result<impl ::robotech::dao::ModelStream<Model> + 'a, DaoError>
+        where
+            C: ConnectionTrait + ::robotech::__private::sea_orm::StreamTrait + Send,
+        {
+            add_order_by(Entity::find()#not_deleted.filter(condition), order_by)?
+                .stream(db)
+                .await
+                .map_err(DaoError::from)
+        }
+    });
+
     // 生成page_by_condition方法
     generated_members.push(quote! {
         /// # 分页查询记录列表
@@ -478,7 +785,7 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
         /// - `condition`: 查询条件
         /// - `order_by`: 排序字段
         /// - `page_num`: 当前页码
-        /// - `page_size`: 每页大小
+        /// - `page_size`: 每页大小(为0时使用默认每页大小，超过最大每页大小时按最大每页大小查询)
         /// - `db`: 数据库连接，如果未提供则使用全局数据库连接
         ///
         /// ## 返回值
@@ -496,7 +803,8 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
             if page_num < 1 {
                 page_num = 1;
             }
-            let paginator = add_order_by(Entity::find().filter(condition), order_by)?.paginate(db, page_size);
+            let page_size = resolve_page_size(Some(page_size));
+            let paginator = add_order_by(Entity::find()#not_deleted.filter(condition), order_by)?.paginate(db, page_size);
             let total  = paginator.num_items().await.map_err(DaoError::from)?;
             if total == 0 {
                 return Ok((1, 0, vec![]));
@@ -510,6 +818,32 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
         }
     });
 
+    // 生成list方法
+    generated_members.push(quote! {
+        /// # 分页列出记录
+        ///
+        /// 不带查询条件，按ID升序分页列出记录
+        ///
+        /// ## 参数
+        /// - `page`: 页码(从1开始，为0时按第1页查询)
+        /// - `page_size`: 每页大小(为0时返回错误，超过最大每页大小时按最大每页大小查询)
+        /// - `db`: 数据库连接
+        ///
+        /// ## 返回值
+        /// - `Result<(Vec<Model>, u64), DaoError>` - 当前页的记录列表及总记录数
+        pub async fn list<C>(page: u64, page_size: u64, db: &C) -> Result<(Vec<Model>, u64), DaoError>
+        where
+            C: ConnectionTrait,
+        {
+            let page_size = check_page_size(page_size)?;
+            let select = ::robotech::__private::sea_orm::QueryOrder::order_by_asc(Entity::find()#not_deleted, Column::Id);
+            let paginator = select.paginate(db, page_size);
+            let total = paginator.num_items().await.map_err(DaoError::from)?;
+            let models = paginator.fetch_page(page.max(1) - 1).await.map_err(DaoError::from)?;
+            Ok((models, total))
+        }
+    });
+
     // 生成also_related相关方法
     if !related_tables.is_empty() {
         // 从 related_tables 中提取表名
@@ -580,6 +914,7 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
                 C: ConnectionTrait,
             {
                 Entity::find_by_id(id as i64)
+                    #not_deleted
                     #(#find_also_related_calls)*
                     .one(db)
                     .await
@@ -593,9 +928,17 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
         })
     }
 
+    // 记录方法耗时
+    if timing {
+        generated_members = generated_members
+            .into_iter()
+            .map(|member| wrap_timing(&struct_name_str, member))
+            .collect();
+    }
+
     let expanded = quote! {
-        use robotech::dao::{add_order_by, DaoError};
-        use sea_orm::{
+        use ::robotech::dao::{add_order_by, check_page_size, resolve_page_size, DaoError};
+        use ::robotech::__private::sea_orm::{
             ActiveModelTrait, ActiveValue, Condition, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, DeleteResult
         };
 
@@ -607,6 +950,8 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
 
         #generated_foreign_keys
 
+        #generated_soft_delete_check
+
         #input
 
         impl #struct_name {
@@ -619,3 +964,143 @@ pub(super) fn dao_macro(args: DaoArgs, input: ItemStruct) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// 包装生成的方法体，在方法结束时以debug级别记录耗时，如: `UserDao::get_by_id 耗时: 3ms`
+///
+/// 不是异步方法的成员(如常量、同步的辅助方法)原样返回
+fn wrap_timing(struct_name_str: &str, member: TokenStream) -> TokenStream {
+    let mut method = match syn::parse2::<ImplItemFn>(member.clone()) {
+        Ok(method) if method.sig.asyncness.is_some() => method,
+        _ => return member,
+    };
+    let timing_log = format!("{struct_name_str}::{} 耗时: {{}}ms", method.sig.ident);
+    let fn_block = &method.block;
+    method.block = syn::parse_quote! {
+        {
+            let start = std::time::Instant::now();
+            let result = async #fn_block.await;
+            ::robotech::__private::tracing::debug!(#timing_log, start.elapsed().as_millis());
+            result
+        }
+    };
+    quote! { #method }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::ToTokens;
+    use std::collections::HashMap;
+    use syn::{ImplItem, Item, parse_quote};
+
+    /// 展开 `dao` 宏，返回生成的方法(方法名 -> 方法)
+    fn expand_methods(args: TokenStream) -> HashMap<String, ImplItemFn> {
+        let args: DaoArgs = syn::parse2(args).unwrap();
+        let input: ItemStruct = parse_quote! { pub struct UserDao; };
+        let file: syn::File = syn::parse2(dao_macro(args, input)).unwrap();
+        file.items
+            .into_iter()
+            .filter_map(|item| match item {
+                Item::Impl(item_impl) => Some(item_impl.items),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|item| match item {
+                ImplItem::Fn(method) => Some((method.sig.ident.to_string(), method)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn first_stmt(method: &ImplItemFn) -> String {
+        method.block.stmts[0].to_token_stream().to_string()
+    }
+
+    #[test]
+    fn update_sets_timestamp_and_update_keep_timestamp_does_not() {
+        let methods = expand_methods(quote! {});
+        assert_eq!(
+            first_stmt(&methods["update"]),
+            quote! { Self::fill_update_fields(&mut active_model, false)?; }.to_string()
+        );
+        assert_eq!(
+            first_stmt(&methods["update_keep_timestamp"]),
+            quote! { Self::fill_update_fields(&mut active_model, true)?; }.to_string()
+        );
+    }
+
+    #[test]
+    fn fill_update_fields_checks_keep_timestamp() {
+        let methods = expand_methods(quote! {});
+        let fill_update_fields = &methods["fill_update_fields"];
+        assert_eq!(
+            fill_update_fields.sig.inputs.to_token_stream().to_string(),
+            quote! { active_model: &mut ActiveModel, keep_timestamp: bool }.to_string()
+        );
+        let set_update_timestamp = fill_update_fields
+            .block
+            .stmts
+            .iter()
+            .find_map(|stmt| match stmt {
+                syn::Stmt::Expr(syn::Expr::If(expr_if), _) => Some(expr_if),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            set_update_timestamp.cond.to_token_stream().to_string(),
+            quote! { !keep_timestamp && active_model.update_timestamp == ActiveValue::NotSet }
+                .to_string()
+        );
+    }
+
+    fn input_names(method: &ImplItemFn) -> Vec<String> {
+        method
+            .sig
+            .inputs
+            .iter()
+            .map(|input| match input {
+                syn::FnArg::Typed(pat_type) => pat_type.pat.to_token_stream().to_string(),
+                syn::FnArg::Receiver(_) => "self".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn delete_by_condition_takes_updator_id_only_for_soft_delete() {
+        let methods = expand_methods(quote! {});
+        assert_eq!(
+            input_names(&methods["delete_by_condition"]),
+            ["condition", "db"]
+        );
+        assert_eq!(
+            input_names(&methods["delete_by_condition_with_updator"]),
+            ["condition", "_updator_id", "db"]
+        );
+
+        let methods = expand_methods(quote! { soft_delete });
+        assert_eq!(
+            input_names(&methods["delete_by_condition"]),
+            ["condition", "updator_id", "db"]
+        );
+        assert_eq!(
+            input_names(&methods["delete_by_condition_with_updator"]),
+            ["condition", "updator_id", "db"]
+        );
+    }
+
+    #[test]
+    fn write_methods_mark_read_primary() {
+        let mark_read_primary = quote! { ::robotech::ctx::mark_read_primary(); }.to_string();
+        for args in [quote! {}, quote! { soft_delete }] {
+            let methods = expand_methods(args);
+            for name in [
+                "fill_insert_fields",
+                "fill_update_fields",
+                "delete",
+                "delete_by_condition",
+            ] {
+                assert_eq!(first_stmt(&methods[name]), mark_read_primary, "{name}");
+            }
+        }
+    }
+}
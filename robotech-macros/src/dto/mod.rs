@@ -81,6 +81,7 @@ pub fn crud_dto_macro(input: ItemStruct) -> TokenStream {
         #[serde(default, rename_all = "camelCase")]
         #[owned_into(ActiveModel)]
         #[ghosts(
+            creator_id: Default::default(),
             updator_id: Default::default(),
             create_timestamp: Default::default(),
             update_timestamp: Default::default(),
@@ -92,9 +93,6 @@ pub fn crud_dto_macro(input: ItemStruct) -> TokenStream {
             #[builder(default, setter(strip_option))]
             pub id: Option<u64>,
             #add_fields
-            #[serde(skip_deserializing)]
-            #[into(creator_id, ActiveValue::Set(~ as i64))]
-            pub _current_user_id: u64,
         }
 
         // ModifyDto
@@ -103,6 +101,7 @@ pub fn crud_dto_macro(input: ItemStruct) -> TokenStream {
         #[owned_into(ActiveModel)]
         #[ghosts(
             creator_id: Default::default(),
+            updator_id: Default::default(),
             create_timestamp: Default::default(),
             update_timestamp: Default::default(),
         )]
@@ -114,9 +113,6 @@ pub fn crud_dto_macro(input: ItemStruct) -> TokenStream {
             #[serde(with = "u64_option_serde")]
             pub id: Option<u64>,
             #modify_fields
-            #[serde(skip_deserializing)]
-            #[into(updator_id, ActiveValue::Set(~ as i64))]
-            pub _current_user_id: u64,
         }
 
         // SaveDto
@@ -130,8 +126,6 @@ pub fn crud_dto_macro(input: ItemStruct) -> TokenStream {
             #[builder(default, setter(strip_option))]
             pub id: Option<u64>,
             #save_fields
-            #[serde(skip_deserializing)]
-            pub _current_user_id: u64,
         }
 
         // QueryDto
@@ -450,7 +444,7 @@ fn generate_serde_attr(field: &Field) -> Option<Attribute> {
 }
 
 /// 判断是否是Option类型
-fn is_option_type(ty: &syn::Type) -> bool {
+pub(crate) fn is_option_type(ty: &syn::Type) -> bool {
     if let syn::Type::Path(type_path) = ty {
         if let Some(segment) = type_path.path.segments.last() {
             return segment.ident == "Option";
@@ -524,7 +518,7 @@ fn add_query_field_to_condition_tokens(field: &Field) -> TokenStream {
     .into()
 }
 
-fn get_value_token_stream(type_name: &str) -> TokenStream {
+pub(crate) fn get_value_token_stream(type_name: &str) -> TokenStream {
     match type_name {
         "u8" => {
             quote! { *v as i8 }
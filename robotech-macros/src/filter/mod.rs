@@ -0,0 +1,307 @@
+use crate::dto::{get_value_token_stream, is_option_type};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Field, Fields, LitStr, Path};
+use wheel_rs::str_utils::{CamelFormat, snake_to_pascal, split_camel_case};
+
+/// 字段的过滤操作
+struct FilterOp {
+    /// 操作名称(eq/ne/like/gt/gte/lt/lte)
+    op: String,
+    /// 比较的列名(蛇形命名，默认为字段名)
+    column: String,
+}
+
+/// QueryFilter派生宏：根据字段上的 `#[filter(...)]` 属性生成 `to_condition` 方法
+pub(crate) fn query_filter_macro(input: DeriveInput) -> TokenStream {
+    let struct_name = &input.ident;
+
+    let column_path = match parse_column_path(&input) {
+        Ok(column_path) => column_path,
+        Err(e) => return e.to_compile_error(),
+    };
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named_fields) => &named_fields.named,
+            _ => {
+                return syn::Error::new_spanned(struct_name, "QueryFilter只支持具名字段的结构体")
+                    .to_compile_error();
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(struct_name, "QueryFilter只支持结构体")
+                .to_compile_error();
+        }
+    };
+
+    let mut condition_tokens = Vec::new();
+    for field in fields {
+        let filter_ops = match parse_filter_ops(field) {
+            Ok(filter_ops) => filter_ops,
+            Err(e) => return e.to_compile_error(),
+        };
+        for filter_op in filter_ops {
+            match add_filter_op_to_condition_tokens(field, &column_path, &filter_op) {
+                Ok(tokens) => condition_tokens.push(tokens),
+                Err(e) => return e.to_compile_error(),
+            }
+        }
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// 根据字段的 `#[filter(...)]` 属性生成查询条件(值为None的字段不参与过滤)
+            pub fn to_condition(&self) -> ::robotech::__private::sea_orm::Condition {
+                let mut condition = ::robotech::__private::sea_orm::Condition::all();
+                #(#condition_tokens)*
+                condition
+            }
+        }
+    }
+}
+
+/// 解析实体列的路径
+///
+/// 优先使用结构体上的 `#[filter(column = "crate::model::xxx::Column")]`，
+/// 否则根据结构体名称推导，如 `SysUserQuery` -> `crate::model::sys_user::Column`
+fn parse_column_path(input: &DeriveInput) -> syn::Result<Path> {
+    for attr in &input.attrs {
+        if !attr.path().is_ident("filter") {
+            continue;
+        }
+        let mut column_path = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("column") {
+                let value: LitStr = meta.value()?.parse()?;
+                column_path = Some(value.parse::<Path>()?);
+                Ok(())
+            } else {
+                Err(meta.error("Unknown argument, expected `column = \"...\"`"))
+            }
+        })?;
+        if let Some(column_path) = column_path {
+            return Ok(column_path);
+        }
+    }
+
+    let struct_name = &input.ident;
+    let struct_name_str = struct_name.to_string();
+    let entity_name = ["QueryDto", "Query", "Dto"]
+        .iter()
+        .find_map(|suffix| struct_name_str.strip_suffix(suffix))
+        .filter(|entity_name| !entity_name.is_empty())
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                struct_name,
+                "Struct name must end with 'QueryDto', 'Query' or 'Dto', or specify #[filter(column = \"...\")]",
+            )
+        })?;
+    let entity_name_split = split_camel_case(entity_name, CamelFormat::Upper).map_err(|_| {
+        syn::Error::new_spanned(struct_name, "Struct name must be a valid upper camel case")
+    })?;
+    let module_name = format_ident!("{}", entity_name_split.join("_").to_lowercase());
+    Ok(syn::parse_quote!(crate::model::#module_name::Column))
+}
+
+/// 解析字段上的 `#[filter(...)]` 属性
+///
+/// 支持 `eq`、`ne`、`like`、`gt`、`gte`、`lt`、`lte`，
+/// 可以指定比较的列名，如 `#[filter(gte = "create_timestamp")]`，不指定则为字段名
+fn parse_filter_ops(field: &Field) -> syn::Result<Vec<FilterOp>> {
+    let field_name = field.ident.as_ref().unwrap().to_string();
+    let mut filter_ops = Vec::new();
+    for attr in &field.attrs {
+        if !attr.path().is_ident("filter") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            let op = meta
+                .path
+                .get_ident()
+                .map(|ident| ident.to_string())
+                .unwrap_or_default();
+            if !matches!(
+                op.as_str(),
+                "eq" | "ne" | "like" | "gt" | "gte" | "lt" | "lte"
+            ) {
+                return Err(
+                    meta.error("Unknown filter, expected one of: eq, ne, like, gt, gte, lt, lte")
+                );
+            }
+            let column = if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<LitStr>()?.value()
+            } else {
+                field_name.clone()
+            };
+            filter_ops.push(FilterOp { op, column });
+            Ok(())
+        })?;
+    }
+    Ok(filter_ops)
+}
+
+/// 生成单个过滤操作添加到查询条件的代码
+fn add_filter_op_to_condition_tokens(
+    field: &Field,
+    column_path: &Path,
+    filter_op: &FilterOp,
+) -> syn::Result<TokenStream> {
+    let field_name = field.ident.as_ref().unwrap();
+    let column_name = format_ident!("{}", snake_to_pascal(&filter_op.column));
+    let ty = &field.ty;
+
+    // 字段值的类型名称(Option<T>取T)
+    let value_type = if is_option_type(ty) {
+        get_inner_type(ty)
+    } else {
+        Some(ty)
+    };
+    let value_type_name = match value_type {
+        Some(syn::Type::Path(type_path)) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        syn::Error::new_spanned(
+            ty,
+            format!(
+                "字段 '{}' 的类型无法识别，请使用基本类型或 Option<T> 包裹的基本类型",
+                field_name
+            ),
+        )
+    })?;
+    let v = get_value_token_stream(&value_type_name);
+
+    let column = quote! { &#column_path::#column_name };
+    let expr = match filter_op.op.as_str() {
+        "eq" => quote! { ::robotech::__private::sea_orm::ColumnTrait::eq(#column, #v) },
+        "ne" => quote! { ::robotech::__private::sea_orm::ColumnTrait::ne(#column, #v) },
+        "like" => quote! { ::robotech::__private::sea_orm::ColumnTrait::contains(#column, v) },
+        "gt" => quote! { ::robotech::__private::sea_orm::ColumnTrait::gt(#column, #v) },
+        "gte" => quote! { ::robotech::__private::sea_orm::ColumnTrait::gte(#column, #v) },
+        "lt" => quote! { ::robotech::__private::sea_orm::ColumnTrait::lt(#column, #v) },
+        "lte" => quote! { ::robotech::__private::sea_orm::ColumnTrait::lte(#column, #v) },
+        _ => unreachable!(),
+    };
+
+    Ok(if is_option_type(ty) {
+        quote! {
+            if let Some(v) = self.#field_name.as_ref() {
+                condition = condition.add(#expr);
+            }
+        }
+    } else {
+        quote! {
+            {
+                let v = &self.#field_name;
+                condition = condition.add(#expr);
+            }
+        }
+    })
+}
+
+/// 获取Option<T>中的T
+fn get_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    if let syn::Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first()
+    {
+        return Some(inner_ty);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::ToTokens;
+    use syn::{ImplItem, ImplItemFn, Item, parse_quote};
+
+    /// 展开 `QueryFilter` 派生宏，返回生成的 `to_condition` 方法
+    fn expand_to_condition(input: DeriveInput) -> ImplItemFn {
+        let file: syn::File = syn::parse2(query_filter_macro(input)).unwrap();
+        file.items
+            .into_iter()
+            .find_map(|item| match item {
+                Item::Impl(item_impl) => item_impl.items.into_iter().find_map(|item| match item {
+                    ImplItem::Fn(method) => Some(method),
+                    _ => None,
+                }),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn to_condition_uses_reexported_sea_orm() {
+        let to_condition = expand_to_condition(parse_quote! {
+            pub struct SysUserQueryDto {
+                #[filter(eq)]
+                pub id: Option<u64>,
+                #[filter(like)]
+                pub name: Option<String>,
+                #[filter(gte = "create_timestamp")]
+                pub start_timestamp: Option<i64>,
+                pub page_num: Option<u64>,
+            }
+        });
+        assert_eq!(
+            to_condition.sig.output.to_token_stream().to_string(),
+            quote! { -> ::robotech::__private::sea_orm::Condition }.to_string()
+        );
+        let stmts: Vec<String> = to_condition
+            .block
+            .stmts
+            .iter()
+            .map(|stmt| stmt.to_token_stream().to_string())
+            .collect();
+        let expected = [
+            quote! { let mut condition = ::robotech::__private::sea_orm::Condition::all(); },
+            quote! {
+                if let Some(v) = self.id.as_ref() {
+                    condition = condition.add(::robotech::__private::sea_orm::ColumnTrait::eq(&crate::model::sys_user::Column::Id, *v as i64));
+                }
+            },
+            quote! {
+                if let Some(v) = self.name.as_ref() {
+                    condition = condition.add(::robotech::__private::sea_orm::ColumnTrait::contains(&crate::model::sys_user::Column::Name, v));
+                }
+            },
+            quote! {
+                if let Some(v) = self.start_timestamp.as_ref() {
+                    condition = condition.add(::robotech::__private::sea_orm::ColumnTrait::gte(&crate::model::sys_user::Column::CreateTimestamp, v));
+                }
+            },
+            quote! { condition },
+        ]
+        .map(|tokens| tokens.to_string());
+        assert_eq!(stmts, expected);
+    }
+
+    #[test]
+    fn column_attribute_overrides_derived_column_path() {
+        let to_condition = expand_to_condition(parse_quote! {
+            #[filter(column = "crate::model::user::Column")]
+            pub struct Criteria {
+                #[filter(ne)]
+                pub status: i16,
+            }
+        });
+        assert_eq!(
+            to_condition.block.stmts[1].to_token_stream().to_string(),
+            quote! {
+                {
+                    let v = &self.status;
+                    condition = condition.add(::robotech::__private::sea_orm::ColumnTrait::ne(&crate::model::user::Column::Status, v));
+                }
+            }
+            .to_string()
+        );
+    }
+}
@@ -2,19 +2,23 @@ mod cfg;
 mod dao;
 mod db;
 mod dto;
+mod filter;
 mod log;
 mod svc;
 mod vo;
 mod web;
 
-use crate::cfg::{WatchCfgFileArgs, watch_cfg_file_macro};
+use crate::cfg::{
+    WatchCfgFileArgs, WatchCfgRemoteArgs, watch_cfg_file_macro, watch_cfg_remote_macro,
+};
 use crate::dao::{DaoArgs, dao_macro};
 use crate::db::MigrateArgs;
 use crate::dto::crud_dto_macro;
+use crate::filter::query_filter_macro;
 use crate::log::{LogCallArgs, log_call_macro};
-use crate::svc::{DbUnwrapArgs, db_unwrap_macro, svc_macro};
+use crate::svc::{DbUnwrapArgs, SvcArgs, db_unwrap_macro, svc_macro};
 use crate::vo::vo_macro;
-use crate::web::{ApiDocArgs, RouterArgs, api_doc_macro, ctrl_macro, router_macro};
+use crate::web::{ApiDocArgs, CtrlArgs, RouterArgs, api_doc_macro, ctrl_macro, router_macro};
 use proc_macro::TokenStream;
 use syn::{DeriveInput, ItemFn, ItemStruct, parse_macro_input};
 
@@ -24,6 +28,20 @@ pub fn watch_cfg_file(args: TokenStream) -> TokenStream {
     watch_cfg_file_macro(args).into()
 }
 
+/// 监控远程配置，远程配置变化时执行代码块
+///
+/// # 使用示例
+/// ```
+/// watch_cfg_remote!("app", remote_cfg_source, Duration::from_secs(30), {
+///     // 重新加载配置
+/// });
+/// ```
+#[proc_macro]
+pub fn watch_cfg_remote(args: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as WatchCfgRemoteArgs);
+    watch_cfg_remote_macro(args).into()
+}
+
 /// 属性宏：在进入方法时使用 log 库记录方法名、参数及参数值
 ///
 /// # 使用示例
@@ -44,9 +62,40 @@ pub fn watch_cfg_file(args: TokenStream) -> TokenStream {
 /// fn risky_operation() {
 ///     // ...
 /// }
+///
+/// // 敏感参数脱敏，日志中记录为: login(username = "admin", password = <redacted>)
+/// #[log_call(level = info, skip = password)]
+/// fn login(username: &str, password: &str) {
+///     // ...
+/// }
+///
+/// // 只记录进入日志，并在退出时记录耗时及返回值
+/// // 退出日志如: ← 离开方法: query() 耗时: 12.3ms 返回: Ok(..)
+/// #[log_call(mode = enter, ret, timing)]
+/// async fn query(id: u64) -> Result<User, SvcError> {
+///     // ...
+/// }
+///
+/// // `?` 提前返回的错误也以 error 级别记录
+/// #[log_call(catch_err)]
+/// fn parse(text: &str) -> Result<u64, ParseIntError> {
+///     let value = text.trim().parse::<u64>()?;
+///     Ok(value * 2)
+/// }
 /// ```
 ///
 /// 支持的日志级别: trace, debug (默认), info, warn, error
+///
+/// `mode` 可选 enter、exit、both (默认)，`ret` 和 `timing` 为开关：
+/// `ret` 在退出时记录返回值(mode 为 enter 时也会记录)，`timing` 在退出时记录方法耗时(async 方法包含 await 的时间)
+///
+/// 返回值类型为 `Result` 时，方法体的值为 `Err` 时以 error 级别记录，`Ok` 仍使用指定的级别，
+/// 进入日志不受影响；预期会频繁返回 `Err` 的方法可以使用 `no_err` 关闭此行为。
+/// 方法体中 `?` 或 `return` 提前返回时不会记录退出日志，需要记录时使用 `catch_err`：
+/// 方法体会放在闭包(async 方法为 async 块)中执行，返回借用自参数的引用时可能无法编译
+///
+/// `skip` 可以重复多次，指定的参数只记录参数名，值显示为 `<redacted>`；
+/// 需要完全不记录的参数可在参数上标注 `#[skip_log]`
 #[proc_macro_attribute]
 pub fn log_call(args: TokenStream, input: TokenStream) -> TokenStream {
     // 解析属性参数
@@ -84,6 +133,40 @@ pub fn crud_dto(_args: TokenStream, input: TokenStream) -> TokenStream {
     crud_dto_macro(input).into()
 }
 
+/// 派生宏：根据查询DTO字段上的 `#[filter(...)]` 属性生成 `fn to_condition(&self) -> Condition`
+///
+/// 值为 `None` 的字段不参与过滤，没有 `#[filter(...)]` 属性的字段(如分页参数)会被忽略
+///
+/// # 使用示例
+/// ```
+/// #[derive(QueryFilter)]
+/// pub struct SysUserQuery {
+///     /// 名称(等于)
+///     #[filter(eq)]
+///     pub name: Option<String>,
+///     /// 备注(模糊匹配)
+///     #[filter(like)]
+///     pub remark: Option<String>,
+///     /// 创建时间范围
+///     #[filter(gte = "create_timestamp")]
+///     pub create_timestamp_start: Option<i64>,
+///     #[filter(lt = "create_timestamp")]
+///     pub create_timestamp_end: Option<i64>,
+///     /// 页码(不参与过滤)
+///     pub page: Option<u64>,
+/// }
+/// ```
+///
+/// 支持的过滤操作: eq, ne, like, gt, gte, lt, lte，`= "列名"` 指定比较的列(默认为字段名)
+///
+/// 实体列默认根据结构体名称推导(去掉 QueryDto/Query/Dto 后缀)，如 `SysUserQuery` ->
+/// `crate::model::sys_user::Column`，也可以通过 `#[filter(column = "...")]` 指定
+#[proc_macro_derive(QueryFilter, attributes(filter))]
+pub fn query_filter(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    query_filter_macro(input).into()
+}
+
 /// 属性宏：为 VO 结构体自动生成标准属性
 ///
 /// 此宏会自动为 VO 结构体添加以下属性和派生宏：
@@ -93,6 +176,10 @@ pub fn crud_dto(_args: TokenStream, input: TokenStream) -> TokenStream {
 /// - `#[serde(rename_all = "camelCase")]` - 驼峰命名
 /// - `#[serde_as]` - serde_with 支持
 ///
+/// 并生成 `FIELD_NAMES` 常量(序列化后的字段名，按声明顺序，字段上有 `#[serde(rename = "...")]` 时使用该名称)，可用作导出CSV的表头
+///
+/// 字段上的 `#[serde(...)]` 属性会保留(此时不再自动添加下面的序列化属性)
+///
 /// 同时会自动为无符号整型字段添加：
 /// - `#[from(~ as u64)]` 或 `#[from(~.to_string())]` - 根据字段名自动判断
 /// - `#[serde_as(as = "String")]` - 避免 JS 精度丢失
@@ -149,6 +236,18 @@ pub fn vo(_args: TokenStream, input: TokenStream) -> TokenStream {
 /// // 只生成查询方法
 /// #[dao(get_by_id)]
 /// pub struct MyDao;
+///
+/// // 以debug级别记录每个方法的耗时，如: `MyDao::get_by_id 耗时: 3ms`
+/// #[dao(timing)]
+/// pub struct MyDao;
+///
+/// // 软删除，Model中必须有 `delete_timestamp: Option<i64>` 字段，否则编译错误
+/// #[dao(soft_delete)]
+/// pub struct MyDao;
+///
+/// // 指定 batch_insert 每批插入的记录数(默认1000)
+/// #[dao(batch_insert_size: 500)]
+/// pub struct MyDao;
 /// ```
 ///
 /// 支持的方法选项:
@@ -157,6 +256,8 @@ pub fn vo(_args: TokenStream, input: TokenStream) -> TokenStream {
 /// - delete: 生成删除方法
 /// - get_by_id: 生成根据ID查询方法
 /// - all: 生成所有方法
+/// - timing: 记录每个生成方法的耗时
+/// - soft_delete: `delete`/`delete_by_condition` 改为设置删除时间并记录修改者(`delete_by_condition` 增加 `updator_id` 参数)，查询方法过滤掉已删除的记录
 #[proc_macro_attribute]
 pub fn dao(args: TokenStream, input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(args as DaoArgs);
@@ -181,6 +282,48 @@ pub fn dao(args: TokenStream, input: TokenStream) -> TokenStream {
 ///             .extra(one.map(|value| OssBucketVo::from(value))),
 ///     )
 /// }
+///
+/// // 未传入数据库连接时，在全局数据库连接上开启事务执行，返回 Ok 时提交，返回 Err 时回滚
+/// // (传入了数据库连接时直接使用，由调用方负责事务)
+/// #[db_unwrap(transaction_required)]
+/// pub async fn transfer<C>(dto: TransferDto, db: Option<&C>) -> Result<Ro<()>, SvcError>
+/// where
+///     C: ConnectionTrait,
+/// {
+///     AccountDao::update(dto.from_active_model(), db).await?;
+///     AccountDao::update(dto.to_active_model(), db).await?;
+///     Ok(Ro::success("转账成功".to_string()))
+/// }
+///
+/// // 只读方法：未传入数据库连接时使用读数据库连接(`get_read_db_conn`)，
+/// // 配置了读库(`replica`)时从读库读取，但当前请求中写入过数据后改为从主库读取
+/// #[db_unwrap(read)]
+/// pub async fn list_by_name<C>(name: &str, db: Option<&C>) -> Result<Ro<Vec<OssBucketVo>>, SvcError>
+/// where
+///     C: ConnectionTrait,
+/// {
+///     // 查询
+/// }
+///
+/// // 指定事务隔离级别(read_committed, repeatable_read, serializable)，不指定则使用数据库连接的默认隔离级别
+/// #[db_unwrap(transaction_required, isolation = "serializable")]
+/// pub async fn deduct_stock<C>(id: u64, db: Option<&C>) -> Result<Ro<()>, SvcError>
+/// where
+///     C: ConnectionTrait,
+/// {
+///     // 读取-修改-写入
+/// }
+///
+/// // 成功返回(开启了事务时为提交事务)后执行闭包，参数为返回值 Ok 中的值的引用，
+/// // 可用于提交后再使缓存失效(访问参数的字段时需要标注参数类型)
+/// #[db_unwrap(transaction_required, after_commit = |_| ACCOUNT_CACHE.invalidate(id))]
+/// pub async fn freeze<C>(id: u64, db: Option<&C>) -> Result<Ro<()>, SvcError>
+/// where
+///     C: ConnectionTrait,
+/// {
+///     AccountDao::freeze(id, db).await?;
+///     Ok(Ro::success("冻结成功".to_string()))
+/// }
 /// ```
 /// 注意：用户代码中应该包含完整的返回逻辑
 #[proc_macro_attribute]
@@ -190,16 +333,50 @@ pub fn db_unwrap(args: TokenStream, input: TokenStream) -> TokenStream {
     db_unwrap_macro(args, input).into()
 }
 
+/// 属性宏：为Svc结构体生成标准的CRUD方法
+///
+/// # 使用示例
+/// ```
+/// #[svc]
+/// pub struct SysUserSvc;
+///
+/// // 在进程内缓存get_by_id的结果60秒(最多缓存500条，默认1000条)，修改及删除时按ID失效
+/// #[svc(cache_get_by_id = "60s", cache_capacity = 500)]
+/// pub struct SysDictSvc;
+///
+/// // 默认从结构体名称推断实体名称(去掉结尾的Svc)，不符合时可以指定实体名称，
+/// // 以下使用 UserDao、UserVo、UserAddDto 等
+/// #[svc(entity = "User")]
+/// pub struct UserAuthSvc;
+/// ```
 #[proc_macro_attribute]
-pub fn svc(_args: TokenStream, input: TokenStream) -> TokenStream {
+pub fn svc(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as SvcArgs);
     let input = parse_macro_input!(input as ItemStruct);
-    svc_macro(input).into()
+    svc_macro(args, input).into()
 }
 
+/// 属性宏：为Ctrl结构体生成标准的CRUD接口
+///
+/// # 使用示例
+/// ```
+/// #[ctrl]
+/// pub struct SysUserCtrl;
+///
+/// // 添加成功时返回 201 Created 及 Location: /sys/user/{id}
+/// #[ctrl(created)]
+/// pub struct SysUserCtrl;
+///
+/// // 额外生成以CSV格式流式导出查询结果的接口 export_by_query_dto
+/// // (需要在 #[router(crud, export)] 中添加路由 GET /sys/user/export)
+/// #[ctrl(export)]
+/// pub struct SysUserCtrl;
+/// ```
 #[proc_macro_attribute]
-pub fn ctrl(_args: TokenStream, input: TokenStream) -> TokenStream {
+pub fn ctrl(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as CtrlArgs);
     let input = parse_macro_input!(input as ItemStruct);
-    ctrl_macro(input).into()
+    ctrl_macro(args, input).into()
 }
 
 #[proc_macro_attribute]
@@ -209,6 +386,18 @@ pub fn router(args: TokenStream, input: TokenStream) -> TokenStream {
     router_macro(args, input).into()
 }
 
+/// 属性宏：生成ApiDoc并注册到Swagger UI
+///
+/// # 使用示例
+/// ```
+/// // 包含 #[ctrl] 生成的所有接口(请求体、路径参数、查询参数及 Ro<Vo> 响应)
+/// #[api_doc]
+/// pub struct SysUserApiDoc;
+///
+/// // 只包含指定的接口(可以包含自己编写的接口)
+/// #[api_doc(get_by_id, page_by_query_dto, reset_password)]
+/// pub struct SysUserApiDoc;
+/// ```
 #[proc_macro_attribute]
 pub fn api_doc(args: TokenStream, input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(args as ApiDocArgs);
@@ -1,7 +1,7 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::parse::{Parse, ParseStream};
-use syn::{FnArg, ItemFn, Pat, PatType, Token};
+use syn::{FnArg, ItemFn, Pat, PatType, ReturnType, Token, Type};
 
 #[derive(PartialEq)]
 enum RecordMode {
@@ -16,39 +16,77 @@ pub(super) struct LogCallArgs {
     level: Ident,
     /// 记录模式：进入、退出、两者都记录
     mode: RecordMode,
+    /// 需要脱敏的参数名(日志中只记录参数名，值显示为 `<redacted>`)
+    skip: Vec<Ident>,
+    /// 退出时记录返回值(mode 不包含退出时也会记录)
+    ret: bool,
+    /// 退出时记录方法耗时
+    timing: bool,
+    /// 返回 `Err` 时不使用 error 级别记录(预期会返回错误且错误较多时使用)
+    no_err: bool,
+    /// 方法体放在闭包(async 方法为 async 块)中执行，`?` 及 `return` 提前返回的错误也会被记录
+    catch_err: bool,
 }
 
 impl Parse for LogCallArgs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        if input.is_empty() {
-            return Ok(LogCallArgs {
-                level: format_ident!("debug"),
-                mode: RecordMode::Both,
-            });
-        }
-
-        let _level_key: Ident = input.parse()?;
-        let _: Token![=] = input.parse()?;
-        let level: Ident = input.parse()?;
+        let mut level = format_ident!("debug");
+        let mut mode = RecordMode::Both;
+        let mut skip = Vec::new();
+        let mut ret = false;
+        let mut timing = false;
+        let mut no_err = false;
+        let mut catch_err = false;
 
-        let mode = if input.peek(Token![,]) {
-            let _: Token![,] = input.parse()?;
-            let mode_key: Ident = input.parse()?;
-            let _: Token![=] = input.parse()?;
-            let mode_ident: Ident = input.parse()?;
-            match mode_ident.to_string().to_lowercase().as_str() {
-                "enter" => RecordMode::Enter,
-                "exit" => RecordMode::Exit,
-                "both" => RecordMode::Both,
+        // 以逗号分隔的 key = value 或开关列表，如: level = info, mode = enter, skip = password, ret, timing, no_err, catch_err
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            match key.to_string().as_str() {
+                "ret" => ret = true,
+                "timing" => timing = true,
+                "no_err" => no_err = true,
+                "catch_err" => catch_err = true,
+                "level" | "mode" | "skip" => {
+                    let _: Token![=] = input.parse()?;
+                    let value: Ident = input.parse()?;
+                    match key.to_string().as_str() {
+                        "level" => level = value,
+                        "mode" => {
+                            mode = match value.to_string().to_lowercase().as_str() {
+                                "enter" => RecordMode::Enter,
+                                "exit" => RecordMode::Exit,
+                                "both" => RecordMode::Both,
+                                _ => {
+                                    return Err(syn::Error::new_spanned(key, "无效的 mode 参数"));
+                                }
+                            }
+                        }
+                        _ => skip.push(value),
+                    }
+                }
                 _ => {
-                    return Err(syn::Error::new_spanned(mode_key, "无效的 mode 参数"));
+                    return Err(syn::Error::new_spanned(
+                        key,
+                        "无效的参数，支持的参数: level, mode, skip, ret, timing, no_err, catch_err",
+                    ));
                 }
             }
-        } else {
-            RecordMode::Both
-        };
 
-        Ok(LogCallArgs { level, mode })
+            if input.is_empty() {
+                break;
+            }
+            let _: Token![,] = input.parse()?;
+        }
+
+        Ok(LogCallArgs {
+            level,
+            mode,
+            skip,
+            ret,
+            timing,
+            no_err,
+            catch_err,
+        })
     }
 }
 
@@ -59,6 +97,30 @@ fn is_axum_wrapper(type_str: &str) -> bool {
     normalized.contains("Path<") || normalized.contains("Json<") || normalized.contains("Query<")
 }
 
+/// 获取 Result 类型的返回值类型(类型名为 Result，如 Result<T, E>、std::result::Result<T, E>)
+fn result_return_type(output: &ReturnType) -> Option<&Type> {
+    let ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    let Type::Path(type_path) = &**ty else {
+        return None;
+    };
+    type_path
+        .path
+        .segments
+        .last()
+        .filter(|segment| segment.ident == "Result")
+        .map(|_| &**ty)
+}
+
+/// 类型中是否包含 `impl Trait`(变量的类型标注中不能使用)
+fn contains_impl_trait(ty: &Type) -> bool {
+    quote!(#ty)
+        .to_string()
+        .split_whitespace()
+        .any(|token| token == "impl")
+}
+
 /// 检查参数是否带有 #[skip_log] 属性
 fn has_skip_log(pat_type: &PatType) -> bool {
     pat_type
@@ -71,6 +133,11 @@ pub(super) fn log_call_macro(args: LogCallArgs, mut input: ItemFn) -> TokenStrea
     let LogCallArgs {
         level: log_level,
         mode: record_mode,
+        skip,
+        ret,
+        timing,
+        no_err,
+        catch_err,
     } = args;
 
     let fn_attrs = &input.attrs;
@@ -82,6 +149,18 @@ pub(super) fn log_call_macro(args: LogCallArgs, mut input: ItemFn) -> TokenStrea
     // ── 第一步：收集需要记录的参数，同时剥除所有 #[skip_log] 属性 ──────────────
     let mut param_formats = Vec::new();
     let mut param_values = Vec::new();
+    // 已匹配到参数的脱敏参数名，用于检查 skip 中是否有不存在的参数
+    let mut matched_skip = Vec::new();
+    // 记录参数：在 skip 中的参数只记录参数名，值显示为 <redacted>，不会对值进行格式化
+    let mut push_param = |name: String, value: TokenStream| {
+        if skip.iter().any(|skip_name| *skip_name == name) {
+            param_formats.push(format!("{} = <redacted>", name));
+            matched_skip.push(name);
+        } else {
+            param_formats.push(format!("{} = {{:?}}", name));
+            param_values.push(value);
+        }
+    };
 
     for arg in &input.sig.inputs {
         match arg {
@@ -98,12 +177,10 @@ pub(super) fn log_call_macro(args: LogCallArgs, mut input: ItemFn) -> TokenStrea
                 if let Pat::Ident(pat_ident) = &*pat_type.pat {
                     // 普通写法：path: Path<T>  或  val: MyType
                     let param_name = &pat_ident.ident;
-                    let param_name_str = param_name.to_string();
-                    param_formats.push(format!("{} = {{:?}}", param_name_str));
                     if is_wrapper {
-                        param_values.push(quote! { #param_name.0 });
+                        push_param(param_name.to_string(), quote! { #param_name.0 });
                     } else {
-                        param_values.push(quote! { #param_name });
+                        push_param(param_name.to_string(), quote! { #param_name });
                     }
                 } else if let Pat::TupleStruct(pat_ts) = &*pat_type.pat {
                     // 解构写法：Path(id): Path<u64>  /  Json(mut dto): Json<Dto>
@@ -111,9 +188,8 @@ pub(super) fn log_call_macro(args: LogCallArgs, mut input: ItemFn) -> TokenStrea
                     if pat_ts.elems.len() == 1 {
                         if let Pat::Ident(inner) = &pat_ts.elems[0] {
                             let inner_name = inner.ident.to_string();
-                            param_formats.push(format!("{} = {{:?}}", inner_name));
                             let bare_ident = Ident::new(&inner_name, Span::call_site());
-                            param_values.push(quote! { #bare_ident });
+                            push_param(inner_name, quote! { #bare_ident });
                         }
                     }
                 } else if let Pat::Tuple(pat_tuple) = &*pat_type.pat {
@@ -121,24 +197,31 @@ pub(super) fn log_call_macro(args: LogCallArgs, mut input: ItemFn) -> TokenStrea
                     if pat_tuple.elems.len() == 1 {
                         if let Pat::Ident(pat_ident) = &pat_tuple.elems[0] {
                             let inner_name = pat_ident.ident.to_string();
-                            param_formats.push(format!("{} = {{:?}}", inner_name));
                             let bare_ident = Ident::new(&inner_name, Span::call_site());
                             if is_wrapper {
-                                param_values.push(quote! { #bare_ident.0 });
+                                push_param(inner_name, quote! { #bare_ident.0 });
                             } else {
-                                param_values.push(quote! { #bare_ident });
+                                push_param(inner_name, quote! { #bare_ident });
                             }
                         }
                     }
                 }
             }
             FnArg::Receiver(_) => {
-                param_formats.push("self = {:?}".to_string());
-                param_values.push(quote! { self });
+                push_param("self".to_string(), quote! { self });
             }
         }
     }
 
+    // skip 中的参数名必须是方法的参数，避免拼写错误导致敏感参数被记录
+    if let Some(unknown) = skip
+        .iter()
+        .find(|skip_name| !matched_skip.contains(&skip_name.to_string()))
+    {
+        return syn::Error::new_spanned(unknown, format!("skip 参数中的 {unknown} 不是方法的参数"))
+            .to_compile_error();
+    }
+
     // ── 第二步：从函数签名中剥除所有 #[skip_log] 属性 ────────────────────────
     // 必须在生成代码之前完成，否则编译器会报"未知属性"错误
     for arg in input.sig.inputs.iter_mut() {
@@ -162,26 +245,98 @@ pub(super) fn log_call_macro(args: LogCallArgs, mut input: ItemFn) -> TokenStrea
     );
     let enter_log = if record_mode == RecordMode::Both || record_mode == RecordMode::Enter {
         quote! {
-            tracing::#log_level!(#enter_log, #(#param_values),*);
+            ::robotech::__private::tracing::#log_level!(#enter_log, #(#param_values),*);
         }
     } else {
         quote! {}
     };
-    let exit_log = if record_mode == RecordMode::Both || record_mode == RecordMode::Exit {
+    // 指定 ret 或 timing 时，即使 mode 不包含退出也会记录退出日志
+    let log_exit = record_mode == RecordMode::Both || record_mode == RecordMode::Exit;
+    let log_ret = log_exit || ret;
+    let mut exit_log = format!("← 离开方法: {fn_name_str}()");
+    let mut exit_values = Vec::new();
+    if timing {
+        exit_log.push_str(" 耗时: {:?}");
+        exit_values.push(quote! { __log_call_start.elapsed() });
+    }
+    if log_ret {
+        exit_log.push_str(" 返回: {:?}");
+        exit_values.push(quote! { result });
+    }
+    let exit_log = if log_ret || timing {
         quote! {
-            tracing::#log_level!("退出方法 ↩️ {}(), 返回值: {:?}", #fn_name_str, result);
+            ::robotech::__private::tracing::#log_level!(#exit_log, #(#exit_values),*);
         }
     } else {
         quote! {}
     };
+    let start_timing = if timing {
+        quote! { let __log_call_start = ::std::time::Instant::now(); }
+    } else {
+        quote! {}
+    };
 
-    let expanded = quote! {
-        #(#fn_attrs)*
-        #fn_vis #fn_sig {
-            #enter_log
-            let result = #fn_block;
-            #exit_log
-            result
+    let result_type = if no_err {
+        None
+    } else {
+        result_return_type(&fn_sig.output)
+    };
+    let expanded = if let Some(result_type) = result_type {
+        // 返回 Result 时，Err 使用 error 级别记录，Ok 仍使用指定的级别记录
+        let mut err_log = format!("← 离开方法: {fn_name_str}()");
+        let mut err_values = Vec::new();
+        if timing {
+            err_log.push_str(" 耗时: {:?}");
+            err_values.push(quote! { __log_call_start.elapsed() });
+        }
+        err_log.push_str(" 返回: {:?}");
+        err_values.push(quote! { result });
+        // 默认直接绑定方法体的值，方法体中 ? 和 return 提前返回时不记录退出日志；
+        // 指定 catch_err 时方法体放在闭包(async 方法为 async 块)中执行，提前返回的错误也会被记录
+        let call_block = if !catch_err {
+            quote! { #fn_block }
+        } else if fn_sig.asyncness.is_some() {
+            quote! { async move #fn_block.await }
+        } else {
+            quote! { (move || #fn_block)() }
+        };
+        // 返回值类型中包含 impl Trait 时不标注类型，由返回值推断
+        let result_binding = if contains_impl_trait(result_type) {
+            quote! { let result }
+        } else {
+            quote! { let result: #result_type }
+        };
+        let allow_closure_call = if catch_err {
+            quote! { #[allow(clippy::redundant_closure_call)] }
+        } else {
+            quote! {}
+        };
+        quote! {
+            #(#fn_attrs)*
+            #fn_vis #fn_sig {
+                #enter_log
+                #start_timing
+                #allow_closure_call
+                #result_binding = #call_block;
+                if ::std::result::Result::is_err(&result) {
+                    ::robotech::__private::tracing::error!(#err_log, #(#err_values),*);
+                } else {
+                    #exit_log
+                }
+                result
+            }
+        }
+    } else {
+        // async 方法的方法体在生成的 async 方法中执行，方法体中的 .await 都在计时范围内
+        quote! {
+            #(#fn_attrs)*
+            #fn_vis #fn_sig {
+                #enter_log
+                #start_timing
+                let result = #fn_block;
+                #exit_log
+                result
+            }
         }
     };
 
@@ -190,3 +345,85 @@ pub(super) fn log_call_macro(args: LogCallArgs, mut input: ItemFn) -> TokenStrea
 
     TokenStream::from(expanded)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::{Expr, Local, Stmt, parse_quote};
+
+    /// 展开 `log_call` 宏，返回绑定 `result` 的语句
+    fn expand_result_binding(args: TokenStream, input: ItemFn) -> Local {
+        let args: LogCallArgs = syn::parse2(args).unwrap();
+        let output: ItemFn = syn::parse2(log_call_macro(args, input)).unwrap();
+        output
+            .block
+            .stmts
+            .into_iter()
+            .find_map(|stmt| match stmt {
+                Stmt::Local(local) if is_result_binding(&local) => Some(local),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn is_result_binding(local: &Local) -> bool {
+        let pat = match &local.pat {
+            syn::Pat::Type(pat_type) => &*pat_type.pat,
+            pat => pat,
+        };
+        matches!(pat, syn::Pat::Ident(pat_ident) if pat_ident.ident == "result")
+    }
+
+    #[test]
+    fn result_binds_body_without_wrapping() {
+        let local = expand_result_binding(
+            quote! {},
+            parse_quote! {
+                fn parse(text: &str) -> Result<u64, ParseIntError> {
+                    text.parse::<u64>()
+                }
+            },
+        );
+        assert!(local.attrs.is_empty());
+        assert!(matches!(*local.init.unwrap().expr, Expr::Block(_)));
+    }
+
+    #[test]
+    fn catch_err_wraps_body_in_closure() {
+        let local = expand_result_binding(
+            quote! { catch_err },
+            parse_quote! {
+                fn parse(text: &str) -> Result<u64, ParseIntError> {
+                    Ok(text.parse::<u64>()?)
+                }
+            },
+        );
+        assert!(matches!(*local.init.unwrap().expr, Expr::Call(_)));
+    }
+
+    #[test]
+    fn catch_err_wraps_async_body_in_async_block() {
+        let local = expand_result_binding(
+            quote! { catch_err },
+            parse_quote! {
+                async fn query(id: u64) -> Result<u64, SvcError> {
+                    Ok(find(id).await?)
+                }
+            },
+        );
+        assert!(matches!(*local.init.unwrap().expr, Expr::Await(_)));
+    }
+
+    #[test]
+    fn impl_trait_result_is_not_annotated() {
+        let local = expand_result_binding(
+            quote! {},
+            parse_quote! {
+                fn ids(&self) -> Result<impl Iterator<Item = u64>, SvcError> {
+                    Ok(self.ids.iter().copied())
+                }
+            },
+        );
+        assert!(matches!(local.pat, syn::Pat::Ident(_)));
+    }
+}
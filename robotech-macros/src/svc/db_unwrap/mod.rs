@@ -1,31 +1,73 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
-use syn::{FnArg, ItemFn, Pat};
+use syn::{ExprClosure, FnArg, ItemFn, LitStr, Pat, ReturnType, Token};
 
 /// db_unwrap属性宏参数解析
 #[derive(Debug, Default)]
 pub(crate) struct DbUnwrapArgs {
     /// 需要事务
     transaction_required: bool,
+    /// 只读方法(未传入数据库连接时使用读数据库连接)
+    read: bool,
+    /// 事务隔离级别(sea_orm::IsolationLevel 的变体名)，不设置则使用数据库连接的默认隔离级别
+    isolation: Option<Ident>,
+    /// 成功返回(提交事务)后执行的闭包，参数为返回值 `Ok` 中的值的引用
+    after_commit: Option<ExprClosure>,
 }
 
 impl Parse for DbUnwrapArgs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        if input.is_empty() {
-            return Ok(DbUnwrapArgs::default());
+        let mut args = DbUnwrapArgs::default();
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            match ident.to_string().to_lowercase().as_str() {
+                "transaction_required" => args.transaction_required = true,
+                "read" => args.read = true,
+                "isolation" => {
+                    let _eq: Token![=] = input.parse()?;
+                    let value: LitStr = input.parse()?;
+                    let isolation = match value.value().as_str() {
+                        "read_committed" => "ReadCommitted",
+                        "repeatable_read" => "RepeatableRead",
+                        "serializable" => "Serializable",
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "Invalid isolation, expected \"read_committed\", \"repeatable_read\" or \"serializable\"",
+                            ));
+                        }
+                    };
+                    args.isolation = Some(Ident::new(isolation, value.span()));
+                }
+                "after_commit" => {
+                    let _eq: Token![=] = input.parse()?;
+                    args.after_commit = Some(input.parse()?);
+                }
+                unknown => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        format!("Unknown argument: {unknown}"),
+                    ));
+                }
+            }
+            if !input.is_empty() {
+                let _comma: Token![,] = input.parse()?;
+            }
         }
-
-        let ident: Ident = input.parse()?;
-        match ident.to_string().to_lowercase().as_str() {
-            "transaction_required" => Ok(DbUnwrapArgs {
-                transaction_required: true,
-            }),
-            unknown => Err(syn::Error::new_spanned(
-                ident,
-                format!("Unknown argument: {unknown}"),
-            )),
+        if args.read && args.transaction_required {
+            return Err(syn::Error::new(
+                input.span(),
+                "read cannot be used with transaction_required",
+            ));
         }
+        if let (false, Some(isolation)) = (args.transaction_required, &args.isolation) {
+            return Err(syn::Error::new_spanned(
+                isolation,
+                "isolation requires transaction_required",
+            ));
+        }
+        Ok(args)
     }
 }
 
@@ -58,24 +100,97 @@ pub(crate) fn db_unwrap_macro(args: DbUnwrapArgs, input: ItemFn) -> TokenStream
         .into();
     }
 
+    let ret_ty = match &fn_sig.output {
+        ReturnType::Type(_, ret_ty) => Some(ret_ty),
+        ReturnType::Default => None,
+    };
+    if ret_ty.is_none() && (transaction_required || args.after_commit.is_some()) {
+        return syn::Error::new_spanned(
+            &fn_sig,
+            "transaction_required or after_commit method must return a Result",
+        )
+        .to_compile_error();
+    }
+
     // 提取用户编写的代码块
     let user_block = &input.block;
+    // 成功返回后执行的闭包
+    let after_commit = match &args.after_commit {
+        Some(after_commit) => quote! { (#after_commit)(&value); },
+        None => quote! {},
+    };
+    // 没有事务需要提交时，在用户代码块成功返回后执行闭包
+    let user_block_with_after_commit = if args.after_commit.is_some() {
+        quote! {
+            let result: #ret_ty = async #user_block.await;
+            if let Ok(value) = &result {
+                #after_commit
+            }
+            result
+        }
+    } else {
+        quote! { #user_block }
+    };
+
+    // 未传入数据库连接时使用全局数据库连接，需要事务时开启事务并包裹用户代码块
+    let generated_default_db = if transaction_required {
+        let begin_transaction = match &args.isolation {
+            Some(isolation) => quote! {
+                ::robotech::db::begin_transaction_with_isolation(
+                    db_conn.as_ref(),
+                    ::robotech::__private::sea_orm::IsolationLevel::#isolation,
+                )
+                .await?
+            },
+            None => quote! { ::robotech::db::begin_transaction(db_conn.as_ref()).await? },
+        };
+        quote! {
+            let db_conn = ::robotech::db::get_db_conn()?;
+            // 开启事务
+            let tx = #begin_transaction;
+            // 用户代码块在async块中执行，其中的 ? 及 return 都不会跳过提交或回滚
+            let result: #ret_ty = async {
+                let db = &tx;
+                #user_block
+            }
+            .await;
+            match result {
+                Ok(value) => {
+                    ::robotech::db::commit_transaction(tx).await?;
+                    #after_commit
+                    Ok(value)
+                }
+                Err(e) => {
+                    // 回滚失败时仍然返回原来的错误
+                    if let Err(rollback_err) = ::robotech::db::rollback_transaction(tx).await {
+                        ::robotech::__private::tracing::error!("回滚事务失败: {rollback_err}");
+                    }
+                    Err(e)
+                }
+            }
+        }
+    } else {
+        // 只读方法使用读数据库连接(配置了读库且当前请求未写入时为读库)
+        let get_db_conn = if args.read {
+            quote! { ::robotech::db::get_read_db_conn()? }
+        } else {
+            quote! { ::robotech::db::get_db_conn()? }
+        };
+        quote! {
+            let db_conn = #get_db_conn;
+            let db = db_conn.as_ref();
+            #user_block_with_after_commit
+        }
+    };
 
     // 生成包装后的方法
     let expanded = quote! {
         #(#fn_attrs)*
         #fn_vis #fn_sig {
             if let Some(db) = db {
-                #user_block
+                #user_block_with_after_commit
             } else {
-                let db_conn = robotech::db::get_db_conn()?;
-                let db = db_conn.as_ref();
-                if #transaction_required {
-                    // 开启事务
-                    let tx = begin_transaction(db).await?;
-                    let db = &tx;
-                }
-                #user_block
+                #generated_default_db
             }
         }
     };
@@ -1,9 +1,80 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
-use syn::ItemStruct;
+use syn::parse::{Parse, ParseStream};
+use syn::{ItemStruct, LitInt, LitStr, Token};
 use wheel_rs::str_utils::{CamelFormat, split_camel_case};
 
-pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
+/// 缓存的默认容量
+const CACHE_CAPACITY_DEFAULT: usize = 1000;
+
+/// svc属性宏参数解析
+#[derive(Debug, Default)]
+pub(crate) struct SvcArgs {
+    /// 缓存get_by_id结果的过期时间(毫秒)，不设置则不缓存
+    cache_get_by_id_millis: Option<u64>,
+    /// 缓存的容量上限
+    cache_capacity: Option<usize>,
+    /// 实体名称(大驼峰)，不设置则从结构体名称推断(去掉结尾的Svc)
+    entity: Option<LitStr>,
+}
+
+impl Parse for SvcArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = SvcArgs::default();
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            let _eq: Token![=] = input.parse()?;
+            match ident.to_string().as_str() {
+                "cache_get_by_id" => {
+                    let value: LitStr = input.parse()?;
+                    let millis = parse_duration_millis(&value.value()).ok_or_else(|| {
+                        syn::Error::new_spanned(
+                            &value,
+                            "Invalid duration, expected such as \"500ms\", \"60s\", \"5m\", \"1h\"",
+                        )
+                    })?;
+                    args.cache_get_by_id_millis = Some(millis);
+                }
+                "cache_capacity" => {
+                    let value: LitInt = input.parse()?;
+                    args.cache_capacity = Some(value.base10_parse()?);
+                }
+                "entity" => {
+                    args.entity = Some(input.parse()?);
+                }
+                unknown => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        format!("Unknown argument: {unknown}"),
+                    ));
+                }
+            }
+            if !input.is_empty() {
+                let _comma: Token![,] = input.parse()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// 解析时长字符串为毫秒数，支持 ms/s/m/h/d 单位，如 `60s`
+fn parse_duration_millis(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let unit_start = value.find(|c: char| !c.is_ascii_digit())?;
+    let (number, unit) = value.split_at(unit_start);
+    let number: u64 = number.parse().ok()?;
+    let unit_millis = match unit.trim() {
+        "ms" => 1,
+        "s" => 1000,
+        "m" => 60 * 1000,
+        "h" => 60 * 60 * 1000,
+        "d" => 24 * 60 * 60 * 1000,
+        _ => return None,
+    };
+    number.checked_mul(unit_millis)
+}
+
+pub(crate) fn svc_macro(args: SvcArgs, input: ItemStruct) -> TokenStream {
     let struct_name = &input.ident;
 
     // 解析结构体的名称，必须是Svc结尾，符合大驼峰命名规范
@@ -24,6 +95,22 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
     }
     let mut struct_name_split = struct_name_split.unwrap();
     struct_name_split.pop();
+    // 指定了实体名称时，使用指定的实体名称
+    if let Some(entity) = &args.entity {
+        let entity_value = entity.value();
+        let is_upper_camel = entity_value.starts_with(|c: char| c.is_ascii_uppercase())
+            && entity_value.chars().all(|c| c.is_ascii_alphanumeric());
+        match split_camel_case(&entity_value, CamelFormat::Upper) {
+            Ok(entity_split) if is_upper_camel => struct_name_split = entity_split,
+            _ => {
+                return syn::Error::new_spanned(
+                    entity,
+                    "Entity name must be a valid upper camel case",
+                )
+                .to_compile_error();
+            }
+        }
+    }
     let module_name = struct_name_split.join("_").to_lowercase();
     let module = format_ident!("{module_name}");
     let dto_module = format_ident!("{module_name}_dto");
@@ -35,6 +122,64 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
     let save_dto_name = format_ident!("{}SaveDto", entity_name);
     let query_dto_name = format_ident!("{}QueryDto", entity_name);
 
+    // get_by_id的缓存(写操作后按ID失效)
+    let cache_name = format_ident!("{}_GET_BY_ID_CACHE", module_name.to_uppercase());
+    let cache_enabled = args.cache_get_by_id_millis.is_some();
+    let cache_static = match args.cache_get_by_id_millis {
+        Some(ttl_millis) => {
+            let capacity = args.cache_capacity.unwrap_or(CACHE_CAPACITY_DEFAULT);
+            quote! {
+                static #cache_name: std::sync::LazyLock<::robotech::svc::SvcCache<#vo_name>> =
+                    std::sync::LazyLock::new(|| {
+                        ::robotech::svc::SvcCache::new(std::time::Duration::from_millis(#ttl_millis), #capacity)
+                    });
+            }
+        }
+        None => quote! {},
+    };
+    let invalidate_by_id = if cache_enabled {
+        quote! { #cache_name.invalidate(id as u64); }
+    } else {
+        quote! {}
+    };
+    let invalidate_by_ids = if cache_enabled {
+        quote! { ids.iter().for_each(|id| #cache_name.invalidate(*id)); }
+    } else {
+        quote! {}
+    };
+    let invalidate_all = if cache_enabled {
+        quote! { #cache_name.invalidate_all(); }
+    } else {
+        quote! {}
+    };
+    // 提交事务后再次使缓存失效(提交前并发的get_by_id可能又缓存了旧的记录)
+    let after_commit_modify = if cache_enabled {
+        quote! {
+            , after_commit = |ro: &Ro<#vo_name>| {
+                if let Some(one) = &ro.extra {
+                    #cache_name.invalidate(one.id as u64);
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let after_commit_by_id = if cache_enabled {
+        quote! { , after_commit = |_| { #invalidate_by_id } }
+    } else {
+        quote! {}
+    };
+    let after_commit_by_ids = if cache_enabled {
+        quote! { , after_commit = |_| { #invalidate_by_ids } }
+    } else {
+        quote! {}
+    };
+    let after_commit_all = if cache_enabled {
+        quote! { , after_commit = |_| { #invalidate_all } }
+    } else {
+        quote! {}
+    };
+
     let mut generated_methods = Vec::new();
 
     // 生成add方法
@@ -45,6 +190,7 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         ///
         /// ## 参数
         /// * `add_to` - 包含要添加记录信息的传输对象
+        /// * `current_user_id` - 当前用户ID(记录为创建者及修改者)
         /// * `db` - 数据库连接或事务，如果未提供则创建连接及事务
         ///
         /// ## 返回值
@@ -54,6 +200,7 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         #[log_call]
         pub async fn add<C>(
             add_dto: #add_dto_name,
+            current_user_id: u64,
             #[skip_log]
             db: Option<&C>,
         ) -> Result<Ro<#vo_name>, SvcError>
@@ -63,7 +210,9 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
             // 先校验dto
             add_dto.validate()?;
 
-            let active_model: ActiveModel = add_dto.into();
+            let mut active_model: ActiveModel = add_dto.into();
+            // 记录创建者(插入时修改者会被设置为创建者)
+            active_model.creator_id = ::robotech::__private::sea_orm::ActiveValue::Set(current_user_id as i64);
             let one = #vo_name::from(#dao_name::insert(active_model, db).await?);
             Ok(Self::get_by_id(one.id as u64, Some(db))
                 .await?
@@ -79,15 +228,17 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         ///
         /// ## 参数
         /// * `modify_to` - 包含要修改记录信息的传输对象，必须包含有效的ID
+        /// * `current_user_id` - 当前用户ID(记录为修改者)
         /// * `db` - 数据库连接，如果未提供则使用全局数据库连接
         ///
         /// ## 返回值
         /// * `Ok(Ro<Vo>)` - 修改成功，返回封装了更新后Vo的Ro对象
         /// * `Err(SvcError)` - 修改失败，可能因为记录不存在、违反唯一约束或其他数据库错误
-        #[db_unwrap(transaction_required)]
+        #[db_unwrap(transaction_required #after_commit_modify)]
         #[log_call]
         pub async fn modify<C>(
             modify_dto: #modify_dto_name,
+            current_user_id: u64,
             #[skip_log]
             db: Option<&C>,
         ) -> Result<Ro<#vo_name>, SvcError>
@@ -98,8 +249,11 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
             modify_dto.validate()?;
 
             let id = modify_dto.id.unwrap();    // id经过校验，可以放心unwrap
-            let active_model: ActiveModel = modify_dto.into();
+            let mut active_model: ActiveModel = modify_dto.into();
+            // 记录修改者
+            active_model.updator_id = ::robotech::__private::sea_orm::ActiveValue::Set(current_user_id as i64);
             let one = #vo_name::from(#dao_name::update(active_model, db).await?);
+            #invalidate_by_id
             Ok(Self::get_by_id(one.id, Some(db))
                 .await?
                 .msg("修改成功".to_string()))
@@ -114,6 +268,7 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         ///
         /// ## 参数
         /// * `save_to` - 包含要保存记录信息的传输对象
+        /// * `current_user_id` - 当前用户ID
         /// * `db` - 数据库连接，如果未提供则使用全局数据库连接
         ///
         /// ## 返回值
@@ -121,15 +276,16 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         /// * `Err(SvcError)` - 保存失败，可能因为违反唯一约束、记录不存在或其他数据库错误
         pub async fn save<C>(
             save_dto: #save_dto_name,
+            current_user_id: u64,
             db: Option<&C>,
         ) -> Result<Ro<#vo_name>, SvcError>
         where
             C: ConnectionTrait,
         {
             if let Some(id) = save_dto.id {
-                Self::modify(save_dto.into(), db).await
+                Self::modify(save_dto.into(), current_user_id, db).await
             } else {
-                Self::add(save_dto.into(), db).await
+                Self::add(save_dto.into(), current_user_id, db).await
             }
         }
     });
@@ -142,15 +298,17 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         ///
         /// ## 参数
         /// * `id` - 要删除的记录的ID
+        /// * `current_user_id` - 当前用户ID(软删除时记录为修改者)
         /// * `db` - 数据库连接，如果未提供则使用全局数据库连接
         ///
         /// ## 返回值
         /// * `Ok(Ro<Vo>)` - 删除成功，返回封装了Vo的Ro对象
         /// * `Err(SvcError)` - 删除失败，可能因为记录不存在或其他数据库错误
-        #[db_unwrap(transaction_required)]
+        #[db_unwrap(transaction_required #after_commit_by_id)]
         #[log_call]
         pub async fn del_by_id<C>(
             id: u64,
+            current_user_id: u64,
             #[skip_log]
             db: Option<&C>,
         ) -> Result<Ro<#vo_name>, SvcError>
@@ -163,7 +321,8 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
                 .ok_or(SvcError::NotFound(id.to_string()))?;
             let rows_affected = #dao_name::delete(
                 ActiveModel {
-                    id: sea_orm::ActiveValue::Set(id as i64),
+                    id: ::robotech::__private::sea_orm::ActiveValue::Set(id as i64),
+                    updator_id: ::robotech::__private::sea_orm::ActiveValue::Set(current_user_id as i64),
                     ..Default::default()
                 },
                 db,
@@ -172,6 +331,7 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
             if rows_affected == 0 {
                 return Err(SvcError::NotFound(id.to_string()));
             }
+            #invalidate_by_id
             Ok(Ro::success("删除成功".to_string()).extra(Some(one)))
         }
     });
@@ -184,14 +344,16 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         ///
         /// ## 参数
         /// * `dto` - 查询参数
+        /// * `current_user_id` - 当前用户ID(软删除时记录为修改者)
         /// * `db` - 数据库连接，如果未提供则使用全局数据库连接
         ///
         /// ## 返回值
         /// * `Result<Ro<Vo>, SvcError>` - 查询结果封装为Ro对象，如果查询成功则返回封装了Vo的Ro对象，否则返回错误信息
-        #[db_unwrap(transaction_required)]
+        #[db_unwrap(transaction_required #after_commit_all)]
         #[log_call]
         pub async fn del_by_query_dto<C>(
             dto: #query_dto_name,
+            current_user_id: u64,
             #[skip_log]
             db: Option<&C>,
         ) -> Result<Ro<()>, SvcError>
@@ -203,7 +365,9 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
                 condition = condition.add(build_like_condition(keyword, #dao_name::LIKE_COLUMNS));
             }
 
-            let rows_affected = #dao_name::delete_by_condition(condition, db).await?.rows_affected;
+            let rows_affected = #dao_name::delete_by_condition_with_updator(condition, current_user_id, db).await?.rows_affected;
+            // 无法得知删除了哪些记录，使所有缓存失效
+            #invalidate_all
             if rows_affected == 0 {
                 return Err(SvcError::NotFound(dto.to_string()));
             }
@@ -211,7 +375,162 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         }
     });
 
-    // 生成get_by_id方法
+    // 生成del_batch方法
+    generated_methods.push(quote! {
+        /// # 批量删除记录
+        ///
+        /// 在同一个事务中根据提供的ID列表删除数据库中的相应记录，并记录一条汇总的审计日志
+        ///
+        /// ## 参数
+        /// * `ids` - 要删除的记录的ID列表
+        /// * `current_user_id` - 当前用户ID
+        /// * `db` - 数据库连接，如果未提供则使用全局数据库连接
+        ///
+        /// ## 返回值
+        /// * `Ok(Ro<u64>)` - 删除成功，返回封装了删除记录数的Ro对象
+        /// * `Err(SvcError)` - 删除失败，可能因为违反外键约束或其他数据库错误
+        #[db_unwrap(transaction_required #after_commit_by_ids)]
+        #[log_call]
+        pub async fn del_batch<C>(
+            ids: Vec<u64>,
+            current_user_id: u64,
+            #[skip_log]
+            db: Option<&C>,
+        ) -> Result<Ro<u64>, SvcError>
+        where
+            C: ConnectionTrait,
+        {
+            if ids.is_empty() {
+                return Ok(Ro::success("没有需要删除的记录".to_string()).extra(Some(0)));
+            }
+            let condition = ::robotech::__private::sea_orm::Condition::all()
+                .add(::robotech::__private::sea_orm::ColumnTrait::is_in(
+                    &crate::model::#module::Column::Id,
+                    ids.iter().map(|id| *id as i64),
+                ));
+            let rows_affected = #dao_name::delete_by_condition_with_updator(condition, current_user_id, db).await?.rows_affected;
+            #invalidate_by_ids
+            ::robotech::__private::tracing::info!(
+                "用户<{}>批量删除{}记录{}条: {:?}",
+                current_user_id,
+                #module_name,
+                rows_affected,
+                ids
+            );
+            Ok(Ro::success("删除成功".to_string()).extra(Some(rows_affected)))
+        }
+    });
+
+    // 生成预览方法(在事务中执行后回滚，不会保存)
+    for (
+        preview_name,
+        method_name,
+        param_name,
+        param_type,
+        extra_params,
+        extra_args,
+        return_type,
+        remark,
+    ) in [
+        (
+            format_ident!("modify_preview"),
+            format_ident!("modify"),
+            format_ident!("modify_dto"),
+            quote! { #modify_dto_name },
+            quote! { current_user_id: u64, },
+            quote! { current_user_id, },
+            quote! { Ro<#vo_name> },
+            "修改记录",
+        ),
+        (
+            format_ident!("del_by_id_preview"),
+            format_ident!("del_by_id"),
+            format_ident!("id"),
+            quote! { u64 },
+            quote! { current_user_id: u64, },
+            quote! { current_user_id, },
+            quote! { Ro<#vo_name> },
+            "删除记录",
+        ),
+    ] {
+        let doc_title = format!(" # 预览{remark}");
+        let doc_desc = format!(
+            " 在事务中执行`{method_name}`后回滚，返回执行的结果，但不会保存，用于在执行有风险的操作前查看将会发生的变化"
+        );
+        generated_methods.push(quote! {
+            #[doc = #doc_title]
+            ///
+            #[doc = #doc_desc]
+            ///
+            /// ## 参数
+            /// * `db` - 数据库连接或事务(会在其中开启嵌套事务)，如果未提供则使用全局数据库连接
+            ///
+            /// ## 返回值
+            /// * `Ok(Ro)` - 执行成功时将会返回的结果
+            /// * `Err(SvcError)` - 执行失败时将会返回的错误
+            pub async fn #preview_name<C>(
+                #param_name: #param_type,
+                #extra_params
+                db: Option<&C>,
+            ) -> Result<#return_type, SvcError>
+            where
+                C: ConnectionTrait + ::robotech::__private::sea_orm::TransactionTrait,
+            {
+                let tx = match db {
+                    Some(db) => db.begin().await.map_err(::robotech::dao::DaoError::from)?,
+                    None => begin_transaction(::robotech::db::get_db_conn()?.as_ref()).await?,
+                };
+                let result = Self::#method_name(#param_name, #extra_args Some(&tx)).await;
+                rollback_transaction(tx).await?;
+                result
+            }
+        });
+    }
+
+    // 生成del_batch_preview方法
+    generated_methods.push(quote! {
+        /// # 预览批量删除记录
+        ///
+        /// 在事务中执行`del_batch`后回滚，返回将会删除的记录数，但不会保存
+        ///
+        /// ## 参数
+        /// * `ids` - 要删除的记录的ID列表
+        /// * `current_user_id` - 当前用户ID
+        /// * `db` - 数据库连接或事务(会在其中开启嵌套事务)，如果未提供则使用全局数据库连接
+        ///
+        /// ## 返回值
+        /// * `Ok(Ro<u64>)` - 返回封装了将会删除的记录数的Ro对象
+        /// * `Err(SvcError)` - 执行失败时将会返回的错误
+        pub async fn del_batch_preview<C>(
+            ids: Vec<u64>,
+            current_user_id: u64,
+            db: Option<&C>,
+        ) -> Result<Ro<u64>, SvcError>
+        where
+            C: ConnectionTrait + ::robotech::__private::sea_orm::TransactionTrait,
+        {
+            let tx = match db {
+                Some(db) => db.begin().await.map_err(::robotech::dao::DaoError::from)?,
+                None => begin_transaction(::robotech::db::get_db_conn()?.as_ref()).await?,
+            };
+            let result = Self::del_batch(ids, current_user_id, Some(&tx)).await;
+            rollback_transaction(tx).await?;
+            result
+        }
+    });
+
+    // 生成get_by_id方法(启用缓存时，从数据库查询的方法为get_by_id_uncached)
+    let get_by_id_name = if cache_enabled {
+        format_ident!("get_by_id_uncached")
+    } else {
+        format_ident!("get_by_id")
+    };
+    // 启用缓存时从主库查询，避免把读库中同步延迟的旧数据写入缓存
+    let get_by_id_db_unwrap = if cache_enabled {
+        quote! { #[db_unwrap] }
+    } else {
+        quote! { #[db_unwrap(read)] }
+    };
     generated_methods.push(quote! {
         /// # 根据id获取记录信息
         ///
@@ -224,9 +543,9 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         /// ## 返回值
         /// * `Ok(Ro<Vo>)` - 查询成功，如果记录存在，返回封装了Vo的Ro对象，如果不存在则返回对象的extra为None
         /// * `Err(SvcError)` - 查询失败，可能是数据库错误
-        #[db_unwrap]
+        #get_by_id_db_unwrap
         #[log_call]
-        pub async fn get_by_id<C>(
+        pub async fn #get_by_id_name<C>(
             id: u64,
             #[skip_log]
             db: Option<&C>
@@ -238,6 +557,41 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
             Ok(Ro::success("查询成功".to_string()).extra(one))
         }
     });
+    if cache_enabled {
+        generated_methods.push(quote! {
+            /// # 根据id获取记录信息(带缓存)
+            ///
+            /// 未提供数据库连接时优先从进程内缓存中获取，缓存中没有再从数据库查询并写入缓存；
+            /// 提供了数据库连接或事务时直接从数据库查询，避免缓存未提交的数据
+            ///
+            /// ## 参数
+            /// * `id` - 要查询的记录的ID
+            /// * `db` - 数据库连接，如果未提供则使用全局数据库连接
+            ///
+            /// ## 返回值
+            /// * `Ok(Ro<Vo>)` - 查询成功，如果记录存在，返回封装了Vo的Ro对象，如果不存在则返回对象的extra为None
+            /// * `Err(SvcError)` - 查询失败，可能是数据库错误
+            pub async fn get_by_id<C>(
+                id: u64,
+                db: Option<&C>
+            ) -> Result<Ro<#vo_name>, SvcError>
+            where
+                C: ConnectionTrait,
+            {
+                if db.is_some() {
+                    return Self::get_by_id_uncached(id, db).await;
+                }
+                if let Some(one) = #cache_name.get(id) {
+                    return Ok(Ro::success("查询成功".to_string()).extra(Some(one)));
+                }
+                let ro = Self::get_by_id_uncached(id, db).await?;
+                if let Some(one) = &ro.extra {
+                    #cache_name.insert(id, one.clone());
+                }
+                Ok(ro)
+            }
+        });
+    }
 
     // 生成get_by_query_dto方法
     generated_methods.push(quote! {
@@ -251,7 +605,7 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         ///
         /// ## 返回值
         /// * `Result<Ro<Vo>, SvcError>` - 查询结果封装为Ro对象，如果查询成功则返回封装了Vo的Ro对象，否则返回错误信息
-        #[db_unwrap]
+        #[db_unwrap(read)]
         #[log_call]
         pub async fn get_by_query_dto<C>(
             dto: #query_dto_name,
@@ -285,7 +639,7 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         ///
         /// ## 返回值
         /// * `Result<Ro<Vec<Vo>>, SvcError>` - 查询结果封装为Ro对象，如果查询成功则返回封装了Vo的Ro对象，否则返回错误信息
-        #[db_unwrap]
+        #[db_unwrap(read)]
         #[log_call]
         pub async fn list_by_query_dto<C>(
             dto: #query_dto_name,
@@ -311,6 +665,36 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         }
     });
 
+    // 生成stream_by_query_dto方法
+    generated_methods.push(quote! {
+        /// # 以流的方式查询记录
+        ///
+        /// 根据提供的查询参数(与list_by_query_dto相同的过滤及排序)逐条读取数据库中的记录，
+        /// 不会一次性加载到内存中，用于导出等需要遍历所有记录的场景
+        ///
+        /// ## 参数
+        /// * `dto` - 查询参数
+        /// * `db` - 数据库连接(流在读取完之前会一直占用该连接)
+        ///
+        /// ## 返回值
+        /// * `Ok(impl ModelStream<Model>)` - 逐条返回Model的流
+        /// * `Err(SvcError)` - 查询失败，可能是数据库错误
+        pub async fn stream_by_query_dto<'a, C>(
+            dto: #query_dto_name,
+            db: &'a C
+        ) -> Result<impl ::robotech::dao::ModelStream<crate::model::#module::Model> + 'a, SvcError>
+        where
+            C: ConnectionTrait + ::robotech::__private::sea_orm::StreamTrait + Send,
+        {
+            let mut condition = dto.to_condition();
+            if let Some(keyword) = &dto._keyword {
+                condition = condition.add(build_like_condition(keyword, #dao_name::LIKE_COLUMNS));
+            }
+
+            Ok(#dao_name::stream_by_condition(condition, &dto._order_by, db).await?)
+        }
+    });
+
     // 生成page_by_query_dto方法
     generated_methods.push(quote! {
         /// # 查询记录列表
@@ -323,7 +707,7 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
         ///
         /// ## 返回值
         /// * `Result<Ro<Vec<Vo>>, SvcError>` - 查询结果封装为Ro对象，如果查询成功则返回封装了Vo的Ro对象，否则返回错误信息
-        #[db_unwrap]
+        #[db_unwrap(read)]
         #[log_call]
         pub async fn page_by_query_dto<C>(
             dto: #query_dto_name,
@@ -336,7 +720,7 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
             let keyword = &dto._keyword;
             let order_by = &dto._order_by;
             let page_num = dto._page.unwrap_or(1);
-            let page_size = dto._size.unwrap_or(10);
+            let page_size = resolve_page_size(dto._size);
 
             let mut condition = dto.to_condition();
             if let Some(keyword) = keyword {
@@ -361,20 +745,23 @@ pub(crate) fn svc_macro(input: ItemStruct) -> TokenStream {
     });
 
     let expanded = quote! {
-        use robotech::dao::{begin_transaction, build_like_condition};
-        use robotech::ro::Ro;
-        use robotech::rx::PageRx;
-        use robotech::svc::SvcError;
-        use robotech::macros::db_unwrap;
-        use robotech::macros::log_call;
-        use sea_orm::ConnectionTrait;
-        use validator::Validate;
+        use ::robotech::dao::{build_like_condition, resolve_page_size};
+        use ::robotech::db::{begin_transaction, rollback_transaction};
+        use ::robotech::ro::Ro;
+        use ::robotech::rx::PageRx;
+        use ::robotech::svc::SvcError;
+        use ::robotech::macros::db_unwrap;
+        use ::robotech::macros::log_call;
+        use ::robotech::__private::sea_orm::ConnectionTrait;
+        use ::robotech::__private::validator::Validate;
 
         use crate::dto::#dto_module::*;
         use crate::dao::#dao_name;
         use crate::model::#module::ActiveModel;
         use crate::vo::#vo_name;
 
+        #cache_static
+
         #input
 
         impl #struct_name {
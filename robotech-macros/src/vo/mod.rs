@@ -1,6 +1,7 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Attribute, Data, DeriveInput, Field, Fields};
+use syn::{Attribute, Data, DeriveInput, Field, Fields, LitStr, Token};
+use wheel_rs::str_utils::snake_to_pascal;
 
 /// 检查字段是否已经有某个属性
 fn has_attribute(attrs: &[Attribute], name: &str) -> bool {
@@ -121,6 +122,57 @@ fn generate_builder_attr(field: &Field) -> Option<TokenStream> {
     None
 }
 
+/// 字段序列化后的名称
+///
+/// 优先使用字段上的 `#[serde(rename = "...")]`，否则与 `#[serde(rename_all = "camelCase")]` 一致
+fn serialized_name(field: &Field) -> Option<String> {
+    let field_name = field.ident.as_ref()?;
+    Some(serde_rename(field).unwrap_or_else(|| to_camel_case(&field_name.to_string())))
+}
+
+/// 获取字段上 `#[serde(rename = "...")]` 或 `#[serde(rename(serialize = "..."))]` 指定的序列化名称
+fn serde_rename(field: &Field) -> Option<String> {
+    let mut rename = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+    {
+        // 其它的serde参数原样跳过，格式不正确时由serde报错
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                if meta.input.peek(Token![=]) {
+                    rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else {
+                    meta.parse_nested_meta(|meta| {
+                        let value = meta.value()?.parse::<LitStr>()?.value();
+                        if meta.path.is_ident("serialize") {
+                            rename = Some(value);
+                        }
+                        Ok(())
+                    })?;
+                }
+            } else if meta.input.peek(Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            } else if !meta.input.is_empty() && !meta.input.peek(Token![,]) {
+                meta.input.parse::<proc_macro2::TokenTree>()?;
+            }
+            Ok(())
+        });
+    }
+    rename
+}
+
+/// 字段名转换为驼峰命名(与 `#[serde(rename_all = "camelCase")]` 一致)
+fn to_camel_case(field_name: &str) -> String {
+    let pascal = snake_to_pascal(field_name.trim_start_matches("r#"));
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => pascal,
+    }
+}
+
 /// 检查类型是否是 Option<T>
 fn is_option_type(ty: &syn::Type) -> bool {
     match ty {
@@ -139,10 +191,17 @@ pub fn vo_macro(input: DeriveInput) -> TokenStream {
     let struct_name = &input.ident;
     let vis = &input.vis;
 
+    // 序列化后的字段名(按声明顺序)
+    let mut field_names = vec![];
     // 处理字段
     let fields = match input.data {
         Data::Struct(data_struct) => match data_struct.fields {
             Fields::Named(fields_named) => {
+                field_names = fields_named
+                    .named
+                    .iter()
+                    .filter_map(serialized_name)
+                    .collect();
                 let processed_fields: Vec<_> = fields_named
                     .named
                     .iter()
@@ -151,14 +210,13 @@ pub fn vo_macro(input: DeriveInput) -> TokenStream {
                         let field_ty = &field.ty;
                         let attrs = generate_field_attrs(field);
 
-                        // 保留原有的注释和其他属性（除了 from/builder/serde）
+                        // 保留原有的注释和其他属性（除了 from/builder），
+                        // 保留serde属性使 `#[serde(rename = "...")]` 等与 `FIELD_NAMES` 一致
                         let original_attrs: Vec<_> = field
                             .attrs
                             .iter()
                             .filter(|attr| {
-                                !attr.path().is_ident("from")
-                                    && !attr.path().is_ident("builder")
-                                    && !attr.path().is_ident("serde")
+                                !attr.path().is_ident("from") && !attr.path().is_ident("builder")
                             })
                             .collect();
 
@@ -204,6 +262,11 @@ pub fn vo_macro(input: DeriveInput) -> TokenStream {
         #[serde_as]
         #[builder]
         #vis struct #struct_name #fields
+
+        impl #struct_name {
+            /// 序列化后的字段名(按声明顺序)，可用作导出CSV的表头
+            pub const FIELD_NAMES: &'static [&'static str] = &[#(#field_names),*];
+        }
     };
 
     // 调试：打印完整展开的代码
@@ -211,3 +274,57 @@ pub fn vo_macro(input: DeriveInput) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::ToTokens;
+    use syn::{ImplItem, Item, ItemStruct, parse_quote};
+
+    /// 展开 `vo` 宏，返回生成的结构体及 `FIELD_NAMES` 常量的值
+    fn expand(input: DeriveInput) -> (ItemStruct, String) {
+        let file: syn::File = syn::parse2(vo_macro(input)).unwrap();
+        let mut item_struct = None;
+        let mut field_names = None;
+        for item in file.items {
+            match item {
+                Item::Struct(item) => item_struct = Some(item),
+                Item::Impl(item_impl) => {
+                    field_names = item_impl.items.into_iter().find_map(|item| match item {
+                        ImplItem::Const(item_const) if item_const.ident == "FIELD_NAMES" => {
+                            Some(item_const.expr.to_token_stream().to_string())
+                        }
+                        _ => None,
+                    })
+                }
+                _ => {}
+            }
+        }
+        (item_struct.unwrap(), field_names.unwrap())
+    }
+
+    #[test]
+    fn field_names_honour_serde_rename() {
+        let (item_struct, field_names) = expand(parse_quote! {
+            pub struct UserVo {
+                pub id: u64,
+                #[serde(rename = "userName")]
+                pub name: String,
+                #[serde(skip_serializing_if = "Option::is_none", rename(serialize = "mail", deserialize = "email"))]
+                pub email: Option<String>,
+                pub create_timestamp: i64,
+            }
+        });
+        assert_eq!(
+            field_names,
+            quote! { &["id", "userName", "mail", "createTimestamp"] }.to_string()
+        );
+
+        // serde属性保留在字段上，序列化的名称与FIELD_NAMES一致
+        let name_field = item_struct.fields.iter().nth(1).unwrap();
+        assert!(name_field.attrs.iter().any(|attr| {
+            attr.to_token_stream().to_string()
+                == quote! { #[serde(rename = "userName")] }.to_string()
+        }));
+    }
+}
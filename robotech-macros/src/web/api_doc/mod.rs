@@ -4,6 +4,19 @@ use syn::parse::{Parse, ParseStream};
 use syn::{Ident, ItemStruct, Token};
 use wheel_rs::str_utils::{CamelFormat, split_camel_case};
 
+/// ctrl宏生成的接口
+const CTRL_GENERATED_PATHS: &[&str] = &[
+    "add",
+    "modify",
+    "save",
+    "del_by_id",
+    "del_by_query_dto",
+    "get_by_id",
+    "get_by_query_dto",
+    "list_by_query_dto",
+    "page_by_query_dto",
+];
+
 /// ApiDoc方法生成宏参数解析
 #[derive(Debug, Default)]
 pub(crate) struct ApiDocArgs {
@@ -12,10 +25,6 @@ pub(crate) struct ApiDocArgs {
 
 impl Parse for ApiDocArgs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        if input.is_empty() {
-            return Err(syn::Error::new(input.span(), "No arguments provided"));
-        }
-
         let mut paths = Vec::new();
 
         while !input.is_empty() {
@@ -58,7 +67,15 @@ pub(crate) fn api_doc_macro(args: ApiDocArgs, input: ItemStruct) -> TokenStream
     let ctrl_module = format_ident!("{module_name}_ctrl");
     let url_path = format!("/{url_path}/openapi.json");
 
-    let paths: Vec<Ident> = args.paths.iter().map(|p| format_ident!("{}", p)).collect();
+    // 未指定时使用ctrl宏生成的所有接口
+    let paths: Vec<Ident> = if args.paths.is_empty() {
+        CTRL_GENERATED_PATHS
+            .iter()
+            .map(|p| format_ident!("{}", p))
+            .collect()
+    } else {
+        args.paths.iter().map(|p| format_ident!("{}", p)).collect()
+    };
 
     let expanded = quote! {
         use crate::web::ctrl::#ctrl_module::*;
@@ -1,9 +1,42 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
-use syn::ItemStruct;
+use syn::parse::{Parse, ParseStream};
+use syn::{ItemStruct, Token};
 use wheel_rs::str_utils::{CamelFormat, split_camel_case};
 
-pub(crate) fn ctrl_macro(input: ItemStruct) -> TokenStream {
+/// ctrl属性宏参数解析
+#[derive(Debug, Default)]
+pub(crate) struct CtrlArgs {
+    /// 添加成功时返回 `201 Created` 及指向新记录的 `Location` 响应头(默认返回200)
+    created: bool,
+    /// 生成以CSV格式导出查询结果的接口 `export_by_query_dto`
+    export: bool,
+}
+
+impl Parse for CtrlArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = CtrlArgs::default();
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            match ident.to_string().to_lowercase().as_str() {
+                "created" => args.created = true,
+                "export" => args.export = true,
+                unknown => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        format!("Unknown argument: {unknown}"),
+                    ));
+                }
+            }
+            if !input.is_empty() {
+                let _comma: Token![,] = input.parse()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+pub(crate) fn ctrl_macro(args: CtrlArgs, input: ItemStruct) -> TokenStream {
     let struct_name = &input.ident;
 
     // 解析结构体的名称，必须是Ctrl结尾，符合大驼峰命名规范
@@ -36,6 +69,8 @@ pub(crate) fn ctrl_macro(input: ItemStruct) -> TokenStream {
     let get_by_query_dto_path = crud_path.clone();
     let list_by_query_dto_path = format!("{crud_path}/list");
     let page_by_query_dto_path = format!("{crud_path}/page");
+    let export_by_query_dto_path = format!("{crud_path}/export");
+    let export_file_name = format!("{module_name}.csv");
     let dto_module = format_ident!("{module_name}_dto");
     let svc_name = format_ident!("{}Svc", entity_name);
     let vo_name = format_ident!("{}Vo", entity_name);
@@ -47,6 +82,22 @@ pub(crate) fn ctrl_macro(input: ItemStruct) -> TokenStream {
     let mut generated_methods = Vec::new();
 
     // 生成add方法
+    let (add_status, add_return_type, add_return) = if args.created {
+        (
+            quote! { CREATED },
+            quote! { robotech::web::RoResponse<#vo_name> },
+            quote! {
+                let location = result.extra.as_ref().map(|vo| format!("{}/{}", #crud_path, vo.id));
+                Ok(robotech::web::RoResponse::created(result, location))
+            },
+        )
+    } else {
+        (
+            quote! { OK },
+            quote! { Json<Ro<#vo_name>> },
+            quote! { Ok(Json(result)) },
+        )
+    };
     generated_methods.push(quote! {
         /// # 添加新的记录
         ///
@@ -69,19 +120,20 @@ pub(crate) fn ctrl_macro(input: ItemStruct) -> TokenStream {
         #[utoipa::path(
             post,
             path = #crud_path,
-            responses((status = OK, body = Ro<#vo_name>))
+            request_body = #add_dto_name,
+            responses((status = #add_status, body = Ro<#vo_name>))
         )]
         #[debug_handler]
         #[log_call]
         pub async fn add(
             headers: HeaderMap,
-            Json(mut dto): Json<#add_dto_name>,
-        ) -> Result<Json<Ro<#vo_name>>, CtrlError> {
+            Json(dto): Json<#add_dto_name>,
+        ) -> Result<#add_return_type, CtrlError> {
             // 从header中解析当前用户ID，如果没有或解析失败则抛出ValidationError
-            dto._current_user_id = get_current_user_id(&headers)?;
+            let current_user_id = get_current_user_id(&headers)?;
 
-            let result = #svc_name::add::<DatabaseTransaction>(dto, None).await?;
-            Ok(Json(result))
+            let result = #svc_name::add::<DatabaseTransaction>(dto, current_user_id, None).await?;
+            #add_return
         }
     });
 
@@ -108,18 +160,19 @@ pub(crate) fn ctrl_macro(input: ItemStruct) -> TokenStream {
         #[utoipa::path(
             put,
             path = #crud_path,
+            request_body = #modify_dto_name,
             responses((status = OK, body = Ro<#vo_name>))
         )]
         #[debug_handler]
         #[log_call]
         pub async fn modify(
             headers: HeaderMap,
-            Json(mut dto): Json<#modify_dto_name>,
+            Json(dto): Json<#modify_dto_name>,
         ) -> Result<Json<Ro<#vo_name>>, CtrlError> {
             // 从header中解析当前用户ID，如果没有或解析失败则抛出ValidationError
-            dto._current_user_id = get_current_user_id(&headers)?;
+            let current_user_id = get_current_user_id(&headers)?;
 
-            let result = #svc_name::modify::<DatabaseTransaction>(dto, None).await?;
+            let result = #svc_name::modify::<DatabaseTransaction>(dto, current_user_id, None).await?;
             Ok(Json(result))
         }
     });
@@ -147,18 +200,19 @@ pub(crate) fn ctrl_macro(input: ItemStruct) -> TokenStream {
         #[utoipa::path(
             post,
             path = #save_path,
+            request_body = #save_dto_name,
             responses((status = OK, body = Ro<#vo_name>))
         )]
         #[debug_handler]
         #[log_call]
         pub async fn save(
             headers: HeaderMap,
-            Json(mut dto): Json<#save_dto_name>,
+            Json(dto): Json<#save_dto_name>,
         ) -> Result<Json<Ro<#vo_name>>, CtrlError> {
             // 从header中解析当前用户ID，如果没有或解析失败则抛出ValidationError
-            dto._current_user_id = get_current_user_id(&headers)?;
+            let current_user_id = get_current_user_id(&headers)?;
 
-            let result = #svc_name::save::<DatabaseTransaction>(dto, None).await?;
+            let result = #svc_name::save::<DatabaseTransaction>(dto, current_user_id, None).await?;
             Ok(Json(result))
         }
     });
@@ -172,6 +226,9 @@ pub(crate) fn ctrl_macro(input: ItemStruct) -> TokenStream {
         /// ## 请求参数
         /// * `id` - 待删除记录的唯一标识符，类型为u64
         ///
+        /// ## 请求头
+        /// * `USER_ID_HEADER_NAME` - 当前用户ID，必需项，类型为u64
+        ///
         /// ## 错误处理
         /// * 当缺少参数`id`时，返回`ValidationError`错误
         /// * 当参数`id`格式不正确时，返回`ValidationError`错误
@@ -187,9 +244,13 @@ pub(crate) fn ctrl_macro(input: ItemStruct) -> TokenStream {
         #[debug_handler]
         #[log_call]
         pub async fn del_by_id(
+            headers: HeaderMap,
             Path(id): Path<u64>,
         ) -> Result<Json<Ro<#vo_name>>, CtrlError> {
-            let ro = #svc_name::del_by_id::<DatabaseTransaction>(id, None).await?;
+            // 从header中解析当前用户ID，如果没有或解析失败则抛出ValidationError
+            let current_user_id = get_current_user_id(&headers)?;
+
+            let ro = #svc_name::del_by_id::<DatabaseTransaction>(id, current_user_id, None).await?;
             Ok(Json(ro))
         }
     });
@@ -203,6 +264,9 @@ pub(crate) fn ctrl_macro(input: ItemStruct) -> TokenStream {
         /// ## 请求体
         /// * `DelByQueryDto` - 封装查询条件的结构体
         ///
+        /// ## 请求头
+        /// * `USER_ID_HEADER_NAME` - 当前用户ID，必需项，类型为u64
+        ///
         /// ## 错误处理
         /// * 当缺少必要参数时，返回`ValidationError`错误
         /// * 当参数格式不正确时，返回`ValidationError`错误
@@ -215,8 +279,14 @@ pub(crate) fn ctrl_macro(input: ItemStruct) -> TokenStream {
         )]
         #[debug_handler]
         #[log_call]
-        pub async fn del_by_query_dto(Query(dto): Query<#query_dto_name>) -> Result<Json<Ro<()>>, CtrlError> {
-            let ro = #svc_name::del_by_query_dto::<DatabaseTransaction>(dto, None).await?;
+        pub async fn del_by_query_dto(
+            headers: HeaderMap,
+            Query(dto): Query<#query_dto_name>,
+        ) -> Result<Json<Ro<()>>, CtrlError> {
+            // 从header中解析当前用户ID，如果没有或解析失败则抛出ValidationError
+            let current_user_id = get_current_user_id(&headers)?;
+
+            let ro = #svc_name::del_by_query_dto::<DatabaseTransaction>(dto, current_user_id, None).await?;
             Ok(Json(ro))
         }
     });
@@ -340,6 +410,47 @@ pub(crate) fn ctrl_macro(input: ItemStruct) -> TokenStream {
         }
     });
 
+    // 生成export_by_query_dto方法
+    if args.export {
+        generated_methods.push(quote! {
+            /// # 导出记录
+            ///
+            /// 该接口通过查询参数(与查询记录列表相同的过滤及排序)以CSV格式导出所有匹配的记录，
+            /// 以流的方式逐条读取及写入，不会一次性加载到内存中
+            ///
+            /// ## 查询参数
+            /// * `QueryDto` - 包含查询条件的结构体
+            ///
+            /// ## 返回值
+            /// * 成功时返回CSV文件(表头为Vo的字段名)
+            /// * 导出中途出错时响应体会被中断，客户端会收到不完整的文件
+            #[utoipa::path(
+                get,
+                path = #export_by_query_dto_path,
+                params(#query_dto_name),
+                responses(
+                    (status = OK, content_type = "text/csv", body = String)
+                )
+            )]
+            #[log_call]
+            pub async fn export_by_query_dto(Query(dto): Query<#query_dto_name>) -> Result<robotech::web::RoFile, CtrlError> {
+                let db_conn = robotech::db::get_read_db_conn().map_err(robotech::svc::SvcError::from)?;
+                let (mut csv_sender, ro_file) = robotech::web::RoFile::csv(#export_file_name, #vo_name::FIELD_NAMES);
+                tokio::spawn(async move {
+                    let result: Result<u64, CtrlError> = async {
+                        let stream = #svc_name::stream_by_query_dto(dto, db_conn.as_ref()).await?;
+                        csv_sender.send_stream(stream, #vo_name::from).await
+                    }
+                    .await;
+                    if let Err(e) = result {
+                        csv_sender.abort(e).await;
+                    }
+                });
+                Ok(ro_file)
+            }
+        });
+    }
+
     let expanded = quote! {
         use axum::debug_handler;
         use axum::extract::{Path, Query};
@@ -26,6 +26,8 @@ impl Parse for RoutesArgs {
 #[derive(Debug, Default)]
 pub(crate) struct RouterArgs {
     crud: bool,
+    /// 添加 `#[ctrl(export)]` 生成的导出接口的路由
+    export: bool,
     routes: Vec<RoutesArgs>,
 }
 
@@ -42,6 +44,9 @@ impl Parse for RouterArgs {
                 "crud" => {
                     result.crud = true;
                 }
+                "export" => {
+                    result.export = true;
+                }
                 "routes" => {
                     // 解析 routes 后面的数组，数组元素是元组 (path, handler)
                     let content;
@@ -100,6 +105,7 @@ pub(crate) fn router_macro(args: RouterArgs, input: ItemStruct) -> TokenStream {
     let get_by_query_dto_path = crud_path.clone();
     let list_by_query_dto_path = format!("{crud_path}/list");
     let page_by_query_dto_path = format!("{crud_path}/page");
+    let export_by_query_dto_path = format!("{crud_path}/export");
     let mut routes = vec![];
 
     for route_args in &args.routes {
@@ -118,6 +124,10 @@ pub(crate) fn router_macro(args: RouterArgs, input: ItemStruct) -> TokenStream {
         routes.push(quote! {#get_by_id_path, get(get_by_id)}); // 这个放在后面，避免覆盖前面的list和page
     }
 
+    if args.export {
+        routes.push(quote! {#export_by_query_dto_path, get(export_by_query_dto)});
+    }
+
     let expanded = quote! {
         use crate::web::ctrl::#ctrl_module::*;
         use axum::{
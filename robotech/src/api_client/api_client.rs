@@ -1,5 +1,7 @@
 use crate::api_client::api_client_config::{ApiAuthStrategy, ApiClientConfig, Claim};
-use crate::api_client::ApiClientError;
+use crate::api_client::{ApiClientError, RequestOptions};
+use crate::cst::request_id_cst::REQUEST_ID_HEADER_NAME;
+use crate::ctx::current_request_id;
 use crate::ro::Ro;
 use chrono::Utc;
 use http::header::HeaderMap;
@@ -9,19 +11,199 @@ use reqwest::{Client, RequestBuilder, Response};
 use robotech_macros::log_call;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::fmt;
 use std::fmt::Debug;
 use std::str::FromStr;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 use wheel_rs::urn_utils::Urn;
 
 pub static REQWEST_CLIENT: LazyLock<Client> = LazyLock::new(|| Client::new());
 
-#[derive(Debug, Clone)]
+/// # 请求钩子
+///
+/// 在每次发送请求之前对构建好的RequestBuilder做最后的修改，如对请求体做HMAC签名、添加一次性随机数等
+pub type RequestHook = Arc<dyn Fn(RequestBuilder) -> RequestBuilder + Send + Sync>;
+
+#[derive(Clone)]
 pub struct ApiClient {
     pub api_client_config: ApiClientConfig,
+    /// 请求钩子(在每次发送请求前调用)
+    pub request_hook: Option<RequestHook>,
+    /// 按配置的超时时间创建的HTTP客户端
+    ///
+    /// 通过 `new`/`builder` 创建时自动设置；直接构造时可设置为None，此时使用共享的 `REQWEST_CLIENT`(不超时)
+    pub client: Option<Client>,
+}
+
+impl Debug for ApiClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiClient")
+            .field("api_client_config", &self.api_client_config)
+            .field("request_hook", &self.request_hook.as_ref().map(|_| "Fn"))
+            .finish()
+    }
+}
+
+/// # API客户端构建器
+///
+/// ## 使用示例
+/// ```
+/// let api_client = ApiClient::builder("http://svc")
+///     .timeout(Duration::from_secs(5))
+///     .build()?;
+/// ```
+pub struct ApiClientBuilder {
+    api_client_config: ApiClientConfig,
+    request_hook: Option<RequestHook>,
+}
+
+impl ApiClientBuilder {
+    /// 设置请求超时时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.api_client_config.timeout = Some(timeout);
+        self
+    }
+
+    /// 设置连接超时时间
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.api_client_config.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// 设置超时或连接失败时的最大重试次数
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.api_client_config.max_retries = max_retries;
+        self
+    }
+
+    /// 设置第一次重试前的等待时间，之后每次重试翻倍
+    pub fn retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.api_client_config.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// 设置传递用户ID的请求头名称
+    pub fn user_id_header(mut self, user_id_header: impl Into<String>) -> Self {
+        self.api_client_config.user_id_header = Some(user_id_header.into());
+        self
+    }
+
+    /// 设置请求钩子
+    pub fn request_hook<F>(mut self, request_hook: F) -> Self
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder + Send + Sync + 'static,
+    {
+        self.request_hook = Some(Arc::new(request_hook));
+        self
+    }
+
+    /// # 构建API客户端
+    ///
+    /// base_url 不是合法的绝对URL时返回 `ApiClientError::InvalidBaseUrl`
+    pub fn build(self) -> Result<ApiClient, ApiClientError> {
+        let mut api_client = ApiClient::new(self.api_client_config)?;
+        api_client.request_hook = self.request_hook;
+        Ok(api_client)
+    }
+}
+
+/// # 按配置创建HTTP客户端
+fn build_client(api_client_config: &ApiClientConfig) -> Result<Client, ApiClientError> {
+    let mut client_builder = Client::builder();
+    if let Some(timeout) = api_client_config.timeout {
+        client_builder = client_builder.timeout(timeout);
+    }
+    if let Some(connect_timeout) = api_client_config.connect_timeout {
+        client_builder = client_builder.connect_timeout(connect_timeout);
+    }
+    client_builder
+        .build()
+        .map_err(|e| ApiClientError::SetApiClient(format!("创建HTTP客户端失败: {e}")))
+}
+
+/// # 校验基础URL
+///
+/// 必须是以 http:// 或 https:// 开头的绝对URL
+fn validate_base_url(base_url: &str) -> Result<(), ApiClientError> {
+    let url = reqwest::Url::parse(base_url)
+        .map_err(|e| ApiClientError::InvalidBaseUrl(base_url.to_string(), e.to_string()))?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(ApiClientError::InvalidBaseUrl(
+            base_url.to_string(),
+            "协议必须是http或https".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// # 检查Ro的结果
+///
+/// 结果为Success时返回Ro中的额外数据，否则返回 `ApiClientError::BusinessError`
+pub(crate) fn check_ro<T>(ro: Ro<T>) -> Result<Option<T>, ApiClientError> {
+    if ro.is_err() {
+        return Err(ApiClientError::BusinessError {
+            result: ro.result,
+            code: ro.code,
+            msg: ro.msg,
+        });
+    }
+    Ok(ro.extra)
+}
+
+/// # 拼接基础URL与请求路径
+///
+/// 合并两者交界处重复的斜杠，如 `http://svc/` + `/user` -> `http://svc/user`
+fn join_url(base_url: &str, uri: &str) -> String {
+    if uri.is_empty() {
+        return base_url.to_string();
+    }
+    if uri.starts_with('?') {
+        return format!("{}{}", base_url.trim_end_matches('/'), uri);
+    }
+    format!(
+        "{}/{}",
+        base_url.trim_end_matches('/'),
+        uri.trim_start_matches('/')
+    )
 }
 
 impl ApiClient {
+    /// # 创建API客户端构建器
+    ///
+    /// ## 参数
+    /// * `base_url` - API请求的基础URL，例如: http://127.0.0.1:8080
+    pub fn builder(base_url: impl Into<String>) -> ApiClientBuilder {
+        ApiClientBuilder {
+            api_client_config: ApiClientConfig::new(base_url),
+            request_hook: None,
+        }
+    }
+
+    /// # 创建API客户端
+    ///
+    /// base_url 不是合法的绝对URL时返回 `ApiClientError::InvalidBaseUrl`
+    pub fn new(api_client_config: ApiClientConfig) -> Result<Self, ApiClientError> {
+        validate_base_url(api_client_config.base_url.as_str())?;
+        let client = build_client(&api_client_config)?;
+        Ok(Self {
+            api_client_config,
+            request_hook: None,
+            client: Some(client),
+        })
+    }
+
+    /// # 设置请求钩子
+    ///
+    /// 钩子会在每个请求方法调用send之前执行，可用于实现任意的请求签名方式
+    pub fn with_request_hook<F>(mut self, request_hook: F) -> Self
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder + Send + Sync + 'static,
+    {
+        self.request_hook = Some(Arc::new(request_hook));
+        self
+    }
+
     fn build_request<D: Serialize + ?Sized>(
         &self,
         method: Method,
@@ -31,11 +213,16 @@ impl ApiClient {
         headers: Option<HeaderMap>,
         auth: Option<ApiAuthStrategy>,
     ) -> Result<(Urn, RequestBuilder), ApiClientError> {
-        let url = format!("{}{}", self.api_client_config.base_url, uri);
+        let url = join_url(self.api_client_config.base_url.as_str(), uri);
         let urn = Urn::from_str(&format!("{method}:{url}"))
             .map_err(|e| ApiClientError::SetApiClient(format!("解析url失败: {e}")))?;
         tracing::debug!("request: {urn}....");
-        let mut request_builder = REQWEST_CLIENT.request(method, &url);
+        let client = self.client.as_ref().unwrap_or(&REQWEST_CLIENT);
+        let mut request_builder = client.request(method, &url);
+        // 在处理请求期间调用时，将当前请求ID传递给下游服务(可被传入的headers覆盖)
+        if let Some(request_id) = current_request_id() {
+            request_builder = request_builder.header(REQUEST_ID_HEADER_NAME, request_id);
+        }
         if let Some(headers) = headers {
             request_builder = request_builder.headers(headers);
         }
@@ -83,11 +270,67 @@ impl ApiClient {
         Ok((urn, request_builder))
     }
 
-    async fn send(urn: &Urn, request_builder: RequestBuilder) -> Result<Response, ApiClientError> {
-        let response = request_builder
-            .send()
-            .await
-            .map_err(|e| ApiClientError::Request(urn.to_string(), e))?;
+    /// # 按请求选项构建请求
+    ///
+    /// 配置了 `user_id_header` 且请求选项中指定了用户ID时添加用户ID请求头，否则不添加
+    fn build_request_with_options<B: Serialize + ?Sized>(
+        &self,
+        method: Method,
+        uri: &str,
+        body: Option<&B>,
+        mut options: RequestOptions,
+    ) -> Result<(Urn, RequestBuilder), ApiClientError> {
+        let auth = options.auth.take();
+        let (urn, mut request_builder) = self.build_request(method, uri, None, body, None, auth)?;
+        // 传递用户ID(可被请求选项中的请求头覆盖)
+        if let (Some(user_id_header), Some(user_id)) =
+            (&self.api_client_config.user_id_header, options.user_id)
+        {
+            request_builder = request_builder.header(user_id_header.as_str(), user_id);
+        }
+        Ok((urn, options.apply(request_builder)))
+    }
+
+    /// # 发送请求
+    ///
+    /// 超时或连接失败时按配置的次数重试(等待时间每次翻倍)，请求钩子在每次发送前都会调用；
+    /// 请求体是流(如multipart)时无法克隆请求，不会重试
+    async fn send(
+        &self,
+        urn: &Urn,
+        mut request_builder: RequestBuilder,
+    ) -> Result<Response, ApiClientError> {
+        let max_retries = self.api_client_config.max_retries;
+        let mut backoff = self.api_client_config.retry_backoff;
+        let mut retries = 0;
+        let response = loop {
+            let retry_builder = if retries < max_retries {
+                request_builder.try_clone()
+            } else {
+                None
+            };
+            let mut hooked_builder = request_builder;
+            if let Some(request_hook) = &self.request_hook {
+                hooked_builder = request_hook(hooked_builder);
+            }
+            let error = match hooked_builder.send().await {
+                Ok(response) => break response,
+                Err(error) => error,
+            };
+            match retry_builder {
+                Some(retry_builder) if error.is_timeout() || error.is_connect() => {
+                    retries += 1;
+                    tracing::warn!("{urn} 请求失败，{backoff:?}后第{retries}次重试: {error}");
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2);
+                    request_builder = retry_builder;
+                }
+                _ if error.is_timeout() => {
+                    return Err(ApiClientError::Timeout(urn.to_string(), error));
+                }
+                _ => return Err(ApiClientError::Request(urn.to_string(), error)),
+            }
+        };
         tracing::debug!("{urn} response....");
         // 检查状态码，如果不是成功状态码则转换为错误
         let status_code = response.status();
@@ -133,7 +376,7 @@ impl ApiClient {
     {
         let (urn, request_builder) =
             self.build_request(method, uri, params, body, headers, auth)?;
-        let response = Self::send(&urn, request_builder).await?;
+        let response = self.send(&urn, request_builder).await?;
         Self::response_json(&urn, response).await
     }
 
@@ -170,7 +413,56 @@ impl ApiClient {
     ) -> Result<Ro<serde_json::Value>, ApiClientError> {
         let (urn, request_builder) =
             self.build_request(Method::GET, uri, params, None, headers, auth)?;
-        let response = Self::send(&urn, request_builder).await?;
+        let response = self.send(&urn, request_builder).await?;
+        Self::response_json(&urn, response).await
+    }
+
+    /// 执行GET请求，将响应解析为指定类型的Ro
+    ///
+    /// 响应的结构与指定的类型不匹配时返回 `ApiClientError::ParseJson`
+    #[log_call]
+    pub async fn get_as<T, D>(
+        &self,
+        uri: &str,
+        params: Option<&D>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> Result<Ro<T>, ApiClientError>
+    where
+        T: DeserializeOwned + Debug,
+        D: Serialize + ?Sized + Debug,
+    {
+        self.request(Method::GET, uri, params, None, headers, auth)
+            .await
+    }
+
+    /// 执行GET请求，检查Ro的结果并返回额外数据
+    ///
+    /// Ro的结果不是Success时返回 `ApiClientError::BusinessError`，需要完整的Ro时请使用 `get_as`
+    pub async fn get_checked<T, D>(
+        &self,
+        uri: &str,
+        params: Option<&D>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> Result<Option<T>, ApiClientError>
+    where
+        T: DeserializeOwned + Debug,
+        D: Serialize + ?Sized + Debug,
+    {
+        check_ro(self.get_as(uri, params, headers, auth).await?)
+    }
+
+    /// 执行GET请求，附加请求选项(请求头、查询参数、认证、用户ID等)
+    #[log_call(skip = options)]
+    pub async fn get_with(
+        &self,
+        uri: &str,
+        options: RequestOptions,
+    ) -> Result<Ro<serde_json::Value>, ApiClientError> {
+        let (urn, request_builder) =
+            self.build_request_with_options::<()>(Method::GET, uri, None, options)?;
+        let response = self.send(&urn, request_builder).await?;
         Self::response_json(&urn, response).await
     }
 
@@ -185,7 +477,7 @@ impl ApiClient {
     ) -> Result<Vec<u8>, ApiClientError> {
         let (urn, request_builder) =
             self.build_request(Method::GET, uri, params, None, headers, auth)?;
-        let response = Self::send(&urn, request_builder).await?;
+        let response = self.send(&urn, request_builder).await?;
         let result = response
             .bytes()
             .await
@@ -205,9 +497,78 @@ impl ApiClient {
     ) -> Result<Ro<serde_json::Value>, ApiClientError> {
         let (urn, request_builder) =
             self.build_request(Method::POST, uri, None, body, headers, auth)?;
-        let response = Self::send(&urn, request_builder).await?;
+        let response = self.send(&urn, request_builder).await?;
+        Self::response_json(&urn, response).await
+    }
+
+    /// 执行POST请求，将响应解析为指定类型的Ro
+    ///
+    /// 响应的结构与指定的类型不匹配时返回 `ApiClientError::ParseJson`
+    #[log_call]
+    pub async fn post_as<T, B>(
+        &self,
+        uri: &str,
+        body: Option<&B>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> Result<Ro<T>, ApiClientError>
+    where
+        T: DeserializeOwned + Debug,
+        B: Serialize + ?Sized + Debug,
+    {
+        self.request(Method::POST, uri, None, body, headers, auth)
+            .await
+    }
+
+    /// 执行POST请求，检查Ro的结果并返回额外数据
+    ///
+    /// Ro的结果不是Success时返回 `ApiClientError::BusinessError`，需要完整的Ro时请使用 `post_as`
+    pub async fn post_checked<T, B>(
+        &self,
+        uri: &str,
+        body: Option<&B>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> Result<Option<T>, ApiClientError>
+    where
+        T: DeserializeOwned + Debug,
+        B: Serialize + ?Sized + Debug,
+    {
+        check_ro(self.post_as(uri, body, headers, auth).await?)
+    }
+
+    /// 执行POST请求，附加请求选项(请求头、查询参数、认证、用户ID等)
+    #[log_call(skip = options)]
+    pub async fn post_with<B: Serialize + ?Sized + Debug>(
+        &self,
+        uri: &str,
+        body: Option<&B>,
+        options: RequestOptions,
+    ) -> Result<Ro<serde_json::Value>, ApiClientError> {
+        let (urn, request_builder) =
+            self.build_request_with_options(Method::POST, uri, body, options)?;
+        let response = self.send(&urn, request_builder).await?;
         Self::response_json(&urn, response).await
     }
+
+    /// 执行POST请求，只检查状态码，不解析响应体
+    ///
+    /// 用于返回 `204 No Content` 或非Ro响应体(如 `OK`)的接口
+    #[log_call]
+    pub async fn post_no_content<D: Serialize + ?Sized + std::fmt::Debug>(
+        &self,
+        uri: &str,
+        body: Option<&D>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> Result<(), ApiClientError> {
+        let (urn, request_builder) =
+            self.build_request(Method::POST, uri, None, body, headers, auth)?;
+        self.send(&urn, request_builder).await?;
+        tracing::debug!("{urn} response.");
+        Ok(())
+    }
+
     /// 执行PUT请求的通用方法
     #[log_call]
     pub async fn put<D: Serialize + ?Sized + std::fmt::Debug>(
@@ -219,9 +580,24 @@ impl ApiClient {
     ) -> Result<Ro<serde_json::Value>, ApiClientError> {
         let (urn, request_builder) =
             self.build_request(Method::PUT, uri, None, Some(body), headers, auth)?;
-        let response = Self::send(&urn, request_builder).await?;
+        let response = self.send(&urn, request_builder).await?;
+        Self::response_json(&urn, response).await
+    }
+
+    /// 执行PUT请求，附加请求选项(请求头、查询参数、认证、用户ID等)
+    #[log_call(skip = options)]
+    pub async fn put_with<B: Serialize + ?Sized + Debug>(
+        &self,
+        uri: &str,
+        body: &B,
+        options: RequestOptions,
+    ) -> Result<Ro<serde_json::Value>, ApiClientError> {
+        let (urn, request_builder) =
+            self.build_request_with_options(Method::PUT, uri, Some(body), options)?;
+        let response = self.send(&urn, request_builder).await?;
         Self::response_json(&urn, response).await
     }
+
     /// 执行DELETE请求的通用方法
     #[log_call]
     pub async fn delete<D: Serialize + ?Sized + std::fmt::Debug>(
@@ -233,7 +609,21 @@ impl ApiClient {
     ) -> Result<Ro<serde_json::Value>, ApiClientError> {
         let (urn, request_builder) =
             self.build_request(Method::DELETE, uri, None, body, headers, auth)?;
-        let response = Self::send(&urn, request_builder).await?;
+        let response = self.send(&urn, request_builder).await?;
+        Self::response_json(&urn, response).await
+    }
+
+    /// 执行DELETE请求，附加请求选项(请求头、查询参数、认证、用户ID等)
+    #[log_call(skip = options)]
+    pub async fn delete_with<B: Serialize + ?Sized + Debug>(
+        &self,
+        uri: &str,
+        body: Option<&B>,
+        options: RequestOptions,
+    ) -> Result<Ro<serde_json::Value>, ApiClientError> {
+        let (urn, request_builder) =
+            self.build_request_with_options(Method::DELETE, uri, body, options)?;
+        let response = self.send(&urn, request_builder).await?;
         Self::response_json(&urn, response).await
     }
 
@@ -249,7 +639,62 @@ impl ApiClient {
         let (urn, mut request_builder) =
             self.build_request::<String>(Method::POST, uri, None, None, headers, auth)?;
         request_builder = request_builder.multipart(form);
-        let response = Self::send(&urn, request_builder).await?;
+        let response = self.send(&urn, request_builder).await?;
         Self::response_json(&urn, response).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_url_merges_slashes() {
+        for (base_url, uri, expected) in [
+            ("http://svc", "user", "http://svc/user"),
+            ("http://svc", "/user", "http://svc/user"),
+            ("http://svc/", "user", "http://svc/user"),
+            ("http://svc/", "/user", "http://svc/user"),
+            ("http://svc//", "//user", "http://svc/user"),
+            ("http://svc/api", "/user/1", "http://svc/api/user/1"),
+            ("http://svc/api/", "user?id=1", "http://svc/api/user?id=1"),
+            ("http://svc/api/", "?id=1", "http://svc/api?id=1"),
+            ("http://svc/api", "", "http://svc/api"),
+            ("http://svc/api/", "", "http://svc/api/"),
+        ] {
+            assert_eq!(join_url(base_url, uri), expected, "{base_url} + {uri}");
+        }
+    }
+
+    #[test]
+    fn new_rejects_invalid_base_url() {
+        for base_url in ["", "svc", "/api", "svc/api", "ftp://svc"] {
+            assert!(
+                matches!(
+                    ApiClient::new(ApiClientConfig::new(base_url)),
+                    Err(ApiClientError::InvalidBaseUrl(..))
+                ),
+                "{base_url}"
+            );
+        }
+        assert!(ApiClient::new(ApiClientConfig::new("http://127.0.0.1:8080")).is_ok());
+        assert!(ApiClient::builder("https://svc/api/").build().is_ok());
+    }
+
+    #[test]
+    fn struct_literal_uses_shared_client() {
+        let api_client = ApiClient {
+            api_client_config: ApiClientConfig {
+                base_url: "http://svc/api".to_string(),
+                ..Default::default()
+            },
+            request_hook: None,
+            client: None,
+        };
+        let (_, request_builder) = api_client
+            .build_request::<()>(Method::GET, "/user", None, None, None, None)
+            .unwrap();
+        let request = request_builder.build().unwrap();
+        assert_eq!(request.url().as_str(), "http://svc/api/user");
+    }
+}
@@ -4,6 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
+use wheel_rs::serde::{duration_option_serde, duration_serde};
 
 /// # API配置结构体
 ///
@@ -16,6 +17,55 @@ pub struct ApiClientConfig {
     /// 例如: http://127.0.0.1:8080
     #[serde()]
     pub base_url: String,
+
+    /// 请求超时时间(从发送请求到读取完响应体，不设置则不超时)
+    #[serde(with = "duration_option_serde", default)]
+    pub timeout: Option<Duration>,
+
+    /// 连接超时时间(不设置则不超时)
+    #[serde(with = "duration_option_serde", default)]
+    pub connect_timeout: Option<Duration>,
+
+    /// 超时或连接失败时的最大重试次数(默认0，不重试)
+    ///
+    /// 服务端返回了非2xx状态码时不会重试
+    #[serde(default)]
+    pub max_retries: u32,
+
+    /// 第一次重试前的等待时间(默认200毫秒)，之后每次重试翻倍
+    #[serde(with = "duration_serde", default = "retry_backoff_default")]
+    pub retry_backoff: Duration,
+
+    /// 传递用户ID的请求头名称(不设置则不传递用户ID)
+    ///
+    /// 调用内部服务时一般设置为 `X-User-Id`，调用第三方接口时不要设置
+    #[serde(default)]
+    pub user_id_header: Option<String>,
+}
+
+/// 默认配置(base_url 为空，需要自行设置)
+///
+/// 直接构造时可以写成 `ApiClientConfig { base_url, ..Default::default() }`
+impl Default for ApiClientConfig {
+    fn default() -> Self {
+        Self {
+            base_url: String::new(),
+            timeout: None,
+            connect_timeout: None,
+            max_retries: 0,
+            retry_backoff: retry_backoff_default(),
+            user_id_header: None,
+        }
+    }
+}
+
+impl ApiClientConfig {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            ..Default::default()
+        }
+    }
 }
 
 /// # API认证策略枚举
@@ -65,3 +115,7 @@ pub struct Claim {
     /// 过期时间（Unix时间戳）
     pub exp: i64,
 }
+
+fn retry_backoff_default() -> Duration {
+    Duration::from_millis(200)
+}
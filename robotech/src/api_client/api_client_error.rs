@@ -1,3 +1,4 @@
+use crate::ro::RoResult;
 use thiserror::Error;
 
 /// # 自定义API客户端错误枚举
@@ -10,9 +11,12 @@ use thiserror::Error;
 /// - `FileError`: 文件读取操作失败，通常发生在加载配置文件或证书时
 /// - `RequestError`: HTTP请求发送失败，可能是网络连接问题或请求构建错误
 /// - `ResponseError`: 获取HTTP响应失败，通常是网络超时或连接中断
+/// - `Timeout`: 请求超时(已按配置重试仍然超时)
 /// - `ResponseStatusError`: HTTP响应状态码表示错误，如4xx客户端错误或5xx服务器错误
 /// - `JsonParseError`: JSON格式响应解析失败
 /// - `BytesParseError`: 字节流格式响应解析失败
+/// - `InvalidBaseUrl`: 基础URL不是合法的绝对URL
+/// - `BusinessError`: 响应的Ro结果不是Success(由 `get_checked`/`post_checked` 等方法返回)
 #[derive(Error, Debug)]
 pub enum ApiClientError {
     #[error("文件读取错误: {0}")]
@@ -21,6 +25,8 @@ pub enum ApiClientError {
     Request(String, #[source] reqwest::Error),
     #[error("获取响应失败: {0}")]
     Response(String, #[source] reqwest::Error),
+    #[error("请求超时: {0}")]
+    Timeout(String, #[source] reqwest::Error),
     #[error("JWT编码失败: {0}")]
     Jwt(#[from] jsonwebtoken::errors::Error),
     /// 响应状态非2xx
@@ -36,4 +42,15 @@ pub enum ApiClientError {
     ParseBytes(String, #[source] reqwest::Error),
     #[error("设置API客户端失败: {0}")]
     SetApiClient(String),
+    #[error("基础URL不合法: {0} -> {1}")]
+    InvalidBaseUrl(String, String),
+    /// 响应的Ro结果不是Success
+    ///
+    /// 携带Ro的结果、业务编码及消息，调用方可以按业务编码匹配处理
+    #[error("业务处理失败: {msg} (code: {code:?})")]
+    BusinessError {
+        result: RoResult,
+        code: Option<String>,
+        msg: String,
+    },
 }
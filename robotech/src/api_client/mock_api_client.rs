@@ -0,0 +1,94 @@
+use crate::api_client::api_client_config::ApiAuthStrategy;
+use crate::api_client::{ApiClientError, RoApiClient};
+use crate::ro::Ro;
+use http::Method;
+use http::header::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+/// # 模拟的API客户端
+///
+/// 按 请求方法+路径(不含查询参数) 返回预设的 `Ro`，用于测试依赖 `RoApiClient` 的业务代码。
+/// 预设的 `Ro` 会先序列化为JSON，调用时再按需要的类型解析，与真实的请求行为一致；
+/// 没有预设响应的请求返回 `ApiClientError::NonSuccessStatus`(404)
+///
+/// ## 使用示例
+/// ```
+/// let api_client = MockApiClient::new()
+///     .with_response(Method::GET, "/user/1", Ro::success("查询成功".to_string()).extra(Some(user_vo)));
+/// let ro = get_user(&api_client, 1).await?;
+/// assert_eq!(api_client.call_count(Method::GET, "/user/1"), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct MockApiClient {
+    /// 预设的响应
+    responses: HashMap<(Method, String), Value>,
+    /// 调用次数
+    call_counts: Mutex<HashMap<(Method, String), usize>>,
+}
+
+impl MockApiClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # 预设响应
+    ///
+    /// 同一请求方法+路径重复设置时覆盖之前的响应
+    pub fn with_response<E: Serialize>(mut self, method: Method, path: &str, ro: Ro<E>) -> Self {
+        let ro = serde_json::to_value(ro).expect("序列化预设的Ro失败");
+        self.responses
+            .insert((method, path_without_query(path)), ro);
+        self
+    }
+
+    /// 获取请求方法+路径被调用的次数
+    pub fn call_count(&self, method: Method, path: &str) -> usize {
+        self.call_counts
+            .lock()
+            .map(|call_counts| {
+                call_counts
+                    .get(&(method, path_without_query(path)))
+                    .copied()
+                    .unwrap_or_default()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl RoApiClient for MockApiClient {
+    async fn request<D, E>(
+        &self,
+        method: Method,
+        uri: &str,
+        _params: Option<&D>,
+        _body: Option<&D>,
+        _headers: Option<HeaderMap>,
+        _auth: Option<ApiAuthStrategy>,
+    ) -> Result<Ro<E>, ApiClientError>
+    where
+        D: Serialize + ?Sized + Debug + Sync,
+        E: DeserializeOwned + Debug,
+    {
+        let key = (method, path_without_query(uri));
+        let urn = format!("{}:{}", key.0, key.1);
+        if let Ok(mut call_counts) = self.call_counts.lock() {
+            *call_counts.entry(key.clone()).or_default() += 1;
+        }
+        let ro = self.responses.get(&key).ok_or_else(|| {
+            ApiClientError::NonSuccessStatus(urn.clone(), "404 Not Found".to_string())
+        })?;
+        serde_json::from_value(ro.clone()).map_err(|e| ApiClientError::ParseJson(urn, e))
+    }
+}
+
+/// 去掉路径中的查询参数
+fn path_without_query(path: &str) -> String {
+    path.split_once('?')
+        .map_or(path, |(path, _)| path)
+        .to_string()
+}
@@ -1,10 +1,16 @@
 mod api_client;
 mod api_client_config;
 mod api_client_error;
+mod mock_api_client;
+mod request_options;
+mod ro_api_client;
 mod webhook_config;
 
 // 重新导出结构体，简化外部引用
 pub use api_client::*;
 pub use api_client_config::*;
 pub use api_client_error::*;
+pub use mock_api_client::*;
+pub use request_options::*;
+pub use ro_api_client::*;
 pub use webhook_config::*;
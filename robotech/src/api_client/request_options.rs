@@ -0,0 +1,81 @@
+use crate::api_client::api_client_config::ApiAuthStrategy;
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::RequestBuilder;
+
+/// # 请求选项
+///
+/// 为单个请求附加请求头、查询参数、认证策略及用户ID，传给 `get_with`/`post_with`/`put_with`/`delete_with` 方法使用。
+/// 查询参数会进行URL编码并追加到请求路径中已有的查询参数之后
+///
+/// ## 使用示例
+/// ```
+/// let options = RequestOptions::new()
+///     .bearer_auth(token)
+///     .query("page_num", "2")
+///     .query("keyword", "张三")
+///     .user_id(Some(user_id));
+/// let ro = api_client.get_with("/user?enabled=true", options).await?;
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct RequestOptions {
+    /// 请求头(与同名的默认请求头冲突时覆盖默认请求头)
+    pub headers: HeaderMap,
+    /// 查询参数
+    pub query: Vec<(String, String)>,
+    /// Bearer令牌
+    pub bearer_token: Option<String>,
+    /// 认证策略
+    pub auth: Option<ApiAuthStrategy>,
+    /// 用户ID(客户端配置了 `user_id_header` 且用户ID不为None时通过该请求头传递，否则不传递)
+    pub user_id: Option<u64>,
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加请求头
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// 添加查询参数
+    pub fn query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.query.push((key.into(), value.into()));
+        self
+    }
+
+    /// 设置Bearer令牌(添加 `Authorization: Bearer <token>` 请求头)
+    pub fn bearer_auth(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    /// 设置认证策略
+    pub fn auth(mut self, auth: ApiAuthStrategy) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// 设置传递的用户ID(为None时不传递)
+    pub fn user_id(mut self, user_id: Option<u64>) -> Self {
+        self.user_id = user_id;
+        self
+    }
+
+    /// 将请求头、查询参数及Bearer令牌应用到请求上(认证策略及用户ID在构建请求时处理)
+    pub(crate) fn apply(self, mut request_builder: RequestBuilder) -> RequestBuilder {
+        if !self.headers.is_empty() {
+            request_builder = request_builder.headers(self.headers);
+        }
+        if !self.query.is_empty() {
+            request_builder = request_builder.query(&self.query);
+        }
+        if let Some(bearer_token) = self.bearer_token {
+            request_builder = request_builder.bearer_auth(bearer_token);
+        }
+        request_builder
+    }
+}
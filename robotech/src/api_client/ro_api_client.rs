@@ -0,0 +1,158 @@
+use crate::api_client::api_client::check_ro;
+use crate::api_client::api_client_config::ApiAuthStrategy;
+use crate::api_client::{ApiClient, ApiClientError};
+use crate::ro::Ro;
+use http::Method;
+use http::header::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt::Debug;
+
+/// # 返回Ro的API客户端
+///
+/// 抽象出调用下游服务并返回 `Ro` 的方法，业务代码依赖此trait而不是具体的 `ApiClient`，
+/// 测试时可以注入 `MockApiClient`，不需要启动HTTP服务
+///
+/// ## 使用示例
+/// ```
+/// async fn get_user(api_client: &impl RoApiClient, id: u64) -> Result<Ro<Value>, ApiClientError> {
+///     api_client.get::<()>(&format!("/user/{id}"), None, None, None).await
+/// }
+/// ```
+pub trait RoApiClient: Send + Sync {
+    /// 执行请求的通用方法
+    fn request<D, E>(
+        &self,
+        method: Method,
+        uri: &str,
+        params: Option<&D>,
+        body: Option<&D>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> impl Future<Output = Result<Ro<E>, ApiClientError>> + Send
+    where
+        D: Serialize + ?Sized + Debug + Sync,
+        E: DeserializeOwned + Debug;
+
+    /// 执行GET请求的通用方法
+    fn get<D: Serialize + ?Sized + Debug + Sync>(
+        &self,
+        uri: &str,
+        params: Option<&D>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> impl Future<Output = Result<Ro<serde_json::Value>, ApiClientError>> + Send {
+        self.request(Method::GET, uri, params, None, headers, auth)
+    }
+
+    /// 执行GET请求，将响应解析为指定类型的Ro
+    fn get_as<T, D>(
+        &self,
+        uri: &str,
+        params: Option<&D>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> impl Future<Output = Result<Ro<T>, ApiClientError>> + Send
+    where
+        T: DeserializeOwned + Debug,
+        D: Serialize + ?Sized + Debug + Sync,
+    {
+        self.request(Method::GET, uri, params, None, headers, auth)
+    }
+
+    /// 执行POST请求的通用方法
+    fn post<D: Serialize + ?Sized + Debug + Sync>(
+        &self,
+        uri: &str,
+        body: Option<&D>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> impl Future<Output = Result<Ro<serde_json::Value>, ApiClientError>> + Send {
+        self.request(Method::POST, uri, None, body, headers, auth)
+    }
+
+    /// 执行POST请求，将响应解析为指定类型的Ro
+    fn post_as<T, B>(
+        &self,
+        uri: &str,
+        body: Option<&B>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> impl Future<Output = Result<Ro<T>, ApiClientError>> + Send
+    where
+        T: DeserializeOwned + Debug,
+        B: Serialize + ?Sized + Debug + Sync,
+    {
+        self.request(Method::POST, uri, None, body, headers, auth)
+    }
+
+    /// 执行GET请求，检查Ro的结果并返回额外数据
+    fn get_checked<T, D>(
+        &self,
+        uri: &str,
+        params: Option<&D>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> impl Future<Output = Result<Option<T>, ApiClientError>> + Send
+    where
+        T: DeserializeOwned + Debug,
+        D: Serialize + ?Sized + Debug + Sync,
+    {
+        async move { check_ro(self.get_as(uri, params, headers, auth).await?) }
+    }
+
+    /// 执行POST请求，检查Ro的结果并返回额外数据
+    fn post_checked<T, B>(
+        &self,
+        uri: &str,
+        body: Option<&B>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> impl Future<Output = Result<Option<T>, ApiClientError>> + Send
+    where
+        T: DeserializeOwned + Debug,
+        B: Serialize + ?Sized + Debug + Sync,
+    {
+        async move { check_ro(self.post_as(uri, body, headers, auth).await?) }
+    }
+
+    /// 执行PUT请求的通用方法
+    fn put<D: Serialize + ?Sized + Debug + Sync>(
+        &self,
+        uri: &str,
+        headers: Option<HeaderMap>,
+        body: &D,
+        auth: Option<ApiAuthStrategy>,
+    ) -> impl Future<Output = Result<Ro<serde_json::Value>, ApiClientError>> + Send {
+        self.request(Method::PUT, uri, None, Some(body), headers, auth)
+    }
+
+    /// 执行DELETE请求的通用方法
+    fn delete<D: Serialize + ?Sized + Debug + Sync>(
+        &self,
+        uri: &str,
+        body: Option<&D>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> impl Future<Output = Result<Ro<serde_json::Value>, ApiClientError>> + Send {
+        self.request(Method::DELETE, uri, None, body, headers, auth)
+    }
+}
+
+impl RoApiClient for ApiClient {
+    async fn request<D, E>(
+        &self,
+        method: Method,
+        uri: &str,
+        params: Option<&D>,
+        body: Option<&D>,
+        headers: Option<HeaderMap>,
+        auth: Option<ApiAuthStrategy>,
+    ) -> Result<Ro<E>, ApiClientError>
+    where
+        D: Serialize + ?Sized + Debug + Sync,
+        E: DeserializeOwned + Debug,
+    {
+        ApiClient::request(self, method, uri, params, body, headers, auth).await
+    }
+}
@@ -0,0 +1,103 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info};
+
+/// # 应用状态
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum AppState {
+    /// 启动中
+    Starting,
+    /// 已启动
+    Started,
+    /// 退出中
+    Stopping,
+}
+
+/// 应用状态(启动完成及开始退出时通知后台任务)
+static APP_STATE: LazyLock<watch::Sender<AppState>> =
+    LazyLock::new(|| watch::Sender::new(AppState::Starting));
+
+/// # 设置应用状态
+///
+/// `wait_app_startup` 启动完成时会设置为 `Started`，`wait_app_exit` 收到退出信号时会设置为 `Stopping`，
+/// 没有使用这两个方法时需要自己设置，否则后台任务不会启动或停止
+pub fn set_app_state(app_state: AppState) {
+    APP_STATE.send_replace(app_state);
+}
+
+/// # 获取应用状态
+pub fn get_app_state() -> AppState {
+    *APP_STATE.borrow()
+}
+
+/// # 创建定时执行的后台任务
+///
+/// 任务在应用启动完成后才开始执行(首次立即执行，之后按间隔执行)，应用开始退出时停止(正在执行的任务会被取消)。
+/// 每次执行都在单独的tokio任务中进行，发生panic时只记录错误日志，不影响下一次执行。
+/// 上一次执行超过间隔时间时，下一次在上一次完成后间隔时间再执行，不会堆积
+///
+/// ## 参数
+/// * `name` - 任务名称(用于日志)
+/// * `interval` - 执行间隔
+/// * `task` - 每次执行的任务
+///
+/// ## 使用示例
+/// ```
+/// spawn_interval_task("清理过期会话", Duration::from_secs(60), || async {
+///     if let Err(e) = SessionSvc::clean_expired().await {
+///         warn!("清理过期会话失败: {e}");
+///     }
+/// });
+/// ```
+pub fn spawn_interval_task<F, Fut>(
+    name: impl Into<String>,
+    interval: Duration,
+    task: F,
+) -> JoinHandle<()>
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let name = name.into();
+    let mut app_state_receiver = APP_STATE.subscribe();
+    tokio::spawn(async move {
+        // 等待应用启动完成
+        let started = app_state_receiver
+            .wait_for(|app_state| *app_state != AppState::Starting)
+            .await
+            .is_ok_and(|app_state| *app_state == AppState::Started);
+        if !started {
+            return;
+        }
+
+        info!("启动后台任务<{name}>，执行间隔: {interval:?}");
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = app_state_receiver.wait_for(|app_state| *app_state == AppState::Stopping) => break,
+            }
+
+            // 在单独的任务中执行，以捕获panic
+            let mut task_handle = tokio::spawn(task());
+            tokio::select! {
+                result = &mut task_handle => {
+                    if let Err(e) = result
+                        && e.is_panic()
+                    {
+                        error!("后台任务<{name}>执行时发生panic: {e}");
+                    }
+                }
+                _ = app_state_receiver.wait_for(|app_state| *app_state == AppState::Stopping) => {
+                    task_handle.abort();
+                    break;
+                }
+            }
+        }
+        info!("停止后台任务<{name}>");
+    })
+}
@@ -1,7 +1,8 @@
-use crate::app::AppError;
+use crate::app::{AppError, AppState, RuntimeConfig, set_app_state};
 use crate::cfg::build_cfg;
 use tracing::{debug, warn};
 use robotech_macros::log_call;
+use tokio::runtime::{Builder, Runtime};
 use tokio::sync::broadcast;
 
 #[log_call]
@@ -11,35 +12,173 @@ pub fn build_app_cfg<'a, T: serde::Deserialize<'a> + std::fmt::Debug>(
     Ok(build_cfg("APP", None, path)?)
 }
 
+/// # 根据配置创建tokio运行时
+///
+/// 需要调整工作线程(如线程栈大小)时，用此函数创建运行时来代替 `#[tokio::main]`
+#[log_call]
+pub fn build_runtime(runtime_config: &RuntimeConfig) -> std::io::Result<Runtime> {
+    let mut builder = Builder::new_multi_thread();
+    builder.enable_all();
+    if let Some(worker_threads) = runtime_config.worker_threads {
+        builder.worker_threads(worker_threads);
+    }
+    if let Some(thread_stack_size) = runtime_config.thread_stack_size {
+        builder.thread_stack_size(thread_stack_size);
+    }
+    if let Some(max_blocking_threads) = runtime_config.max_blocking_threads {
+        builder.max_blocking_threads(max_blocking_threads);
+    }
+    if let Some(thread_name) = &runtime_config.thread_name {
+        builder.thread_name(thread_name);
+    }
+    builder.build()
+}
+
+/// # 退出模式
+///
+/// 运维人员可以通过发送不同的信号选择退出方式:
+/// - `kill -15`(SIGTERM) 或 Ctrl+C(SIGINT): 快速退出，停止接受新连接，进行中的请求最多等待配置的超时时间
+/// - `kill -3`(SIGQUIT): 排空退出，停止接受新连接，等待所有进行中的请求处理完成(不论多久)后再退出
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ShutdownMode {
+    /// 快速退出(SIGINT/SIGTERM)
+    Fast,
+    /// 排空退出(SIGQUIT)
+    Drain,
+}
+
+/// # 等待应用启动
+///
+/// 在启动期间(如 `start_web_server` 等待健康检查时)同时监听退出信号，
+/// 如果启动完成前收到退出信号(如编排系统快速回滚时发送的SIGTERM)，
+/// 则取消启动(丢弃 `startup`，由其负责清理已启动的服务)并返回 `None`，
+/// 此时调用方不应写入PID文件，直接正常退出即可
+///
+/// 启动完成时会将应用状态设置为 `AppState::Started`，通过 `spawn_interval_task` 创建的后台任务随之开始执行
+///
+/// ## 参数
+/// * `signal_receiver` - 信号接收器(启动完成后可继续传给 `wait_app_exit` 使用)
+/// * `startup` - 启动过程
+///
+/// ## 返回值
+/// * `Some(T)` - 启动完成，`T` 为启动过程的结果
+/// * `None` - 启动期间收到退出信号，启动已被取消
+pub async fn wait_app_startup<Fut, T>(
+    signal_receiver: &mut broadcast::Receiver<nix::sys::signal::Signal>,
+    startup: Fut,
+) -> Option<T>
+where
+    Fut: Future<Output = T>,
+{
+    tokio::select! {
+        result = startup => {
+            set_app_state(AppState::Started);
+            Some(result)
+        }
+        shutdown_mode = recv_shutdown_mode(signal_receiver) => {
+            warn!("启动期间收到退出信号({:?})，取消启动", shutdown_mode);
+            set_app_state(AppState::Stopping);
+            None
+        }
+    }
+}
+
+/// # 等待应用退出
+///
+/// 等待退出信号，收到后将应用状态设置为 `AppState::Stopping`(停止后台任务)，
+/// 再按信号对应的退出模式调用 `graceful_shutdown` 进行优雅退出，
+/// 退出完成后调用 `shutdown_log` 导出缓冲中的span及日志
+///
+/// ## 参数
+/// * `signal_receiver` - 信号接收器
+/// * `graceful_shutdown` - 优雅退出的回调，参数为退出模式
 pub async fn wait_app_exit<F, Fut>(
     mut signal_receiver: broadcast::Receiver<nix::sys::signal::Signal>,
     graceful_shutdown: F,
 ) -> Result<(), AppError>
 where
-    F: Fn() -> Fut,
+    F: Fn(ShutdownMode) -> Fut,
     Fut: Future<Output = Result<(), AppError>>,
 {
+    let shutdown_mode = recv_shutdown_mode(&mut signal_receiver).await;
+    set_app_state(AppState::Stopping);
+    debug!("正在优雅退出({:?})...", shutdown_mode);
+    graceful_shutdown(shutdown_mode).await?;
+    debug!("优雅退出完成.");
+    // 导出缓冲中的span及日志
+    crate::log::shutdown_log();
+    Ok(())
+}
+
+/// 接收信号，直到收到退出信号，返回对应的退出模式
+async fn recv_shutdown_mode(
+    signal_receiver: &mut broadcast::Receiver<nix::sys::signal::Signal>,
+) -> ShutdownMode {
     loop {
         match signal_receiver.recv().await {
             Ok(signal) => {
                 debug!("收到信号: {:?}", signal);
                 match signal {
-                    nix::sys::signal::Signal::SIGINT
-                    | nix::sys::signal::Signal::SIGTERM
-                    | nix::sys::signal::Signal::SIGQUIT => {
-                        break;
+                    nix::sys::signal::Signal::SIGINT | nix::sys::signal::Signal::SIGTERM => {
+                        break ShutdownMode::Fast;
+                    }
+                    nix::sys::signal::Signal::SIGQUIT => {
+                        break ShutdownMode::Drain;
                     }
                     _ => {}
                 }
             }
             Err(err) => {
                 warn!("无法接收信号: {}", err);
-                break;
+                break ShutdownMode::Fast;
             }
         }
     }
-    debug!("正在优雅退出...");
-    graceful_shutdown().await?;
-    debug!("优雅退出完成.");
-    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nix::sys::signal::Signal;
+    use std::net::TcpListener;
+    use std::time::Duration;
+    use wheel_rs::process::PidFileGuard;
+
+    #[tokio::test]
+    async fn signal_before_ready_cancels_startup() {
+        let pid_file_path =
+            std::env::temp_dir().join(format!("robotech-startup-{}.pid", std::process::id()));
+        let (signal_sender, mut signal_receiver) = broadcast::channel(8);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        // 模拟一直等不到就绪的启动过程
+        let startup = async move {
+            let _listener = listener;
+            std::future::pending::<()>().await
+        };
+        signal_sender.send(Signal::SIGTERM).unwrap();
+
+        let result = wait_app_startup(&mut signal_receiver, startup).await;
+        // 与README中的用法一致，启动完成后才写入PID文件
+        let _pid_file_guard = result.map(|_| PidFileGuard::new(pid_file_path.clone()).unwrap());
+
+        assert!(result.is_none());
+        // 启动过程被丢弃，监听已经释放
+        assert!(TcpListener::bind(addr).is_ok());
+        assert!(!pid_file_path.exists());
+    }
+
+    #[tokio::test]
+    async fn non_exit_signal_does_not_cancel_startup() {
+        let (signal_sender, mut signal_receiver) = broadcast::channel(8);
+        signal_sender.send(Signal::SIGHUP).unwrap();
+        let startup = async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            1
+        };
+        assert_eq!(
+            wait_app_startup(&mut signal_receiver, startup).await,
+            Some(1)
+        );
+    }
 }
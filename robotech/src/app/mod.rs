@@ -1,6 +1,10 @@
 mod app_error;
+mod app_task_utils;
 mod app_utils;
+mod runtime_config;
 
 // 重新导出结构体，简化外部引用
 pub use app_error::*;
+pub use app_task_utils::*;
 pub use app_utils::*;
+pub use runtime_config::*;
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// # 运行时配置
+///
+/// 调整tokio运行时的工作线程。axum本身没有工作线程相关的设置，请求都在tokio运行时的工作线程中处理，
+/// 所以这些设置只有在使用 `build_runtime` 创建运行时(而不是使用 `#[tokio::main]`)时才会生效
+///
+/// ## 使用示例
+/// ```
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let (app_config, _) = build_app_cfg::<AppConfig>(None)?;
+///     build_runtime(&app_config.web_server.runtime)?.block_on(async_main(app_config))
+/// }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct RuntimeConfig {
+    /// 工作线程数(默认为CPU核数)
+    #[serde(default)]
+    pub worker_threads: Option<usize>,
+    /// 线程栈大小，单位字节(默认2MiB)，处理函数递归较深(如生成报表)导致栈溢出时可调大
+    #[serde(default)]
+    pub thread_stack_size: Option<usize>,
+    /// 阻塞线程池(`spawn_blocking`)的最大线程数(默认512)
+    #[serde(default)]
+    pub max_blocking_threads: Option<usize>,
+    /// 线程名称(默认为tokio-runtime-worker)
+    #[serde(default)]
+    pub thread_name: Option<String>,
+}
@@ -10,4 +10,6 @@ pub enum CfgError {
     Build(ConfigError),
     #[error("Fail to deserialize config: {0}")]
     Deserialize(ConfigError),
+    #[error("Fail to fetch remote config({0}): {1}")]
+    FetchRemote(String, String),
 }
@@ -14,12 +14,79 @@ pub fn build_cfg<'a, T: serde::Deserialize<'a>>(
     cfg_file_path: Option<String>,
 ) -> Result<(T, Vec<String>), CfgError> {
     // Add in `./xxx.toml`, `./xxx.yml`, `./xxx.json`, `./xxx.ini`, `./xxx.ron`
-    let mut config = Config::builder();
+    let mut files = vec![];
+    let config = add_file_sources(
+        Config::builder(),
+        cfg_file_name_without_ext,
+        cfg_file_path,
+        &mut files,
+    )?;
 
+    // 后续添加环境变量，以覆盖配置文件中的设置
+    let config = config
+        // Add in cfg from the environment (with a prefix of XXX)
+        // E.g. `XXX_DEBUG=true ./target/app` would set the `debug` to `true`
+        .add_source(config::Environment::with_prefix(env_var_prefix))
+        .build()
+        .map_err(CfgError::Build)?;
+
+    Ok((
+        config.try_deserialize().map_err(CfgError::Deserialize)?,
+        files,
+    ))
+}
+
+/// # 构建由多个配置文件合并而成的配置
+///
+/// 大型应用可将配置拆分为多个文件，如 `database.toml`、`server.toml`、`logging.toml`，
+/// 按 `cfg_file_names_without_ext` 的顺序依次叠加(后面文件中的设置会覆盖前面文件中相同的设置)，
+/// 最后再叠加环境变量
+///
+/// ## 参数
+/// * `env_var_prefix` - 环境变量前缀
+/// * `cfg_file_names_without_ext` - 应用目录下不带扩展名的配置文件名列表
+///
+/// ## 返回值
+/// 返回配置及实际加载的配置文件列表(可传给 `watch_cfg_file` 监控所有配置文件的变化)
+///
+/// ## 使用示例
+/// ```
+/// let (app_config, files) = build_multi_cfg::<AppConfig>("APP", &["server", "database", "logging"])?;
+/// ```
+pub fn build_multi_cfg<'a, T: serde::Deserialize<'a>>(
+    env_var_prefix: &str,
+    cfg_file_names_without_ext: &[&str],
+) -> Result<(T, Vec<String>), CfgError> {
     let mut files = vec![];
+    let mut config = Config::builder();
+    for cfg_file_name_without_ext in cfg_file_names_without_ext {
+        config = add_file_sources(config, Some(cfg_file_name_without_ext), None, &mut files)?;
+    }
+
+    // 后续添加环境变量，以覆盖配置文件中的设置
+    let config = config
+        .add_source(config::Environment::with_prefix(env_var_prefix))
+        .build()
+        .map_err(CfgError::Build)?;
+
+    Ok((
+        config.try_deserialize().map_err(CfgError::Deserialize)?,
+        files,
+    ))
+}
+
+/// # 添加配置文件源
+///
+/// 如果已指定配置文件路径则只添加该文件，否则在应用目录下查找各种格式的配置文件
+pub(crate) fn add_file_sources(
+    mut config: ConfigBuilder<DefaultState>,
+    cfg_file_name_without_ext: Option<&str>,
+    cfg_file_path: Option<String>,
+    files: &mut Vec<String>,
+) -> Result<ConfigBuilder<DefaultState>, CfgError> {
     // 如果已指定配置文件路径
     config = if let Some(cfg_file_path) = cfg_file_path.clone() {
-        add_source(config, cfg_file_path.as_str(), None, &mut files)
+        add_source(config, cfg_file_path.as_str(), None, files)
     } else {
         let AppEnv {
             app_dir,
@@ -36,26 +103,14 @@ pub fn build_cfg<'a, T: serde::Deserialize<'a>>(
             )
             .to_string_lossy()
             .to_string();
-        config = add_source(config, temp_path.as_str(), Some("toml"), &mut files);
-        config = add_source(config, temp_path.as_str(), Some("yml"), &mut files);
-        config = add_source(config, temp_path.as_str(), Some("json"), &mut files);
-        config = add_source(config, temp_path.as_str(), Some("ini"), &mut files);
-        config = add_source(config, temp_path.as_str(), Some("ron"), &mut files);
+        config = add_source(config, temp_path.as_str(), Some("toml"), files);
+        config = add_source(config, temp_path.as_str(), Some("yml"), files);
+        config = add_source(config, temp_path.as_str(), Some("json"), files);
+        config = add_source(config, temp_path.as_str(), Some("ini"), files);
+        config = add_source(config, temp_path.as_str(), Some("ron"), files);
         config
     };
-
-    // 后续添加环境变量，以覆盖配置文件中的设置
-    let config = config
-        // Add in cfg from the environment (with a prefix of XXX)
-        // E.g. `XXX_DEBUG=true ./target/app` would set the `debug` to `true`
-        .add_source(config::Environment::with_prefix(env_var_prefix))
-        .build()
-        .map_err(CfgError::Build)?;
-
-    Ok((
-        config.try_deserialize().map_err(CfgError::Deserialize)?,
-        files,
-    ))
+    Ok(config)
 }
 
 fn add_source(
@@ -1,5 +1,15 @@
 mod cfg_error;
 mod cfg_utils;
+mod live_cfg_utils;
+#[cfg(feature = "cfg-remote")]
+mod remote_cfg_source;
+#[cfg(feature = "cfg-remote")]
+mod remote_cfg_utils;
 
 pub use cfg_error::*;
 pub use cfg_utils::*;
+pub use live_cfg_utils::*;
+#[cfg(feature = "cfg-remote")]
+pub use remote_cfg_source::*;
+#[cfg(feature = "cfg-remote")]
+pub use remote_cfg_utils::*;
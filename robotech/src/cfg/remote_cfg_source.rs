@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// # 远程配置源
+///
+/// 从配置中心通过HTTP获取配置文档
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct RemoteCfgSource {
+    /// 配置文档的URL，例如: http://config-server/app.toml
+    pub url: String,
+    /// 配置文档的格式
+    #[serde(default = "format_default")]
+    pub format: RemoteCfgFormat,
+    /// 请求配置文档时附加的请求头(如认证头 Authorization)
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+/// # 远程配置文档的格式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RemoteCfgFormat {
+    Json,
+    Toml,
+}
+
+impl RemoteCfgSource {
+    pub fn new(url: impl Into<String>, format: RemoteCfgFormat) -> Self {
+        Self {
+            url: url.into(),
+            format,
+            headers: HashMap::new(),
+        }
+    }
+
+    /// 添加请求头
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+}
+
+fn format_default() -> RemoteCfgFormat {
+    RemoteCfgFormat::Toml
+}
@@ -0,0 +1,122 @@
+use crate::cfg::cfg_error::CfgError;
+use crate::cfg::cfg_utils::add_file_sources;
+use crate::cfg::{RemoteCfgFormat, RemoteCfgSource};
+use config::{Config, FileFormat};
+use notify_debouncer_mini::{DebounceEventResult, DebouncedEvent, DebouncedEventKind};
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, mpsc};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+static CFG_HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+
+/// # 构建配置(含远程配置源)
+///
+/// 配置的优先级从低到高依次为: 远程配置 -> 本地配置文件 -> 环境变量，
+/// 即本地配置文件中的设置会覆盖远程配置中相同的项，其余的项合并
+///
+/// ## 参数
+/// * `env_var_prefix` - 环境变量前缀
+/// * `cfg_file_name_without_ext` - 本地配置文件名(不含扩展名)，不指定则使用应用文件名
+/// * `cfg_file_path` - 本地配置文件路径
+/// * `remote_cfg_source` - 远程配置源
+///
+/// ## 返回值
+/// 返回配置及读取到的本地配置文件列表
+pub async fn build_cfg_remote<'a, T: serde::Deserialize<'a>>(
+    env_var_prefix: &str,
+    cfg_file_name_without_ext: Option<&str>,
+    cfg_file_path: Option<String>,
+    remote_cfg_source: &RemoteCfgSource,
+) -> Result<(T, Vec<String>), CfgError> {
+    let content = fetch_remote_cfg(remote_cfg_source).await?;
+    let file_format = match remote_cfg_source.format {
+        RemoteCfgFormat::Json => FileFormat::Json,
+        RemoteCfgFormat::Toml => FileFormat::Toml,
+    };
+    let config =
+        Config::builder().add_source(config::File::from_str(content.as_str(), file_format));
+
+    let mut files = vec![];
+    let config = add_file_sources(config, cfg_file_name_without_ext, cfg_file_path, &mut files)?;
+
+    // 后续添加环境变量，以覆盖配置文件中的设置
+    let config = config
+        .add_source(config::Environment::with_prefix(env_var_prefix))
+        .build()
+        .map_err(CfgError::Build)?;
+
+    Ok((
+        config.try_deserialize().map_err(CfgError::Deserialize)?,
+        files,
+    ))
+}
+
+/// # 获取远程配置文档
+async fn fetch_remote_cfg(remote_cfg_source: &RemoteCfgSource) -> Result<String, CfgError> {
+    let mut request_builder = CFG_HTTP_CLIENT.get(remote_cfg_source.url.as_str());
+    for (name, value) in &remote_cfg_source.headers {
+        request_builder = request_builder.header(name.as_str(), value.as_str());
+    }
+    let response = request_builder
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| CfgError::FetchRemote(remote_cfg_source.url.clone(), e.to_string()))?;
+    response
+        .text()
+        .await
+        .map_err(|e| CfgError::FetchRemote(remote_cfg_source.url.clone(), e.to_string()))
+}
+
+/// # 监控远程配置
+///
+/// 按间隔轮询远程配置文档，内容变化时通过与监控配置文件相同的通知机制发出事件，
+/// 可配合 `watch_cfg_remote!` 宏使用
+///
+/// ## 参数
+/// * `remote_cfg_source` - 远程配置源
+/// * `poll_interval` - 轮询间隔
+///
+/// ## 返回值
+/// 返回轮询任务句柄及事件接收器，句柄被丢弃不会停止轮询，接收器被丢弃时轮询任务结束
+pub fn watch_cfg_remote(
+    remote_cfg_source: Arc<RemoteCfgSource>,
+    poll_interval: Duration,
+) -> (JoinHandle<()>, mpsc::Receiver<DebounceEventResult>) {
+    let (sender, receiver) = mpsc::channel();
+    let join_handle = tokio::spawn(async move {
+        let mut last_content = fetch_remote_cfg(&remote_cfg_source).await.ok();
+        let mut interval = tokio::time::interval(poll_interval);
+        // 第一次tick立即完成，跳过
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let content = match fetch_remote_cfg(&remote_cfg_source).await {
+                Ok(content) => content,
+                Err(e) => {
+                    warn!("轮询远程配置失败，保留当前配置: {e}");
+                    continue;
+                }
+            };
+            if last_content.as_ref() == Some(&content) {
+                continue;
+            }
+            debug!("远程配置已变化: {}", remote_cfg_source.url);
+            last_content = Some(content);
+            let event = DebouncedEvent {
+                path: PathBuf::from(remote_cfg_source.url.as_str()),
+                kind: DebouncedEventKind::Any,
+            };
+            if sender.send(Ok(vec![event])).is_err() {
+                debug!(
+                    "远程配置事件接收器已关闭，结束轮询: {}",
+                    remote_cfg_source.url
+                );
+                break;
+            }
+        }
+    });
+    (join_handle, receiver)
+}
@@ -1 +1,2 @@
+pub mod request_id_cst;
 pub mod user_id_cst;
@@ -0,0 +1,2 @@
+/// 请求ID的Http header的名称
+pub const REQUEST_ID_HEADER_NAME: &str = "X-Request-Id";
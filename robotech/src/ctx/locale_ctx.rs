@@ -0,0 +1,18 @@
+tokio::task_local! {
+    /// 当前处理的请求的语言
+    static LOCALE: String;
+}
+
+/// # 在语言的上下文中执行
+///
+/// `future` 执行期间可以通过 `current_locale` 获取到该语言
+pub async fn scope_locale<F: Future>(locale: String, future: F) -> F::Output {
+    LOCALE.scope(locale, future).await
+}
+
+/// # 获取当前的语言
+///
+/// 不在语言的上下文中(如未启用国际化、后台任务、`tokio::spawn` 出去的任务)时返回 `None`
+pub fn current_locale() -> Option<String> {
+    LOCALE.try_with(|locale| locale.clone()).ok()
+}
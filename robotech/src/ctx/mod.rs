@@ -0,0 +1,7 @@
+mod locale_ctx;
+mod read_primary_ctx;
+mod request_id_ctx;
+
+pub use locale_ctx::*;
+pub use read_primary_ctx::*;
+pub use request_id_ctx::*;
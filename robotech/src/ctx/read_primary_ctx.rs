@@ -0,0 +1,50 @@
+use std::cell::Cell;
+
+tokio::task_local! {
+    /// 当前请求之后是否从主库读取(执行过写操作后设置)
+    static READ_PRIMARY: Cell<bool>;
+}
+
+/// # 在读主库标记的上下文中执行
+///
+/// `future` 执行期间执行过写操作(`mark_read_primary`)后，`get_read_db_conn` 返回主库连接，
+/// 避免读库同步延迟而读不到刚写入的数据
+pub async fn scope_read_primary<F: Future>(future: F) -> F::Output {
+    READ_PRIMARY.scope(Cell::new(false), future).await
+}
+
+/// # 标记当前请求之后从主库读取
+///
+/// `dao` 宏生成的写方法会自动调用，不在上下文中时不做任何操作
+pub fn mark_read_primary() {
+    let _ = READ_PRIMARY.try_with(|read_primary| read_primary.set(true));
+}
+
+/// # 当前请求是否需要从主库读取
+///
+/// 不在读主库标记的上下文中(如后台任务、`tokio::spawn` 出去的任务)时返回 `false`
+pub fn current_read_primary() -> bool {
+    READ_PRIMARY
+        .try_with(|read_primary| read_primary.get())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn mark_only_applies_within_scope() {
+        mark_read_primary();
+        assert!(!current_read_primary());
+
+        scope_read_primary(async {
+            assert!(!current_read_primary());
+            mark_read_primary();
+            assert!(current_read_primary());
+        })
+        .await;
+
+        assert!(!current_read_primary());
+    }
+}
@@ -0,0 +1,36 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+tokio::task_local! {
+    /// 当前处理的请求的ID
+    static REQUEST_ID: String;
+}
+
+/// 生成请求ID的序号
+static REQUEST_ID_SEQ: AtomicU32 = AtomicU32::new(0);
+
+/// # 在请求ID的上下文中执行
+///
+/// `future` 执行期间(包括其中调用的 `ApiClient`)可以通过 `current_request_id` 获取到该请求ID
+pub async fn scope_request_id<F: Future>(request_id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// # 获取当前的请求ID
+///
+/// 不在请求ID的上下文中(如后台任务、`tokio::spawn` 出去的任务)时返回 `None`
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|request_id| request_id.clone()).ok()
+}
+
+/// # 生成新的请求ID
+///
+/// 由毫秒时间戳和进程内序号组成的16进制字符串
+pub fn new_request_id() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default();
+    let seq = REQUEST_ID_SEQ.fetch_add(1, Ordering::Relaxed);
+    format!("{millis:x}{seq:08x}")
+}
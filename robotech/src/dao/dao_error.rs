@@ -1,5 +1,8 @@
 use crate::dao::eo::{ForeignKey, UniqueKey};
-use crate::dao::{calc_key_of_foreign_key, get_from_foreign_keys, get_from_unique_keys};
+use crate::dao::{
+    calc_key_of_foreign_key, find_unique_key_by_index_name, get_from_foreign_keys,
+    get_from_unique_keys,
+};
 use anyhow::anyhow;
 use idworker::IdWorkerError;
 use regex::{Captures, Regex};
@@ -7,6 +10,7 @@ use robotech_macros::log_call;
 use sea_orm::DbErr;
 use std::sync::LazyLock;
 use std::time::SystemTimeError;
+use tracing::warn;
 
 /// # 正则匹配重复键错误-Postgres
 /// 格式: duplicate key value violates unique constraint "...", detail: Some("Key (<字段名>)=(<字段值>) already exists."), ...
@@ -16,9 +20,13 @@ static REGEX_DUPLICATE_KEY_POSTGRES: LazyLock<Regex> = LazyLock::new(|| {
 });
 
 /// # 正则匹配重复键错误-MySQL
-/// 格式: Duplicate entry '<字段值>' for key '<字段名>'
+/// 格式:
+/// - MySQL 5.7: Duplicate entry '<字段值>' for key '<索引名>'
+/// - MySQL 8.0及MariaDB: Duplicate entry '<字段值>' for key '<表名>.<索引名>'
+///
+/// 组合唯一键的字段值以 `-` 连接，如 `Duplicate entry 'bucket-a-1' for key 'oss_bucket.ak_name_and_owner'`
 static REGEX_DUPLICATE_KEY_MYSQL: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r#"Duplicate entry '(?P<value>[^']+)' for key '(?P<ak_name>[^']*)'"#)
+    Regex::new(r#"Duplicate entry '(?P<value>[^']*)' for key '(?P<ak_name>[^']*)'"#)
         .expect("正则表达式错误")
 });
 
@@ -46,17 +54,56 @@ static REGEX_DELETE_VIOLATE_FK_MYSQL: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r#"Cannot delete or update a parent row: a foreign key constraint fails \(`[A-Za-z_0-9]+`\.`(?P<fk_table>[A-Za-z_0-9]+)`, CONSTRAINT `[A-Za-z_0-9]+` FOREIGN KEY \(`(?P<fk_column>[A-Za-z_0-9]+)`\) REFERENCES `(?P<pk_table>[A-Za-z_0-9]+)`"#).expect("正则表达式错误")
 });
 
-/// # 自定义服务层的错误枚举
+/// # 正则匹配违反外键约束错误-Postgres
+/// 格式: ... violates foreign key constraint "<约束名>"
 ///
-/// 该枚举定义了服务层可能遇到的各种错误类型，包括数据未找到、重复键约束违反、
-/// IO错误和数据库错误。这些错误类型用于在服务层统一处理各种异常情况，
-/// 并提供清晰的错误信息反馈给调用方。
+/// 约束名不符合 `fk_<字段名>__from__<表名>` 的命名，或者没有在 `dao` 宏中注册外键时匹配
+static REGEX_VIOLATE_FK_POSTGRES: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"violates foreign key constraint \\"(?P<constraint>[^\\"]+)\\""#)
+        .expect("正则表达式错误")
+});
+
+/// # 正则匹配违反外键约束错误-MySQL
+/// 格式: Cannot add or update a child row: a foreign key constraint fails (..., CONSTRAINT `<约束名>` ...)
+///
+/// 约束名不符合命名，或者没有在 `dao` 宏中注册外键时匹配
+static REGEX_VIOLATE_FK_MYSQL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"a foreign key constraint fails \(.*?CONSTRAINT `(?P<constraint>[^`]+)`"#)
+        .expect("正则表达式错误")
+});
+
+/// # 正则匹配违反非空约束错误-Postgres
+/// 格式: null value in column "<字段名>" of relation "<表名>" violates not-null constraint
+static REGEX_NOT_NULL_POSTGRES: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"null value in column \\"(?P<column>[A-Za-z_0-9]+)\\"(?: of relation \\"[A-Za-z_0-9]+\\")? violates not-null constraint"#)
+        .expect("正则表达式错误")
+});
+
+/// # 正则匹配违反非空约束错误-MySQL
+/// 格式: Column '<字段名>' cannot be null 或 Field '<字段名>' doesn't have a default value
+static REGEX_NOT_NULL_MYSQL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?:Column|Field) '(?P<column>[^']+)' (?:cannot be null|doesn't have a default value)"#,
+    )
+    .expect("正则表达式错误")
+});
+
+/// # 数据访问层的错误枚举
+///
+/// `dao` 宏生成的方法都返回此错误，数据库返回的错误通过 `parse_db_err` 解析成具体的错误类型，
+/// 在服务层通过 `?` 转换为 `SvcError::Dao`，在控制器层再转换为相应的Ro及HTTP状态码
 ///
 /// ## 错误类型说明
-/// - `NotFound`: 表示请求的数据未找到，通常用于查询操作
-/// - `DuplicateKey`: 表示违反了唯一性约束，如重复的用户名或邮箱
-/// - `IoError`: 表示输入输出相关的错误，如文件读写失败
-/// - `DatabaseError`: 表示底层数据库操作发生的错误
+/// - `NotFound`: 要操作的记录不存在(对应 `DbErr::RecordNotFound`)
+/// - `DuplicateKey`: 违反了唯一键约束，携带 `dao` 宏中注册的唯一键信息及冲突的值
+/// - `InsertViolateFk`: 插入(或更新)时违反了外键约束(引用的记录不存在)
+/// - `DeleteViolateFk`: 删除(或更新)时违反了外键约束(存在引用该记录的记录)
+/// - `ForeignKeyViolation`: 违反了没有注册(或不符合命名)的外键约束，携带约束名
+/// - `NotNullViolation`: 违反了非空约束，携带字段名
+/// - `Db`: 其它数据库错误(可由 `DbErr` 直接转换，但不会解析约束错误，应优先使用 `parse_db_err`)
+/// - `IdWorker`/`SystemTime`: 生成ID或获取当前时间失败(可由相应的错误直接转换)
+/// - `GetDbConn`/`NotInitialized`/`AlreadyInitialized`: 数据库连接及唯一键、外键列表的初始化错误
+/// - `Runtime`: 其它运行时错误(可由 `anyhow::Error` 直接转换)
 #[derive(Debug, thiserror::Error)]
 pub enum DaoError {
     #[error("运行时错误: {0}")]
@@ -67,12 +114,18 @@ pub enum DaoError {
     SystemTime(#[from] SystemTimeError),
     #[error("ID工作者错误: {0}")]
     IdWorker(#[from] IdWorkerError),
+    #[error("找不到数据: {0}")]
+    NotFound(String),
     #[error("重复键错误: {0} -> {1}")]
     DuplicateKey(UniqueKey, String),
     #[error("插入(或更新)操作违反了数据库外键约束条件: {0}")]
     InsertViolateFk(ForeignKey),
     #[error("删除(或更新)操作违反了数据库外键约束条件: {0}")]
     DeleteViolateFk(ForeignKey),
+    #[error("违反了外键约束: {0}")]
+    ForeignKeyViolation(String),
+    #[error("违反了非空约束: {0}")]
+    NotNullViolation(String),
     #[error("数据库错误: {0}")]
     Db(#[from] DbErr),
     #[error("未初始化错误: {0}")]
@@ -82,20 +135,25 @@ pub enum DaoError {
 }
 
 impl DaoError {
-    /// # 处理数据库错误，并转换为服务层错误
+    /// # 解析数据库错误
     ///
-    /// 该函数用于将数据库层的错误(DbErr)转换为服务层错误(SvcError)，
-    /// 特别处理了重复键错误，能够识别Postgres和MySQL的重复键错误格式，
-    /// 并将其转换为带有字段名称和值的DuplicateKey错误。
+    /// 该函数用于将数据库层的错误(DbErr)转换为数据访问层错误，
+    /// 能够识别Postgres和MySQL的重复键、外键约束及非空约束错误格式，
+    /// 并通过 `dao` 宏注册的唯一键、外键列表转换为带有业务描述的错误，
+    /// 记录不存在的错误转换为 `NotFound`，其它错误原样包装为 `Db`
     ///
     /// ## 参数
     /// * `db_err` - 数据库错误对象
-    /// * `unique_key_hashmap` - 用于映射数据库列名到业务字段名的哈希表
     ///
     /// ## 返回值
-    /// 返回对应的SvcError服务层错误对象
+    /// 返回对应的DaoError数据访问层错误对象
     #[log_call(level = warn, mode = enter)]
     pub fn parse_db_err(db_err: DbErr) -> DaoError {
+        // 连接级别的错误可能需要重连数据库
+        crate::db::report_db_err(&db_err);
+        if let DbErr::RecordNotFound(msg) = db_err {
+            return DaoError::NotFound(msg);
+        }
         let db_err_string = format!("{:?}", db_err);
         if let Some(caps) = REGEX_DUPLICATE_KEY_POSTGRES.captures(&db_err_string) {
             // 正则匹配重复键错误-Postgres
@@ -115,6 +173,18 @@ impl DaoError {
         } else if let Some(caps) = REGEX_DELETE_VIOLATE_FK_MYSQL.captures(&db_err_string) {
             // 正则匹配删除操作违反了约束条件错误-MySQL
             return Self::parse_delete_violate_fk(caps);
+        } else if let Some(caps) = REGEX_VIOLATE_FK_POSTGRES.captures(&db_err_string) {
+            // 正则匹配违反外键约束错误-Postgres
+            return DaoError::ForeignKeyViolation(caps["constraint"].to_string());
+        } else if let Some(caps) = REGEX_VIOLATE_FK_MYSQL.captures(&db_err_string) {
+            // 正则匹配违反外键约束错误-MySQL
+            return DaoError::ForeignKeyViolation(caps["constraint"].to_string());
+        } else if let Some(caps) = REGEX_NOT_NULL_POSTGRES.captures(&db_err_string) {
+            // 正则匹配违反非空约束错误-Postgres
+            return DaoError::NotNullViolation(caps["column"].to_string());
+        } else if let Some(caps) = REGEX_NOT_NULL_MYSQL.captures(&db_err_string) {
+            // 正则匹配违反非空约束错误-MySQL
+            return DaoError::NotNullViolation(caps["column"].to_string());
         }
 
         DaoError::from(db_err)
@@ -123,29 +193,45 @@ impl DaoError {
     /// # 从正则匹配中抓取有用信息转换成重复键错误
     ///
     /// 该函数用于从正则表达式匹配结果中提取重复键错误的相关信息，
-    /// 包括冲突的列名和值，并通过映射表转换为业务层的字段名，
-    /// 最终构造出一个包含字段名和冲突值的DuplicateKey服务错误。
+    /// 包括冲突的索引名和值，并通过 `dao` 宏注册的唯一键列表找到对应的唯一键，
+    /// 最终构造出一个包含唯一键和冲突值的DuplicateKey错误。
+    ///
+    /// 依次按完整的索引名(Postgres及MySQL 8.0的 `表名.索引名`)、去掉表名的索引名(MySQL 5.7)查找唯一键，
+    /// 都找不到时以索引名作为唯一键的说明，不会因为没有注册唯一键而变成运行时错误
     ///
     /// ## 参数
-    /// * `caps` - 正则表达式匹配结果，包含column和value两个命名捕获组
-    /// * `unique_key_hashmap` - 数据库列名到业务字段名的映射表
+    /// * `caps` - 正则表达式匹配结果，包含ak_name和value两个命名捕获组
     ///
     /// ## 返回值
-    /// 返回一个包含字段名和冲突值的SvcError::DuplicateKey错误
+    /// 返回一个包含唯一键和冲突值的DaoError::DuplicateKey错误
     fn parse_duplicate_key(caps: Captures) -> DaoError {
-        let ak_name = caps["ak_name"].to_lowercase().to_string();
+        let ak_name = caps["ak_name"].to_lowercase();
         let value = caps["value"].to_string();
-        let unique_filed = match get_from_unique_keys(&ak_name) {
-            Ok(Some(unique_filed)) => unique_filed,
+        let (table, index_name) = match ak_name.rsplit_once('.') {
+            Some((table, index_name)) => (table, index_name),
+            None => ("", ak_name.as_str()),
+        };
+        let unique_key = match get_from_unique_keys(&ak_name) {
+            Ok(Some(unique_key)) => Ok(Some(unique_key)),
+            Ok(None) => find_unique_key_by_index_name(index_name),
+            Err(e) => Err(e),
+        };
+        let unique_key = match unique_key {
+            Ok(Some(unique_key)) => unique_key.clone(),
             Ok(None) => {
-                return DaoError::from(anyhow!(format!("获取unique字段列表错误: {ak_name}不存在")));
+                warn!("没有注册唯一键: {ak_name}");
+                UniqueKey::builder()
+                    .table(table.to_string())
+                    .key_name(index_name.to_string())
+                    .key_remark(index_name.to_string())
+                    .build()
             }
             Err(e) => {
                 return DaoError::from(anyhow!(format!("获取unique字段列表错误: {e}")));
             }
         };
 
-        DaoError::DuplicateKey(unique_filed.clone(), value)
+        DaoError::DuplicateKey(unique_key, value)
     }
 
     fn parse_violate_fk(caps: Captures) -> Result<ForeignKey, DaoError> {
@@ -186,3 +272,81 @@ impl DaoError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dao::{UNIQUE_KEYS, push_unique_key};
+    use std::collections::HashMap;
+
+    fn parse_mysql_duplicate_key(message: &str) -> (UniqueKey, String) {
+        UNIQUE_KEYS.get_or_init(|| {
+            let mut unique_keys = HashMap::new();
+            push_unique_key(
+                &mut unique_keys,
+                "oss_bucket".to_string(),
+                "name".to_string(),
+                "桶名称".to_string(),
+            );
+            push_unique_key(
+                &mut unique_keys,
+                "oss_bucket".to_string(),
+                "name,owner".to_string(),
+                "桶名称及所有者".to_string(),
+            );
+            unique_keys
+        });
+        match DaoError::parse_db_err(DbErr::Custom(message.to_string())) {
+            DaoError::DuplicateKey(unique_key, value) => (unique_key, value),
+            e => panic!("应解析为重复键错误: {e}"),
+        }
+    }
+
+    #[test]
+    fn parse_mysql57_duplicate_key() {
+        let (unique_key, value) =
+            parse_mysql_duplicate_key("Duplicate entry 'x' for key 'ak_name'");
+        assert_eq!(value, "x");
+        assert_eq!(unique_key.table, "oss_bucket");
+        assert_eq!(unique_key.key_name, "name");
+        assert_eq!(unique_key.key_remark, "桶名称");
+    }
+
+    #[test]
+    fn parse_mysql80_duplicate_key() {
+        let (unique_key, value) =
+            parse_mysql_duplicate_key("Duplicate entry 'x' for key 'oss_bucket.ak_name'");
+        assert_eq!(value, "x");
+        assert_eq!(unique_key.table, "oss_bucket");
+        assert_eq!(unique_key.key_name, "name");
+        assert_eq!(unique_key.key_remark, "桶名称");
+    }
+
+    #[test]
+    fn parse_mysql_duplicate_key_with_composite_value() {
+        let (unique_key, value) = parse_mysql_duplicate_key(
+            "Duplicate entry 'a-b' for key 'oss_bucket.ak_name_and_owner'",
+        );
+        assert_eq!(value, "a-b");
+        assert_eq!(unique_key.table, "oss_bucket");
+        assert_eq!(unique_key.key_name, "name,owner");
+    }
+
+    #[test]
+    fn parse_mysql57_unregistered_duplicate_key() {
+        let (unique_key, value) =
+            parse_mysql_duplicate_key("Duplicate entry 'x' for key 'uk_name'");
+        assert_eq!(value, "x");
+        assert_eq!(unique_key.table, "");
+        assert_eq!(unique_key.key_name, "uk_name");
+    }
+
+    #[test]
+    fn parse_mysql80_unregistered_duplicate_key() {
+        let (unique_key, value) =
+            parse_mysql_duplicate_key("Duplicate entry 'x' for key 'oss_bucket.uk_name'");
+        assert_eq!(value, "x");
+        assert_eq!(unique_key.table, "oss_bucket");
+        assert_eq!(unique_key.key_name, "uk_name");
+    }
+}
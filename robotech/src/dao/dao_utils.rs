@@ -3,8 +3,8 @@ use crate::db::get_db_conn;
 use anyhow::anyhow;
 use sea_orm::sea_query::{Expr, Func};
 use sea_orm::{
-    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbConn,
-    EntityTrait, ExprTrait, QueryOrder, Select, TransactionTrait,
+    ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait, ExprTrait,
+    QueryOrder, Select,
 };
 use std::sync::Arc;
 
@@ -28,15 +28,6 @@ where
     }
 }
 
-pub async fn begin_transaction(db: &DbConn) -> Result<DatabaseTransaction, DaoError> {
-    Ok(db.begin().await?)
-}
-
-pub async fn commit_transaction(db: DatabaseTransaction) -> Result<(), DaoError> {
-    db.commit().await?;
-    Ok(())
-}
-
 /// 关键字多字段OR模糊查询
 pub fn build_like_condition<T>(keyword: &str, cols: &[T]) -> Condition
 where
@@ -79,3 +70,15 @@ where
     }
     Ok(select)
 }
+
+/// # 软删除的删除时间字段
+///
+/// `#[dao(soft_delete)]` 生成的代码用此trait在编译期检查Model中 `delete_timestamp` 字段的类型
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`#[dao(soft_delete)]` 要求Model中的 `delete_timestamp` 字段为 `Option<i64>`，实际为 `{Self}`",
+    label = "soft_delete 要求 `delete_timestamp: Option<i64>`"
+)]
+pub trait SoftDeleteTimestamp {}
+
+impl SoftDeleteTimestamp for Option<i64> {}
@@ -2,9 +2,15 @@ mod dao_error;
 mod dao_utils;
 pub mod eo;
 mod foreign_keys_utils;
+mod model_stream;
+mod page_config;
+mod page_utils;
 mod unique_keys_utils;
 
 pub use dao_error::*;
 pub use dao_utils::*;
 pub use foreign_keys_utils::*;
+pub use model_stream::*;
+pub use page_config::*;
+pub use page_utils::*;
 pub use unique_keys_utils::*;
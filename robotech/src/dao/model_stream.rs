@@ -0,0 +1,10 @@
+use futures_util::Stream;
+use sea_orm::DbErr;
+
+/// # 记录流
+///
+/// 逐条读取查询结果的流，用于导出等需要遍历所有记录而不能一次性加载到内存的场景，
+/// 宏生成的 `stream_by_condition` 返回此类型，避免使用方直接依赖 futures
+pub trait ModelStream<M>: Stream<Item = Result<M, DbErr>> + Send {}
+
+impl<M, S> ModelStream<M> for S where S: Stream<Item = Result<M, DbErr>> + Send {}
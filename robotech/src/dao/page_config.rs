@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// # 分页配置
+///
+/// 防止客户端请求过大的每页大小(如 `_size=1000000`)导致服务器内存耗尽
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct PageConfig {
+    /// 默认每页大小(未指定或指定为0时使用，默认10)
+    #[serde(default = "default_page_size_default")]
+    pub default_page_size: u64,
+    /// 最大每页大小(超过时按最大每页大小查询，默认200)
+    #[serde(default = "max_page_size_default")]
+    pub max_page_size: u64,
+}
+
+impl Default for PageConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: default_page_size_default(),
+            max_page_size: max_page_size_default(),
+        }
+    }
+}
+
+fn default_page_size_default() -> u64 {
+    10
+}
+
+fn max_page_size_default() -> u64 {
+    200
+}
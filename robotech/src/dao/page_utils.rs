@@ -0,0 +1,103 @@
+use crate::dao::{DaoError, PageConfig};
+use anyhow::anyhow;
+use std::sync::RwLock;
+use tracing::debug;
+
+/// 分页配置
+static PAGE_CONFIG: RwLock<Option<PageConfig>> = RwLock::new(None);
+
+/// 设置分页配置(初始化数据库连接时设置)
+pub fn set_page_config(page_config: PageConfig) {
+    if let Ok(mut write_lock) = PAGE_CONFIG.write() {
+        *write_lock = Some(page_config);
+    }
+}
+
+/// 获取分页配置(未设置时返回默认配置)
+pub fn get_page_config() -> PageConfig {
+    PAGE_CONFIG
+        .read()
+        .ok()
+        .and_then(|read_lock| read_lock.clone())
+        .unwrap_or_default()
+}
+
+/// # 解析每页大小
+///
+/// * 未指定或指定为0时，使用默认每页大小
+/// * 超过最大每页大小时，按最大每页大小查询
+pub fn resolve_page_size(page_size: Option<u64>) -> u64 {
+    clamp_page_size(page_size, &get_page_config())
+}
+
+/// 按分页配置解析每页大小
+fn clamp_page_size(page_size: Option<u64>, page_config: &PageConfig) -> u64 {
+    let &PageConfig {
+        default_page_size,
+        max_page_size,
+    } = page_config;
+    let max_page_size = max_page_size.max(1);
+    match page_size {
+        None | Some(0) => default_page_size.clamp(1, max_page_size),
+        Some(page_size) if page_size > max_page_size => {
+            debug!("每页大小{page_size}超过最大值，按{max_page_size}查询");
+            max_page_size
+        }
+        Some(page_size) => page_size,
+    }
+}
+
+/// # 检查每页大小
+///
+/// * 指定为0时返回错误
+/// * 超过最大每页大小时，按最大每页大小查询
+pub fn check_page_size(page_size: u64) -> Result<u64, DaoError> {
+    if page_size == 0 {
+        return Err(DaoError::from(anyhow!("每页大小不能为0")));
+    }
+    Ok(resolve_page_size(Some(page_size)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_page_size_at_boundary() {
+        let page_config = PageConfig {
+            default_page_size: 20,
+            max_page_size: 200,
+        };
+        assert_eq!(clamp_page_size(Some(1), &page_config), 1);
+        assert_eq!(clamp_page_size(Some(200), &page_config), 200);
+        assert_eq!(clamp_page_size(Some(201), &page_config), 200);
+        assert_eq!(clamp_page_size(Some(u64::MAX), &page_config), 200);
+        assert_eq!(clamp_page_size(Some(0), &page_config), 20);
+        assert_eq!(clamp_page_size(None, &page_config), 20);
+    }
+
+    #[test]
+    fn clamp_page_size_with_invalid_config() {
+        // 默认每页大小超过最大值时按最大值查询
+        let page_config = PageConfig {
+            default_page_size: 500,
+            max_page_size: 200,
+        };
+        assert_eq!(clamp_page_size(None, &page_config), 200);
+        // 最大每页大小配置为0时按1查询
+        let page_config = PageConfig {
+            default_page_size: 0,
+            max_page_size: 0,
+        };
+        assert_eq!(clamp_page_size(None, &page_config), 1);
+        assert_eq!(clamp_page_size(Some(10), &page_config), 1);
+    }
+
+    #[test]
+    fn check_page_size_rejects_zero() {
+        let max_page_size = get_page_config().max_page_size;
+        assert!(check_page_size(0).is_err());
+        assert_eq!(check_page_size(max_page_size).unwrap(), max_page_size);
+        assert_eq!(check_page_size(max_page_size + 1).unwrap(), max_page_size);
+    }
+}
@@ -30,6 +30,26 @@ pub fn get_from_unique_keys(key: &str) -> Result<Option<&'static UniqueKey>, Dao
         .get(key))
 }
 
+/// # 按不带表名的索引名查找唯一键
+///
+/// MySQL 5.7的重复键错误中索引名不带表名(如 `ak_name`)，在MySQL类的key(`表名.索引名`)中查找，
+/// 只有一个表有该索引名时才返回
+pub fn find_unique_key_by_index_name(
+    index_name: &str,
+) -> Result<Option<&'static UniqueKey>, DaoError> {
+    let suffix = format!(".{index_name}");
+    let mut unique_keys = UNIQUE_KEYS
+        .get()
+        .ok_or_else(|| DaoError::NotInitialized("UNIQUE_KEYS未初始化".to_string()))?
+        .iter()
+        .filter(|(key, _)| key.ends_with(suffix.as_str()))
+        .map(|(_, unique_key)| unique_key);
+    Ok(match (unique_keys.next(), unique_keys.next()) {
+        (Some(unique_key), None) => Some(unique_key),
+        _ => None,
+    })
+}
+
 pub fn push_unique_key(
     unique_keys: &mut HashMap<String, UniqueKey>,
     table: String,
@@ -11,4 +11,23 @@ pub enum DbError {
     Config(String),
     #[error("Fail to connect database: {0}")]
     Connect(DbErr),
+    #[error("Fail to ping database: {0}")]
+    Ping(DbErr),
+    #[error("Fail to begin/commit/rollback transaction: {0}")]
+    Transaction(DbErr),
+}
+
+/// # 升级数据库版本的错误
+#[derive(Error, Debug)]
+pub enum MigrateError {
+    #[error("{0}")]
+    Db(#[from] DbError),
+    #[error("Unsupported database for migration: {0}")]
+    UnsupportedDb(String),
+    #[error("Fail to load migrations: {0}")]
+    Load(sqlx::migrate::MigrateError),
+    #[error("Fail to connect database for migration: {0}")]
+    Connect(sqlx::Error),
+    #[error("Fail to run migrations: {0}")]
+    Run(sqlx::migrate::MigrateError),
 }
@@ -0,0 +1,62 @@
+use crate::db::{DbConnConfig, MigrateError, resolve_db_url};
+use sqlx::AnyPool;
+use sqlx::any::install_default_drivers;
+use sqlx::migrate::Migrator;
+use std::path::Path;
+use tracing::debug;
+
+/// 默认的迁移目录
+pub const DEFAULT_MIGRATIONS_DIR: &str = "migrations";
+
+/// # 升级数据库版本
+///
+/// 使用默认的迁移目录(`migrations`)执行迁移，见 `migrate_from`
+pub async fn migrate(db_conn_config: &DbConnConfig) -> Result<(), MigrateError> {
+    migrate_from(db_conn_config, Path::new(DEFAULT_MIGRATIONS_DIR)).await
+}
+
+/// # 从指定的目录升级数据库版本
+///
+/// 与 `db_migrate!` 宏在编译时嵌入迁移脚本不同，此方法在运行时读取迁移目录，
+/// 下游应用可以使用自己的迁移目录(相对路径相对于当前目录)。
+///
+/// 迁移目录下有与数据库类型对应的子目录(`mysql`、`pgsql`、`sqlite`)时使用该子目录，
+/// 否则直接使用迁移目录
+///
+/// ## 参数
+/// * `db_conn_config` - 数据库配置(与 `init_db_conn` 使用相同的配置，连接的是同一个数据库)
+/// * `dir` - 迁移目录
+pub async fn migrate_from(db_conn_config: &DbConnConfig, dir: &Path) -> Result<(), MigrateError> {
+    let db_url = resolve_db_url(db_conn_config)?;
+    let sub_dir = if db_url.starts_with("mysql://") {
+        "mysql"
+    } else if db_url.starts_with("postgres://")
+        || db_url.starts_with("postgresql://")
+        || db_url.starts_with("postgis://")
+    {
+        "pgsql"
+    } else if db_url.starts_with("sqlite:") {
+        "sqlite"
+    } else {
+        let scheme = db_url.split_once(':').map_or("", |(scheme, _)| scheme);
+        return Err(MigrateError::UnsupportedDb(scheme.to_string()));
+    };
+    let migrations_dir = dir.join(sub_dir);
+    let migrations_dir = if migrations_dir.is_dir() {
+        migrations_dir
+    } else {
+        dir.to_path_buf()
+    };
+
+    debug!("migrating database from {}...", migrations_dir.display());
+    let migrator = Migrator::new(migrations_dir.as_path())
+        .await
+        .map_err(MigrateError::Load)?;
+    install_default_drivers();
+    let pool = AnyPool::connect(&db_url)
+        .await
+        .map_err(MigrateError::Connect)?;
+    let result = migrator.run(&pool).await.map_err(MigrateError::Run);
+    pool.close().await;
+    result
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use wheel_rs::serde::duration_serde;
+
+/// # 数据库断线重连配置
+///
+/// 数据库故障切换或网络抖动后，连接池中的连接会失效，
+/// 在一段时间内连续出现连接级别的错误时，使用原来的数据库配置重新创建连接并替换全局的数据库连接
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct DbReconnectConfig {
+    /// 是否启用(不设置此项默认为true)
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+    /// 触发重连的连续连接错误次数(默认3次)
+    #[serde(default = "threshold_default")]
+    pub threshold: u32,
+    /// 连续错误的统计窗口(默认60秒)，距离上次连接错误超过此时间则重新计数
+    #[serde(with = "duration_serde", default = "window_default")]
+    pub window: Duration,
+    /// 重连失败后的初始等待时间(默认1秒)，之后每次失败翻倍
+    #[serde(with = "duration_serde", default = "initial_backoff_default")]
+    pub initial_backoff: Duration,
+    /// 重连失败后的最大等待时间(默认30秒)
+    #[serde(with = "duration_serde", default = "max_backoff_default")]
+    pub max_backoff: Duration,
+}
+
+impl Default for DbReconnectConfig {
+    fn default() -> Self {
+        Self {
+            enabled: enabled_default(),
+            threshold: threshold_default(),
+            window: window_default(),
+            initial_backoff: initial_backoff_default(),
+            max_backoff: max_backoff_default(),
+        }
+    }
+}
+
+fn enabled_default() -> bool {
+    true
+}
+
+fn threshold_default() -> u32 {
+    3
+}
+
+fn window_default() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn initial_backoff_default() -> Duration {
+    Duration::from_secs(1)
+}
+
+fn max_backoff_default() -> Duration {
+    Duration::from_secs(30)
+}
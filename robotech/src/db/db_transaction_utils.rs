@@ -0,0 +1,39 @@
+use crate::db::DbError;
+use sea_orm::{DatabaseTransaction, IsolationLevel, TransactionTrait};
+
+/// # 开启事务
+///
+/// `db_unwrap(transaction_required)` 及 `svc` 宏生成的代码通过此方法开启事务，也可以自己调用，
+/// 之后用 `commit_transaction` 提交或 `rollback_transaction` 回滚
+///
+/// ## 嵌套调用
+/// 传入的是数据库连接时开启新的事务；传入的是已经开启的事务时不会复用该事务，
+/// 而是开启嵌套事务(通过 `SAVEPOINT` 实现)：嵌套事务回滚只撤销嵌套事务中的修改，
+/// 嵌套事务提交后其修改仍然要等外层事务提交才会生效，外层事务回滚时一并撤销
+pub async fn begin_transaction<C>(db: &C) -> Result<DatabaseTransaction, DbError>
+where
+    C: TransactionTrait,
+{
+    db.begin().await.map_err(DbError::Transaction)
+}
+
+/// 以指定的隔离级别开启事务(隔离级别只能在最外层事务设置，开启嵌套事务时应使用 `begin_transaction`)
+pub async fn begin_transaction_with_isolation<C>(
+    db: &C,
+    isolation_level: IsolationLevel,
+) -> Result<DatabaseTransaction, DbError>
+where
+    C: TransactionTrait,
+{
+    db.begin_with_config(Some(isolation_level), None)
+        .await
+        .map_err(DbError::Transaction)
+}
+
+pub async fn commit_transaction(db: DatabaseTransaction) -> Result<(), DbError> {
+    db.commit().await.map_err(DbError::Transaction)
+}
+
+pub async fn rollback_transaction(db: DatabaseTransaction) -> Result<(), DbError> {
+    db.rollback().await.map_err(DbError::Transaction)
+}
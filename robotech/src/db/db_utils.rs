@@ -1,56 +1,390 @@
+use crate::cfg::register_live_cfg;
+use crate::ctx::current_read_primary;
+use crate::dao::set_page_config;
 use crate::db::{DbConnConfig, DbError};
-use tracing::debug;
+use crate::env::{APP_ENV, AppEnv};
+use tracing::{debug, error, info, warn};
 use robotech_macros::log_call;
-use sea_orm::{ConnectOptions, Database, DbConn};
-use std::sync::{Arc, RwLock};
+use sea_orm::{ConnectOptions, Database, DbConn, DbErr};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
 
-/// 数据库连接
-static DB_CONN: RwLock<Option<Arc<DbConn>>> = RwLock::new(None);
+/// 默认数据库连接的名称(`dao` 宏生成的代码使用默认数据库连接)
+pub const DEFAULT_DB_CONN_NAME: &str = "default";
 
-/// 获取App配置的只读访问
+/// 读库连接的名称(`get_read_db_conn` 优先使用该连接)
+pub const REPLICA_DB_CONN_NAME: &str = "replica";
+
+/// 数据库连接(名称 -> 数据库连接)
+static DB_CONNS: RwLock<Option<HashMap<String, Arc<DbConn>>>> = RwLock::new(None);
+
+/// 默认数据库连接的配置(保留下来用于断线重连)
+static DB_CONN_CONFIG: RwLock<Option<DbConnConfig>> = RwLock::new(None);
+
+/// 连续的连接错误(次数, 最近一次错误的时间)
+static CONN_ERR_STAT: Mutex<Option<(u32, Instant)>> = Mutex::new(None);
+
+/// 是否正在重连
+static RECONNECTING: AtomicBool = AtomicBool::new(false);
+
+/// 获取默认的数据库连接
 pub fn get_db_conn() -> Result<Arc<DbConn>, DbError> {
-    let read_lock = DB_CONN.read().map_err(|_| DbError::GetDbConn())?;
-    read_lock.clone().ok_or(DbError::GetDbConn())
+    get_db_conn_named(DEFAULT_DB_CONN_NAME)
 }
 
-/// 设置App配置
+/// 设置默认的数据库连接
 pub fn set_db_conn(value: DbConn) -> Result<(), DbError> {
-    let mut write_lock = DB_CONN.write().map_err(|_| DbError::SetDbConn())?;
-    *write_lock = Some(Arc::new(value));
+    set_db_conn_named(DEFAULT_DB_CONN_NAME, value)
+}
+
+/// 获取指定名称的数据库连接(如读库 `replica`)
+pub fn get_db_conn_named(name: &str) -> Result<Arc<DbConn>, DbError> {
+    let read_lock = DB_CONNS.read().map_err(|_| DbError::GetDbConn())?;
+    read_lock
+        .as_ref()
+        .and_then(|db_conns| db_conns.get(name))
+        .cloned()
+        .ok_or(DbError::GetDbConn())
+}
+
+/// # 获取读数据库连接
+///
+/// 初始化了读库(`REPLICA_DB_CONN_NAME`)时返回读库连接，否则返回默认的数据库连接；
+/// 当前请求中执行过写操作(见 `crate::ctx::mark_read_primary`)时返回默认的(主库)连接，避免读不到刚写入的数据
+pub fn get_read_db_conn() -> Result<Arc<DbConn>, DbError> {
+    if !current_read_primary()
+        && let Ok(db_conn) = get_db_conn_named(REPLICA_DB_CONN_NAME)
+    {
+        return Ok(db_conn);
+    }
+    get_db_conn()
+}
+
+/// 设置指定名称的数据库连接
+pub fn set_db_conn_named(name: &str, value: DbConn) -> Result<(), DbError> {
+    let mut write_lock = DB_CONNS.write().map_err(|_| DbError::SetDbConn())?;
+    write_lock
+        .get_or_insert_with(HashMap::new)
+        .insert(name.to_string(), Arc::new(value));
     Ok(())
 }
 
 /// # 初始化数据库连接
 ///
-/// 该函数接收数据库配置信息，建立数据库连接，并将连接存储到全局静态变量 `DB_CONN` 中。
-/// 连接建立后，可以通过 `DB_CONN` 全局访问数据库连接。
+/// 该函数接收数据库配置信息，建立数据库连接，并将连接存储到全局静态变量 `DB_CONNS` 中作为默认的数据库连接。
+/// 连接建立后，可以通过 `get_db_conn` 全局访问数据库连接(宏生成的代码依赖于此)，
+/// 同时也会返回该连接，方便调用方直接使用(如在临时数据库上执行一次性的迁移)。
 ///
 /// # 参数
 ///
 /// * `db_config` - 数据库配置信息，包含连接数据库所需的信息
 ///
+/// # 返回值
+///
+/// 返回建立好的数据库连接(与全局变量中的是同一个连接)
+///
 /// # Panics
 ///
 /// * 如果数据库连接失败，程序将 panic
 /// * 如果无法设置全局数据库连接，程序将 panic
+pub async fn init_db_conn(db_conn_config: DbConnConfig) -> Result<Arc<DbConn>, DbError> {
+    init_db_conn_named(DEFAULT_DB_CONN_NAME, db_conn_config).await
+}
+
+/// # 初始化指定名称的数据库连接
+///
+/// 用于读写分离或访问第二个数据库，可以用不同的名称多次调用，之后通过 `get_db_conn_named` 获取。
+///
+/// 分页配置及断线重连只对默认的数据库连接(`DEFAULT_DB_CONN_NAME`)生效，
+/// 其它名称的数据库连接的健康检查名为 `db.<名称>`
+///
+/// # 参数
+///
+/// * `name` - 数据库连接的名称
+/// * `db_config` - 数据库配置信息，包含连接数据库所需的信息
+///
+/// # 返回值
+///
+/// 返回建立好的数据库连接(与全局变量中的是同一个连接)
 #[log_call]
-pub async fn init_db_conn(db_conn_config: DbConnConfig) -> Result<(), DbError> {
-    debug!("init database...");
+pub async fn init_db_conn_named(
+    name: &str,
+    db_conn_config: DbConnConfig,
+) -> Result<Arc<DbConn>, DbError> {
+    debug!("init database<{name}>...");
+
+    let is_default = name == DEFAULT_DB_CONN_NAME;
+    let cfg_name = if is_default {
+        "db".to_string()
+    } else {
+        format!("db.{name}")
+    };
+
+    // 登记当前生效的数据库配置(导出时会遮盖url中的密码)
+    register_live_cfg(&cfg_name, &db_conn_config);
+
+    // 没有配置url时，由scheme、host等配置项组成连接URL
+    let db_conn_config = DbConnConfig {
+        url: resolve_db_url(&db_conn_config)?,
+        ..db_conn_config
+    };
 
-    if db_conn_config.url.is_empty() {
+    // 设置分页配置
+    if is_default {
+        set_page_config(db_conn_config.page.clone());
+    }
+
+    // 连接数据库
+    let connection = connect_db(&db_conn_config).await?;
+    // 设置数据库连接到全局变量中
+    set_db_conn_named(name, connection)?;
+    // 保留数据库配置，用于断线重连
+    if is_default {
+        let mut write_lock = DB_CONN_CONFIG.write().map_err(|_| DbError::SetDbConn())?;
+        *write_lock = Some(db_conn_config);
+    }
+    // 登记数据库的健康检查(每次检查时获取当前的全局连接，重连后同样有效)
+    #[cfg(feature = "web")]
+    {
+        let conn_name = name.to_string();
+        crate::web::register_health_check(&cfg_name, true, move || {
+            let conn_name = conn_name.clone();
+            async move { ping_db_named(&conn_name).await.map_err(|e| e.to_string()) }
+        });
+    }
+    get_db_conn_named(name)
+}
+
+/// # 检查默认的数据库连接是否可用
+///
+/// 获取全局的数据库连接并执行一次ping，数据库连接未初始化时返回 `DbError::GetDbConn`，不会panic
+pub async fn ping_db() -> Result<(), DbError> {
+    ping_db_named(DEFAULT_DB_CONN_NAME).await
+}
+
+/// # 检查指定名称的数据库连接是否可用
+///
+/// 默认的数据库连接ping失败时同样会报告数据库错误(连接级别的错误可能触发断线重连)，
+/// 断线重连只针对默认的数据库连接，所以其它连接的错误不会报告
+pub async fn ping_db_named(name: &str) -> Result<(), DbError> {
+    let db_conn = get_db_conn_named(name)?;
+    db_conn.ping().await.map_err(|e| {
+        if name == DEFAULT_DB_CONN_NAME {
+            report_db_err(&e);
+        }
+        DbError::Ping(e)
+    })
+}
+
+/// # 解析数据库连接URL
+///
+/// 配置了url时直接使用，否则由 `scheme`、`host`、`port`、`database`、`username` 及
+/// 从 `password-file`/`password-env` 中读取的密码组成连接URL(用户名、密码及数据库名称会进行URL编码)
+pub(crate) fn resolve_db_url(db_conn_config: &DbConnConfig) -> Result<String, DbError> {
+    let DbConnConfig {
+        url,
+        scheme,
+        host,
+        port,
+        database,
+        username,
+        password_file,
+        password_env,
+        ..
+    } = db_conn_config;
+    let has_components = scheme.is_some()
+        || host.is_some()
+        || port.is_some()
+        || database.is_some()
+        || username.is_some()
+        || password_file.is_some()
+        || password_env.is_some();
+    if !url.is_empty() {
+        if has_components {
+            Err(DbError::Config(
+                "db.url and the url components (scheme/host/port/database/username/password-file/password-env) cannot be configured at the same time".to_string(),
+            ))?;
+        }
+        return Ok(url.clone());
+    }
+    if !has_components {
         Err(DbError::Config(
             "db.url (database connection string) item has not been configured yet".to_string(),
         ))?;
     }
+    let (Some(scheme), Some(host)) = (scheme, host) else {
+        return Err(DbError::Config(
+            "db.scheme and db.host must be configured when db.url is not configured".to_string(),
+        ));
+    };
+
+    let password = match (password_file, password_env) {
+        (Some(_), Some(_)) => {
+            return Err(DbError::Config(
+                "db.password-file and db.password-env cannot be configured at the same time"
+                    .to_string(),
+            ));
+        }
+        (Some(password_file), None) => {
+            let password_file = if password_file.is_relative() {
+                match APP_ENV.get() {
+                    Some(AppEnv { app_dir, .. }) => app_dir.join(password_file),
+                    None => password_file.clone(),
+                }
+            } else {
+                password_file.clone()
+            };
+            let password = std::fs::read_to_string(&password_file).map_err(|e| {
+                DbError::Config(format!(
+                    "Fail to read db.password-file<{}>: {e}",
+                    password_file.display()
+                ))
+            })?;
+            Some(password.trim_end_matches(['\r', '\n']).to_string())
+        }
+        (None, Some(password_env)) => Some(std::env::var(password_env).map_err(|e| {
+            DbError::Config(format!("Fail to read db.password-env<{password_env}>: {e}"))
+        })?),
+        (None, None) => None,
+    };
+
+    let mut url = format!("{scheme}://");
+    if let Some(username) = username {
+        url.push_str(&encode_url_component(username));
+        if let Some(password) = password {
+            url.push(':');
+            url.push_str(&encode_url_component(&password));
+        }
+        url.push('@');
+    }
+    url.push_str(host);
+    if let Some(port) = port {
+        url.push_str(&format!(":{port}"));
+    }
+    if let Some(database) = database {
+        url.push('/');
+        url.push_str(&encode_url_component(database));
+    }
+    Ok(url)
+}
 
+/// URL编码(除了字母、数字及 `-._~` 以外的字符都进行编码)
+fn encode_url_component(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// 根据数据库配置连接数据库
+async fn connect_db(db_conn_config: &DbConnConfig) -> Result<DbConn, DbError> {
     // 获取数据库配置
-    let mut opt = ConnectOptions::new(db_conn_config.url);
+    let mut opt = ConnectOptions::new(db_conn_config.url.clone());
 
     // 设置sql日志按什么级别输出
     opt.sqlx_logging_level(db_conn_config.log_level);
 
-    // 连接数据库
-    let connection = Database::connect(opt).await.map_err(DbError::Connect)?;
-    // 设置数据库连接到全局变量中
-    set_db_conn(connection)
+    // 设置连接池(未配置的项使用默认值)
+    if let Some(max_connections) = db_conn_config.max_connections {
+        opt.max_connections(max_connections);
+    }
+    if let Some(min_connections) = db_conn_config.min_connections {
+        opt.min_connections(min_connections);
+    }
+    if let Some(connect_timeout) = db_conn_config.connect_timeout {
+        opt.connect_timeout(connect_timeout);
+    }
+    if let Some(acquire_timeout) = db_conn_config.acquire_timeout {
+        opt.acquire_timeout(acquire_timeout);
+    }
+    if let Some(idle_timeout) = db_conn_config.idle_timeout {
+        opt.idle_timeout(idle_timeout);
+    }
+    if let Some(max_lifetime) = db_conn_config.max_lifetime {
+        opt.max_lifetime(max_lifetime);
+    }
+
+    Database::connect(opt).await.map_err(DbError::Connect)
+}
+
+/// # 报告数据库错误
+///
+/// 如果是连接级别的错误(获取连接失败或连接断开)，则累计连续错误次数，
+/// 在统计窗口内达到阈值时，在后台使用保留的数据库配置重连(失败时按指数退避重试)，
+/// 成功后替换全局的数据库连接
+///
+/// DAO解析数据库错误时会自动调用，自己执行SQL时也可以调用此方法报告错误
+///
+/// 未配置 `reconnect` 或未启用时不做任何处理，不在tokio运行时中调用时只累计错误次数，不会重连
+pub fn report_db_err(db_err: &DbErr) {
+    if !matches!(db_err, DbErr::ConnectionAcquire(_) | DbErr::Conn(_)) {
+        return;
+    }
+    let Some(db_conn_config) = DB_CONN_CONFIG.read().ok().and_then(|lock| lock.clone()) else {
+        return;
+    };
+    let Some(reconnect_config) = db_conn_config.reconnect.clone() else {
+        return;
+    };
+    if !reconnect_config.enabled {
+        return;
+    }
+
+    // 累计连续错误次数
+    let count = {
+        let Ok(mut stat) = CONN_ERR_STAT.lock() else {
+            return;
+        };
+        let count = match *stat {
+            Some((count, last_time)) if last_time.elapsed() < reconnect_config.window => count + 1,
+            _ => 1,
+        };
+        *stat = Some((count, Instant::now()));
+        count
+    };
+    warn!(
+        "数据库连接错误({count}/{}): {db_err}",
+        reconnect_config.threshold
+    );
+    if count < reconnect_config.threshold {
+        return;
+    }
+    // 重连需要在tokio运行时中进行
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        warn!("不在tokio运行时中，无法重新连接数据库");
+        return;
+    };
+    if RECONNECTING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    runtime.spawn(async move {
+        let mut backoff = reconnect_config.initial_backoff;
+        loop {
+            warn!("重新连接数据库...");
+            match connect_db(&db_conn_config).await {
+                Ok(connection) => match set_db_conn(connection) {
+                    Ok(()) => {
+                        info!("重新连接数据库成功");
+                        break;
+                    }
+                    Err(e) => error!("重新连接数据库后设置全局数据库连接失败: {e}"),
+                },
+                Err(e) => error!("重新连接数据库失败，{backoff:?}后重试: {e}"),
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(reconnect_config.max_backoff);
+        }
+        if let Ok(mut stat) = CONN_ERR_STAT.lock() {
+            *stat = None;
+        }
+        RECONNECTING.store(false, Ordering::SeqCst);
+    });
 }
@@ -1,8 +1,14 @@
 mod db_conn_config;
 mod db_error;
+mod db_migrate_utils;
+mod db_reconnect_config;
+mod db_transaction_utils;
 mod db_utils;
 
 // 重新导出结构体，简化外部引用
 pub use db_conn_config::DbConnConfig;
 pub use db_error::*;
+pub use db_migrate_utils::*;
+pub use db_reconnect_config::*;
+pub use db_transaction_utils::*;
 pub use db_utils::*;
@@ -5,6 +5,8 @@ pub mod app;
 #[cfg(feature = "app")]
 pub mod cfg;
 pub mod cst;
+#[cfg(any(feature = "web", feature = "api-client", feature = "db"))]
+pub mod ctx;
 #[cfg(feature = "db")]
 pub mod dao;
 #[cfg(feature = "db")]
@@ -16,6 +18,7 @@ pub mod log;
 #[cfg(feature = "macros")]
 pub mod macros;
 pub mod mq;
+pub mod prelude;
 pub mod ro;
 #[cfg(feature = "app")]
 pub mod signal;
@@ -26,3 +29,20 @@ pub mod tsdb;
 pub mod web;
 
 pub use ro::rx;
+
+// 使宏生成的 `::robotech::...` 路径在本crate内部同样可用
+extern crate self as robotech;
+
+/// 宏生成的代码使用的依赖，使用宏的项目不需要为此再直接依赖这些crate
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "app")]
+    pub use idworker;
+    pub use linkme;
+    #[cfg(feature = "db")]
+    pub use sea_orm;
+    pub use tracing;
+    #[cfg(feature = "app")]
+    pub use validator;
+    pub use wheel_rs;
+}
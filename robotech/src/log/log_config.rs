@@ -1,4 +1,7 @@
+use crate::log::OtlpConfig;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
 use tracing_appender::rolling::Rotation;
 use wheel_rs::serde::rotation_serde;
 
@@ -7,14 +10,117 @@ use wheel_rs::serde::rotation_serde;
 pub struct LogConfig {
     #[serde(default = "level_default")]
     pub level: String,
+    /// 按target(模块路径)覆盖日志级别，如 `sqlx = "warn"`、`"robotech::svc" = "debug"`
+    ///
+    /// 在 `level` 的基础上生效，设置了 `RUST_LOG` 环境变量时以环境变量为准
+    #[serde(default)]
+    pub targets: HashMap<String, String>,
     #[serde(default = "console_time_format_default")]
     pub console_time_format: String,
     #[serde(default = "file_time_format_default")]
     pub file_time_format: String,
     #[serde(with = "rotation_serde", default = "log_rotation_default")]
     pub rotation: Rotation,
+    /// 单个日志文件的最大字节数(默认不限制)
+    ///
+    /// 设置后在 `rotation` 按时间滚动的基础上，文件超过此大小时也会滚动到新文件，
+    /// 文件名为 `{app}.log.{时间段}.{序号}.{后缀}`
+    #[serde(default)]
+    pub max_file_size: Option<u64>,
+    /// 最多保留的日志文件数(默认不限制)，超出时删除最旧的日志文件
+    #[serde(default)]
+    pub max_files: Option<usize>,
     #[serde(default)]
     pub show_spans: bool,
+    /// 打印span链时，每个span的字段(参数)输出的最大字符数(默认不限制)
+    ///
+    /// `#[instrument]` 捕获了整个DTO等大参数时，超出部分以 `…` 省略，避免日志行过长
+    #[serde(default)]
+    pub max_span_field_len: Option<usize>,
+    /// 控制台日志是否显示target(模块路径)，显示在级别与消息之间
+    #[serde(default)]
+    pub show_target: bool,
+    /// 控制台日志是否输出ANSI转义码(颜色及超链接)
+    ///
+    /// 不设置时标准输出是终端则输出，否则(如被journald收集或重定向到文件)不输出
+    #[serde(default)]
+    pub console_ansi: Option<bool>,
+    /// 控制台日志中源码超链接的源码根目录(日志中的源码路径相对于此目录)
+    ///
+    /// 不设置时依次在 `CARGO_MANIFEST_DIR` 环境变量(`cargo run` 时会设置)及当前目录(包括它们的上级目录)中查找源码文件，
+    /// 找不到源码文件时只输出 `文件:行号`，不输出超链接
+    #[serde(default)]
+    pub source_root: Option<PathBuf>,
+    /// 日志时间的时区(默认Local)，同时作用于控制台及文件日志
+    ///
+    /// 集中收集日志时，建议各主机统一配置为 `utc`，便于关联不同主机的日志
+    #[serde(default)]
+    pub timezone: LogTimezone,
+    /// 是否输出日志文件(默认开启)
+    ///
+    /// 在容器中部署且日志只通过标准输出收集时，可关闭以避免写入容器的临时文件系统
+    #[serde(default = "file_enabled_default")]
+    pub file_enabled: bool,
+    /// 日志文件的格式(默认json)
+    ///
+    /// json便于日志收集系统解析，本地调试时可设置为text以便直接查看
+    #[serde(default)]
+    pub file_format: LogFileFormat,
+    /// 是否同时输出人类可读的日志文件(默认关闭)
+    ///
+    /// 开启后除了JSON格式的日志文件，还会以控制台的格式(不带颜色)输出到 `{app}.human.log` 文件，
+    /// 与JSON日志文件使用相同的滚动策略
+    #[serde(default)]
+    pub human_file: bool,
+    /// OpenTelemetry(OTLP)导出配置(不设置默认不导出)
+    #[serde(default)]
+    pub otlp: Option<OtlpConfig>,
+}
+
+/// # 日志文件的格式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogFileFormat {
+    /// JSON格式(文件后缀为json)
+    #[default]
+    Json,
+    /// 纯文本格式(文件后缀为log)
+    Text,
+}
+
+impl LogFileFormat {
+    /// 日志文件的后缀
+    pub fn filename_suffix(&self) -> &'static str {
+        match self {
+            LogFileFormat::Json => "json",
+            LogFileFormat::Text => "log",
+        }
+    }
+}
+
+/// # 日志时间的时区
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogTimezone {
+    /// 本机时区
+    #[default]
+    Local,
+    /// UTC
+    Utc,
+}
+
+impl LogTimezone {
+    /// 按时区格式化当前时间
+    pub fn format_now(&self, time_format: &str) -> String {
+        match self {
+            LogTimezone::Local => chrono::Local::now().format(time_format).to_string(),
+            LogTimezone::Utc => chrono::Utc::now().format(time_format).to_string(),
+        }
+    }
+}
+
+fn file_enabled_default() -> bool {
+    true
 }
 
 fn level_default() -> String {
@@ -11,8 +11,12 @@ pub enum LogError {
     GetEnv(#[from] EnvError),
     #[error("Fail to create file appender: {0}")]
     CreateFileAppender(InitError),
+    #[error("Fail to create file writer: {0}")]
+    CreateFileWriter(std::io::Error),
     #[error("Fail to set LOG_GUARD")]
     SetLogGuard(),
     #[error("Fail to set LOG_CONFIG_GUARD")]
     SetLogConfigGuard(),
+    #[error("Fail to init OTLP exporter: {0}")]
+    InitOtlp(String),
 }
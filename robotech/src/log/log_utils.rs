@@ -1,17 +1,22 @@
-use crate::cfg::{CfgError, build_cfg, watch_cfg_file};
+use crate::cfg::{CfgError, build_cfg, register_live_cfg, watch_cfg_file};
 use crate::env::{APP_ENV, AppEnv, EnvError};
-use crate::log::{LogConfig, LogError};
-use tracing::debug;
+use crate::log::{LogConfig, LogError, LogFileFormat, LogTimezone, SizeRollingWriter};
+#[cfg(feature = "otlp")]
+use crate::log::{init_otlp_tracer, shutdown_otlp};
+use tracing::{debug, warn};
 use robotech_macros::watch_cfg_file;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::env;
-use std::path::Path;
-use std::sync::{Arc, RwLock};
-use tracing_appender::non_blocking::WorkerGuard;
-use tracing_appender::rolling::RollingFileAppender;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_core::{Event, Level, Subscriber};
 use tracing_log::NormalizeEvent;
 use tracing_subscriber::fmt::format::{DefaultFields, Writer};
-use tracing_subscriber::fmt::time::ChronoLocal;
+use tracing_subscriber::fmt::time::FormatTime;
 use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::registry::LookupSpan;
@@ -21,20 +26,115 @@ use tracing_subscriber::{EnvFilter, fmt, reload};
 /// 日志文件输出锁
 /// 解决锁在初始化方法结束后被提前释放导致后续日志不能输出
 static LOG_GUARD: RwLock<Option<WorkerGuard>> = RwLock::new(None);
+/// 人类可读日志文件输出锁
+static HUMAN_LOG_GUARD: RwLock<Option<WorkerGuard>> = RwLock::new(None);
+
+/// 按配置时区输出时间的计时器
+///
+/// 本地时区与UTC使用同一个类型，以便重新加载配置时可以切换时区
+struct LogTimer {
+    /// 时间格式
+    timer_format: String,
+    /// 时区
+    timezone: LogTimezone,
+}
+
+impl FormatTime for LogTimer {
+    fn format_time(&self, writer: &mut Writer<'_>) -> std::fmt::Result {
+        write!(
+            writer,
+            "{}",
+            self.timezone.format_now(self.timer_format.as_str())
+        )
+    }
+}
 
 struct CustomConsoleFormatter {
     /// 时间格式
     timer_format: String,
+    /// 时区
+    timezone: LogTimezone,
     /// 是否打印 span 链（包括函数名和参数，需 #[instrument] 配合）
     show_spans: bool,
+    /// 每个 span 的字段输出的最大字符数
+    max_span_field_len: Option<usize>,
+    /// 是否打印 target（模块路径）
+    show_target: bool,
+    /// 是否输出ANSI转义码(颜色及超链接)，输出到文件或配置关闭时不输出
+    ansi: bool,
+    /// 源码根目录
+    source_root: Option<PathBuf>,
+    /// 源码文件的超链接缓存(源码路径 -> 超链接，找不到源码文件时为None)
+    source_links: Mutex<HashMap<String, Option<String>>>,
 }
 
 impl CustomConsoleFormatter {
-    pub fn new(timer_format: String, show_spans: bool) -> Self {
+    pub fn new(
+        timer_format: String,
+        timezone: LogTimezone,
+        show_spans: bool,
+        max_span_field_len: Option<usize>,
+        show_target: bool,
+        ansi: bool,
+        source_root: Option<PathBuf>,
+    ) -> Self {
         Self {
             timer_format,
+            timezone,
             show_spans,
+            max_span_field_len,
+            show_target,
+            ansi,
+            source_root,
+            source_links: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 获取源码文件的超链接(file:// URL)，找不到源码文件时返回None
+    fn source_link(&self, file_path: &str) -> Option<String> {
+        if let Ok(source_links) = self.source_links.lock()
+            && let Some(source_link) = source_links.get(file_path)
+        {
+            return source_link.clone();
         }
+        let source_link = self
+            .resolve_source_path(file_path)
+            .map(|source_path| file_url(&source_path));
+        if let Ok(mut source_links) = self.source_links.lock() {
+            source_links.insert(file_path.to_string(), source_link.clone());
+        }
+        source_link
+    }
+
+    /// 查找源码文件的位置
+    ///
+    /// 源码路径是相对路径时(相对于编译时的工作空间目录)，在源码根目录及其上级目录中查找
+    fn resolve_source_path(&self, file_path: &str) -> Option<PathBuf> {
+        let file_path = Path::new(file_path);
+        if file_path.is_absolute() {
+            return file_path.is_file().then(|| file_path.to_path_buf());
+        }
+        let source_roots: Vec<PathBuf> = match &self.source_root {
+            Some(source_root) => vec![source_root.clone()],
+            None => env::var_os("CARGO_MANIFEST_DIR")
+                .map(PathBuf::from)
+                .into_iter()
+                .chain(env::current_dir().ok())
+                .collect(),
+        };
+        source_roots
+            .iter()
+            .flat_map(|source_root| source_root.ancestors())
+            .map(|dir| dir.join(file_path))
+            .find(|source_path| source_path.is_file())
+    }
+
+    /// 输出ANSI转义码(关闭ANSI时忽略)
+    fn write_ansi(&self, writer: &mut Writer<'_>, code: &str) -> std::fmt::Result {
+        if self.ansi {
+            write!(writer, "{}", code)?;
+        }
+        Ok(())
     }
 }
 
@@ -56,46 +156,53 @@ where
 
         // 根据日志级别设置不同字体颜色
         let level = metadata.level();
-        write!(
-            writer,
-            "\x1B[{}m ",
+        self.write_ansi(
+            &mut writer,
             match *level {
-                Level::TRACE => 37,
-                Level::DEBUG => 32,
-                Level::INFO => 97,
-                Level::WARN => 33,
-                Level::ERROR => 31,
-            }
+                Level::TRACE => "\x1B[37m",
+                Level::DEBUG => "\x1B[32m",
+                Level::INFO => "\x1B[97m",
+                Level::WARN => "\x1B[33m",
+                Level::ERROR => "\x1B[31m",
+            },
         )?;
+        write!(writer, " ")?;
 
-        let time_str = chrono::Local::now()
-            .format(self.timer_format.as_str())
-            .to_string();
+        let time_str = self.timezone.format_now(self.timer_format.as_str());
         write!(writer, "{} ", time_str)?;
 
         write!(writer, "{:<5} ", *level)?;
 
+        if self.show_target {
+            write!(writer, "{} - ", metadata.target())?;
+        }
+
         // 格式化事件字段
         // 设置字体颜色
         let visitor = DefaultFields::default();
         visitor.format_fields(writer.by_ref(), event)?;
 
         // 添加一个分隔符"-"
-        write!(writer, " \x1B[1;93m-\x1B[0m ")?;
+        write!(writer, " ")?;
+        self.write_ansi(&mut writer, "\x1B[1;93m")?;
+        write!(writer, "-")?;
+        self.write_ansi(&mut writer, "\x1B[0m")?;
+        write!(writer, " ")?;
 
         // 获取文件和行号信息
         // 设置字体颜色为蓝色
-        write!(writer, "\x1B[34m")?;
+        self.write_ansi(&mut writer, "\x1B[34m")?;
         if let (Some(file_path), Some(line_number)) = (metadata.file(), metadata.line()) {
-            let current_dir = env::current_dir().map_err(|_| std::fmt::Error)?;
-            let absolute_path = current_dir.join(file_path);
-            let path = format!("{}:{}", absolute_path.display(), line_number);
             let label = format!("{}:{}", file_path, line_number);
-            write!(
-                writer,
-                "\x1B]8;;file://{}\x1B\\{}\x1B]8;;\x1B\\",
-                path, label
-            )?;
+            match self.ansi.then(|| self.source_link(file_path)).flatten() {
+                Some(source_link) => write!(
+                    writer,
+                    "\x1B]8;;{}:{}\x1B\\{}\x1B]8;;\x1B\\",
+                    source_link, line_number, label
+                )?,
+                // 找不到源码文件时只输出文件及行号，避免输出打不开的超链接
+                None => write!(writer, "{}", label)?,
+            }
         }
 
         // 打印 span 链（包括函数名和参数）
@@ -103,51 +210,137 @@ where
             if let Some(scope) = _ctx.event_scope() {
                 for span in scope.from_root() {
                     // 添加一个箭头"->"
-                    write!(writer, " \x1B[1;93m->\x1B[0m ")?;
+                    write!(writer, " ")?;
+                    self.write_ansi(&mut writer, "\x1B[1;93m")?;
+                    write!(writer, "->")?;
+                    self.write_ansi(&mut writer, "\x1B[0m")?;
+                    write!(writer, " ")?;
                     // 设置字体颜色为蓝色
-                    write!(writer, "\x1B[34m")?;
+                    self.write_ansi(&mut writer, "\x1B[34m")?;
                     write!(writer, "{}(", span.name())?;
                     // 重置字体颜色
-                    write!(writer, "\x1B[0m")?;
+                    self.write_ansi(&mut writer, "\x1B[0m")?;
                     // 打印 span 的字段（参数）
                     let extensions = span.extensions();
                     if let Some(fields) = extensions.get::<fmt::FormattedFields<N>>() {
-                        write!(writer, "{}", fields)?;
+                        match self.max_span_field_len {
+                            Some(max_len) => {
+                                write!(writer, "{}", truncate_fields(&fields.fields, max_len))?
+                            }
+                            None => write!(writer, "{}", fields)?,
+                        }
                     }
                     // 设置字体颜色为蓝色
-                    write!(writer, "\x1B[34m")?;
+                    self.write_ansi(&mut writer, "\x1B[34m")?;
                     write!(writer, ")")?;
                     // 重置字体颜色
-                    write!(writer, "\x1B[0m")?;
+                    self.write_ansi(&mut writer, "\x1B[0m")?;
                 }
             }
         }
 
         // 重置字体颜色
-        write!(writer, "\x1B[0m")?;
+        self.write_ansi(&mut writer, "\x1B[0m")?;
 
         writeln!(writer)
     }
 }
 
+/// 将文件路径转换为 file:// URL
+///
+/// 统一使用正斜杠(Windows的 `C:\src` 转换为 `/C:/src`)，并对路径中的特殊字符进行百分号编码
+fn file_url(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut url = String::from("file://");
+    if !path.starts_with('/') {
+        url.push('/');
+    }
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/:".contains(&byte) {
+            url.push(byte as char);
+        } else {
+            url.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    url
+}
+
+/// 截断span的字段输出，超过最大字符数时以 `…` 省略
+///
+/// 字段中可能带有ANSI转义码(如斜体的字段名)，转义码不计入字符数，也不会被截断
+fn truncate_fields(fields: &str, max_len: usize) -> Cow<'_, str> {
+    if fields.chars().count() <= max_len {
+        return Cow::Borrowed(fields);
+    }
+    let mut truncated = String::with_capacity(fields.len().min(max_len * 4));
+    let mut visible_len = 0;
+    let mut chars = fields.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1B' {
+            // 完整保留转义码(以字母结尾)
+            truncated.push(c);
+            for c in chars.by_ref() {
+                truncated.push(c);
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+        if visible_len >= max_len {
+            truncated.push('…');
+            break;
+        }
+        truncated.push(c);
+        visible_len += 1;
+    }
+    Cow::Owned(truncated)
+}
+
 macro_rules! creat_console_layer {
-    ($console_time_format:expr, $show_spans:expr) => {
+    ($console_time_format:expr, $timezone:expr, $show_spans:expr, $max_span_field_len:expr, $show_target:expr, $ansi:expr, $source_root:expr) => {
         fmt::layer()
             // .with_timer(ChronoLocal::new("%H:%M:%S%.6f".to_string()))
             // .with_target(false)
             // .pretty()
             .event_format(CustomConsoleFormatter::new(
                 $console_time_format,
+                $timezone,
                 $show_spans,
+                $max_span_field_len,
+                $show_target,
+                $ansi,
+                $source_root,
             ))
+            .with_ansi($ansi)
             .with_writer(std::io::stdout)
     };
 }
 
+macro_rules! creat_human_file_layer {
+    ($file_time_format:expr, $timezone:expr, $show_spans:expr, $max_span_field_len:expr, $show_target:expr, $non_blocking:expr) => {
+        fmt::layer()
+            .event_format(CustomConsoleFormatter::new(
+                $file_time_format.to_string(),
+                $timezone,
+                $show_spans,
+                $max_span_field_len,
+                $show_target,
+                false,
+                None,
+            ))
+            .with_ansi(false)
+            .with_writer($non_blocking)
+    };
+}
+
 macro_rules! creat_file_layer {
-    ($file_time_format:expr,$non_blocking:expr) => {
+    ($file_time_format:expr, $timezone:expr, $non_blocking:expr) => {
         fmt::layer()
-            .with_timer(ChronoLocal::new($file_time_format.to_string()))
+            .with_timer(LogTimer {
+                timer_format: $file_time_format.to_string(),
+                timezone: $timezone,
+            })
             .with_file(true)
             .with_line_number(true)
             .json()
@@ -155,26 +348,61 @@ macro_rules! creat_file_layer {
     };
 }
 
+macro_rules! creat_text_file_layer {
+    ($file_time_format:expr, $timezone:expr, $non_blocking:expr) => {
+        fmt::layer()
+            .with_timer(LogTimer {
+                timer_format: $file_time_format.to_string(),
+                timezone: $timezone,
+            })
+            .with_file(true)
+            .with_line_number(true)
+            .compact()
+            .with_ansi(false)
+            .with_writer($non_blocking)
+    };
+}
+
 /// 初始化日志
 pub fn init_log() -> Result<(), LogError> {
     let (
         LogConfig {
             level,
+            targets,
             console_time_format,
             file_time_format,
             show_spans,
+            max_span_field_len,
+            show_target,
+            console_ansi,
+            source_root,
             rotation,
+            max_file_size,
+            max_files,
+            timezone,
+            file_enabled,
+            file_format,
+            human_file,
+            otlp,
         },
         files,
     ) = build_log_cfg()?;
     let files = Arc::new(files);
 
     // 创建环境过滤器，支持 RUST_LOG 环境变量
-    let env_filter = create_env_filter(level);
+    let env_filter = create_env_filter(level, &targets);
     let (env_filter_layer, env_layer_reload_handle) = reload::Layer::new(env_filter);
 
     // 控制台输出层
-    let console_layer = creat_console_layer!(console_time_format, show_spans);
+    let console_layer = creat_console_layer!(
+        console_time_format,
+        timezone,
+        show_spans,
+        max_span_field_len,
+        show_target,
+        console_ansi_enabled(console_ansi),
+        source_root
+    );
     let (console_layer, console_layer_reload_handle) = reload::Layer::new(console_layer);
 
     // 文件输出层
@@ -184,37 +412,151 @@ pub fn init_log() -> Result<(), LogError> {
         ..
     } = APP_ENV.get().ok_or(EnvError::GetAppEnv())?;
     let log_dir_path = app_dir.join("log");
-    let log_dir = log_dir_path.to_string_lossy().to_string();
-    let file_appender = RollingFileAppender::builder()
-        .rotation(rotation.clone()) // 滚动策略
-        .filename_prefix(format!("{}.log", app_file_name)) // 文件名前缀
-        .filename_suffix("json") // 文件后缀，如 "log", "txt" 等
-        .build(log_dir_path) // 日志目录
-        .map_err(|e| LogError::CreateFileAppender(e))?;
-    let (non_blocking, log_guard) = tracing_appender::non_blocking(file_appender);
-    let file_layer = creat_file_layer!(file_time_format, non_blocking);
-    {
-        let mut log_guard_write_lock = LOG_GUARD.write().map_err(|_| LogError::SetLogGuard())?;
-        *log_guard_write_lock = Some(log_guard); // 解决锁在初始化方法结束后被提前释放导致后续日志不能输出
-    }
+    // JSON与纯文本格式的输出层类型不同，分别放在两个输出层中，同时只会启用其中一个
+    let mut file_writer = if file_enabled {
+        let (non_blocking, log_guard) = create_file_writer(
+            &log_dir_path,
+            format!("{}.log", app_file_name),
+            file_format.filename_suffix(),
+            rotation.clone(),
+            max_file_size,
+            max_files,
+        )?;
+        {
+            let mut log_guard_write_lock =
+                LOG_GUARD.write().map_err(|_| LogError::SetLogGuard())?;
+            *log_guard_write_lock = Some(log_guard); // 解决锁在初始化方法结束后被提前释放导致后续日志不能输出
+        }
+        Some(non_blocking)
+    } else {
+        None
+    };
+    let (file_layer, text_file_layer) = match &file_writer {
+        Some(file_writer) => match file_format {
+            LogFileFormat::Json => (
+                Some(creat_file_layer!(
+                    file_time_format,
+                    timezone,
+                    file_writer.clone()
+                )),
+                None,
+            ),
+            LogFileFormat::Text => (
+                None,
+                Some(creat_text_file_layer!(
+                    file_time_format,
+                    timezone,
+                    file_writer.clone()
+                )),
+            ),
+        },
+        None => (None, None),
+    };
     let (file_layer, file_layer_reload_handle) = reload::Layer::new(file_layer);
+    let (text_file_layer, text_file_layer_reload_handle) = reload::Layer::new(text_file_layer);
+
+    // 人类可读的文件输出层
+    let mut human_file_writer = if human_file {
+        let (non_blocking, log_guard) = create_file_writer(
+            &log_dir_path,
+            format!("{}.human", app_file_name),
+            "log",
+            rotation.clone(),
+            max_file_size,
+            max_files,
+        )?;
+        {
+            let mut log_guard_write_lock = HUMAN_LOG_GUARD
+                .write()
+                .map_err(|_| LogError::SetLogGuard())?;
+            *log_guard_write_lock = Some(log_guard);
+        }
+        Some(non_blocking)
+    } else {
+        None
+    };
+    let human_file_layer = human_file_writer.as_ref().map(|human_file_writer| {
+        creat_human_file_layer!(
+            file_time_format,
+            timezone,
+            show_spans,
+            max_span_field_len,
+            show_target,
+            human_file_writer.clone()
+        )
+    });
+    let (human_file_layer, human_file_layer_reload_handle) = reload::Layer::new(human_file_layer);
+
+    // 当前文件输出层的设置，热加载时据此判断是否需要重建
+    let mut file_appender_settings = (
+        file_enabled,
+        rotation.clone(),
+        file_format,
+        max_file_size,
+        max_files,
+    );
+    let mut file_layer_settings = (file_time_format.clone(), timezone);
+    let mut human_file_appender_settings = (human_file, rotation.clone(), max_file_size, max_files);
+    let mut human_file_layer_settings = (
+        file_time_format.clone(),
+        timezone,
+        show_spans,
+        max_span_field_len,
+        show_target,
+    );
 
-    tracing_subscriber::registry()
+    // OpenTelemetry输出层(与其它输出层共用环境过滤器)
+    #[cfg(feature = "otlp")]
+    let otlp_layer = match &otlp {
+        Some(otlp_config) => {
+            let AppEnv {
+                app_file_name_without_ext,
+                ..
+            } = APP_ENV.get().ok_or(EnvError::GetAppEnv())?;
+            Some(
+                tracing_opentelemetry::layer()
+                    .with_tracer(init_otlp_tracer(otlp_config, app_file_name_without_ext)?),
+            )
+        }
+        None => None,
+    };
+
+    let registry = tracing_subscriber::registry()
         .with(env_filter_layer)
         .with(console_layer) // 控制台输出层
         .with(file_layer) // 文件输出层
-        .init();
+        .with(text_file_layer) // 纯文本格式的文件输出层
+        .with(human_file_layer); // 人类可读的文件输出层
+    #[cfg(feature = "otlp")]
+    let registry = registry.with(otlp_layer); // OpenTelemetry输出层
+    registry.init();
     debug!("初始化日志成功");
+    #[cfg(not(feature = "otlp"))]
+    if otlp.is_some() {
+        warn!("没有启用otlp特性，忽略otlp配置");
+    }
 
     watch_cfg_file!("log", files.clone(), {
         // 重新加载配置
         let (
             LogConfig {
                 level,
+                targets,
                 console_time_format,
                 show_spans,
+                max_span_field_len,
+                show_target,
+                console_ansi,
+                source_root,
                 file_time_format,
                 rotation,
+                max_file_size,
+                max_files,
+                timezone,
+                file_enabled,
+                file_format,
+                human_file,
+                ..
             },
             _,
         ) = build_log_cfg().expect("build log config error");
@@ -222,43 +564,209 @@ pub fn init_log() -> Result<(), LogError> {
         // 应用新配置
         env_layer_reload_handle
             .modify(|filter| {
-                *filter = create_env_filter(level);
+                *filter = create_env_filter(level, &targets);
             })
             .expect("reload log config error");
 
         console_layer_reload_handle
             .modify(|layer| {
-                *layer = creat_console_layer!(console_time_format, show_spans);
+                *layer = creat_console_layer!(
+                    console_time_format,
+                    timezone,
+                    show_spans,
+                    max_span_field_len,
+                    show_target,
+                    console_ansi_enabled(console_ansi),
+                    source_root
+                );
             })
             .expect("reload console config error");
 
-        file_layer_reload_handle
-            .modify(|layer| {
-                // 重新创建文件appender
-                let file_appender = RollingFileAppender::builder()
-                    .rotation(rotation.clone())
-                    .filename_prefix(format!("{}.log", app_file_name))
-                    .filename_suffix("json")
-                    .build(Path::new(log_dir.as_str()))
-                    .expect("create file appender error");
-                let (non_blocking, log_guard) = tracing_appender::non_blocking(file_appender);
-
-                *layer = creat_file_layer!(file_time_format, non_blocking);
-
-                // 更新全局guard
-                let mut guard = LOG_GUARD.write().expect("write log guard");
-                *guard = Some(log_guard);
-            })
-            .expect("reload file config error");
+        // 文件appender的设置变化时才重新创建文件appender，只修改了时间格式等时沿用原来的写入器，
+        // 避免频繁重建写入器导致缓冲中的日志丢失
+        let new_file_appender_settings = (
+            file_enabled,
+            rotation.clone(),
+            file_format,
+            max_file_size,
+            max_files,
+        );
+        let new_file_layer_settings = (file_time_format.clone(), timezone);
+        // 被替换的guard，在输出层切换到新的写入器后再释放(释放时会将缓冲中的日志写入文件)
+        let mut old_log_guard = None;
+        if new_file_appender_settings != file_appender_settings {
+            old_log_guard = LOG_GUARD.write().expect("write log guard").take();
+            // 重新创建文件appender(已关闭文件日志时不再创建)
+            file_writer = file_enabled.then(|| {
+                let (non_blocking, log_guard) = create_file_writer(
+                    &log_dir_path,
+                    format!("{}.log", app_file_name),
+                    file_format.filename_suffix(),
+                    rotation.clone(),
+                    max_file_size,
+                    max_files,
+                )
+                .expect("create file appender error");
+                *LOG_GUARD.write().expect("write log guard") = Some(log_guard);
+                non_blocking
+            });
+        }
+        if new_file_appender_settings != file_appender_settings
+            || new_file_layer_settings != file_layer_settings
+        {
+            // JSON及纯文本格式的输出层只保留配置的那个
+            file_layer_reload_handle
+                .modify(|layer| {
+                    *layer = match &file_writer {
+                        Some(file_writer) if file_format == LogFileFormat::Json => Some(
+                            creat_file_layer!(file_time_format, timezone, file_writer.clone()),
+                        ),
+                        _ => None,
+                    };
+                })
+                .expect("reload file config error");
+            text_file_layer_reload_handle
+                .modify(|layer| {
+                    *layer = match &file_writer {
+                        Some(file_writer) if file_format == LogFileFormat::Text => Some(
+                            creat_text_file_layer!(file_time_format, timezone, file_writer.clone()),
+                        ),
+                        _ => None,
+                    };
+                })
+                .expect("reload text file config error");
+            file_appender_settings = new_file_appender_settings;
+            file_layer_settings = new_file_layer_settings;
+        }
+        drop(old_log_guard);
+
+        let new_human_file_appender_settings =
+            (human_file, rotation.clone(), max_file_size, max_files);
+        let new_human_file_layer_settings = (
+            file_time_format.clone(),
+            timezone,
+            show_spans,
+            max_span_field_len,
+            show_target,
+        );
+        let mut old_human_log_guard = None;
+        if new_human_file_appender_settings != human_file_appender_settings {
+            old_human_log_guard = HUMAN_LOG_GUARD
+                .write()
+                .expect("write human log guard")
+                .take();
+            human_file_writer = human_file.then(|| {
+                let (non_blocking, log_guard) = create_file_writer(
+                    &log_dir_path,
+                    format!("{}.human", app_file_name),
+                    "log",
+                    rotation.clone(),
+                    max_file_size,
+                    max_files,
+                )
+                .expect("create human file appender error");
+                *HUMAN_LOG_GUARD.write().expect("write human log guard") = Some(log_guard);
+                non_blocking
+            });
+        }
+        if new_human_file_appender_settings != human_file_appender_settings
+            || new_human_file_layer_settings != human_file_layer_settings
+        {
+            human_file_layer_reload_handle
+                .modify(|layer| {
+                    *layer = human_file_writer.as_ref().map(|human_file_writer| {
+                        creat_human_file_layer!(
+                            file_time_format,
+                            timezone,
+                            show_spans,
+                            max_span_field_len,
+                            show_target,
+                            human_file_writer.clone()
+                        )
+                    });
+                })
+                .expect("reload human file config error");
+            human_file_appender_settings = new_human_file_appender_settings;
+            human_file_layer_settings = new_human_file_layer_settings;
+        }
+        drop(old_human_log_guard);
     });
 
     Ok(())
 }
 
+/// # 关闭日志
+///
+/// 应用退出前调用，导出缓冲中的span(启用了OTLP导出时)，并将缓冲中的日志写入日志文件
+pub fn shutdown_log() {
+    #[cfg(feature = "otlp")]
+    shutdown_otlp();
+    if let Ok(mut log_guard) = LOG_GUARD.write() {
+        log_guard.take();
+    }
+    if let Ok(mut human_log_guard) = HUMAN_LOG_GUARD.write() {
+        human_log_guard.take();
+    }
+}
+
+/// 创建日志文件的写入器
+///
+/// 配置了 `max-file-size` 时在时间滚动的基础上同时按大小滚动，否则使用 `RollingFileAppender`
+fn create_file_writer(
+    log_dir: &Path,
+    prefix: String,
+    suffix: &str,
+    rotation: Rotation,
+    max_file_size: Option<u64>,
+    max_files: Option<usize>,
+) -> Result<(NonBlocking, WorkerGuard), LogError> {
+    match max_file_size {
+        Some(max_file_size) => {
+            let writer =
+                SizeRollingWriter::new(log_dir, prefix, suffix, rotation, max_file_size, max_files)
+                    .map_err(LogError::CreateFileWriter)?;
+            Ok(tracing_appender::non_blocking(writer))
+        }
+        None => {
+            let mut builder = RollingFileAppender::builder()
+                .rotation(rotation) // 滚动策略
+                .filename_prefix(prefix) // 文件名前缀
+                .filename_suffix(suffix); // 文件后缀，如 "log", "json" 等
+            if let Some(max_files) = max_files {
+                builder = builder.max_log_files(max_files);
+            }
+            let file_appender = builder
+                .build(log_dir) // 日志目录
+                .map_err(LogError::CreateFileAppender)?;
+            Ok(tracing_appender::non_blocking(file_appender))
+        }
+    }
+}
+
 fn build_log_cfg() -> Result<(LogConfig, Vec<String>), CfgError> {
-    build_cfg("LOG", Some("log"), None)
+    let (log_config, files) = build_cfg("LOG", Some("log"), None)?;
+    // 登记当前生效的日志配置(初始化及热加载时都会经过这里)
+    register_live_cfg("log", &log_config);
+    Ok((log_config, files))
+}
+
+/// 控制台日志是否输出ANSI转义码(未配置时标准输出是终端才输出)
+fn console_ansi_enabled(console_ansi: Option<bool>) -> bool {
+    console_ansi.unwrap_or_else(|| std::io::stdout().is_terminal())
 }
 
-fn create_env_filter(level: String) -> EnvFilter {
-    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(level))
+/// 创建环境过滤器
+///
+/// 设置了 `RUST_LOG` 环境变量时以环境变量为准，否则以 `level` 为基础级别，再按target覆盖级别
+fn create_env_filter(level: String, targets: &HashMap<String, String>) -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        let mut directives = vec![level];
+        directives.extend(
+            targets
+                .iter()
+                .map(|(target, level)| format!("{target}={level}")),
+        );
+        // 忽略格式不正确的指令
+        EnvFilter::new(directives.join(","))
+    })
 }
@@ -1,8 +1,16 @@
 mod log_config;
 mod log_error;
 mod log_utils;
+mod otlp_config;
+#[cfg(feature = "otlp")]
+mod otlp_utils;
+mod size_rolling_writer;
 
 // 重新导出结构体，简化外部引用
 pub use log_config::*;
 pub use log_error::*;
 pub use log_utils::*;
+pub use otlp_config::*;
+#[cfg(feature = "otlp")]
+pub(crate) use otlp_utils::*;
+pub(crate) use size_rolling_writer::*;
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// # OpenTelemetry(OTLP)导出配置
+///
+/// 配置后将 `#[instrument]` 等产生的span通过OTLP(HTTP/protobuf)导出到采集器，
+/// 需要启用 `otlp` 特性，修改此配置需要重启应用才能生效
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct OtlpConfig {
+    /// 采集器接收span的地址(默认http://localhost:4318/v1/traces)
+    #[serde(default = "endpoint_default")]
+    pub endpoint: String,
+    /// 服务名称(不设置默认为执行文件名，不带后缀)
+    #[serde(default)]
+    pub service_name: Option<String>,
+    /// 采样比例(默认1.0，即全部采样)，上游已经决定采样的请求沿用上游的决定
+    #[serde(default = "sampling_ratio_default")]
+    pub sampling_ratio: f64,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: endpoint_default(),
+            service_name: None,
+            sampling_ratio: sampling_ratio_default(),
+        }
+    }
+}
+
+fn endpoint_default() -> String {
+    "http://localhost:4318/v1/traces".to_string()
+}
+
+fn sampling_ratio_default() -> f64 {
+    1.0
+}
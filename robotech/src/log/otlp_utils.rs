@@ -0,0 +1,55 @@
+use crate::log::{LogError, OtlpConfig};
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider, Tracer};
+use std::sync::RwLock;
+
+/// 导出span的提供者(退出时需要调用shutdown，将缓冲的span导出)
+static OTLP_TRACER_PROVIDER: RwLock<Option<SdkTracerProvider>> = RwLock::new(None);
+
+/// # 初始化OTLP导出
+///
+/// 创建批量导出span的提供者，并设置为全局的提供者，返回用于创建 `tracing-opentelemetry` 层的tracer
+pub(crate) fn init_otlp_tracer(
+    otlp_config: &OtlpConfig,
+    default_service_name: &str,
+) -> Result<Tracer, LogError> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(otlp_config.endpoint.as_str())
+        .build()
+        .map_err(|e| LogError::InitOtlp(e.to_string()))?;
+    let service_name = otlp_config
+        .service_name
+        .clone()
+        .unwrap_or_else(|| default_service_name.to_string());
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            otlp_config.sampling_ratio,
+        ))))
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .build();
+    let tracer = provider.tracer("robotech");
+    opentelemetry::global::set_tracer_provider(provider.clone());
+
+    let mut write_lock = OTLP_TRACER_PROVIDER
+        .write()
+        .map_err(|_| LogError::SetLogGuard())?;
+    *write_lock = Some(provider);
+    Ok(tracer)
+}
+
+/// 关闭OTLP导出，导出缓冲中的span
+pub(crate) fn shutdown_otlp() {
+    let provider = OTLP_TRACER_PROVIDER
+        .write()
+        .ok()
+        .and_then(|mut write_lock| write_lock.take());
+    if let Some(provider) = provider
+        && let Err(e) = provider.shutdown()
+    {
+        eprintln!("关闭OTLP导出失败: {e}");
+    }
+}
@@ -0,0 +1,196 @@
+use chrono::{Datelike, Utc};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tracing_appender::rolling::Rotation;
+
+/// # 按大小及时间滚动的日志文件写入器
+///
+/// 在时间滚动的基础上，文件超过 `max_file_size` 时也会滚动到新文件，
+/// 文件名为 `{prefix}.{时间段}.{序号}.{suffix}`(不按时间滚动时为 `{prefix}.{序号}.{suffix}`)，
+/// 时间段与 `RollingFileAppender` 一样按UTC计算。
+/// 每次滚动后，按修改时间删除超出 `max_files` 的最旧的日志文件
+pub(crate) struct SizeRollingWriter {
+    log_dir: PathBuf,
+    prefix: String,
+    suffix: String,
+    rotation: Rotation,
+    max_file_size: u64,
+    max_files: Option<usize>,
+    /// 当前文件所属的时间段
+    period: Option<String>,
+    /// 当前文件在时间段内的序号
+    index: u32,
+    /// 当前文件
+    file: File,
+    /// 当前文件的大小
+    size: u64,
+}
+
+impl SizeRollingWriter {
+    pub(crate) fn new(
+        log_dir: &Path,
+        prefix: impl Into<String>,
+        suffix: impl Into<String>,
+        rotation: Rotation,
+        max_file_size: u64,
+        max_files: Option<usize>,
+    ) -> io::Result<Self> {
+        fs::create_dir_all(log_dir)?;
+        let prefix = prefix.into();
+        let suffix = suffix.into();
+        let period = period_of(&rotation);
+        // 重启时接着写入当前时间段中序号最大的文件
+        let index = last_index(log_dir, &prefix, &suffix, period.as_deref())?;
+        let path = log_dir.join(file_name(&prefix, &suffix, period.as_deref(), index));
+        let file = open_file(&path)?;
+        let size = file.metadata()?.len();
+        let mut writer = Self {
+            log_dir: log_dir.to_path_buf(),
+            prefix,
+            suffix,
+            rotation,
+            max_file_size,
+            max_files,
+            period,
+            index,
+            file,
+            size,
+        };
+        if writer.size >= writer.max_file_size {
+            writer.roll(writer.period.clone(), writer.index + 1)?;
+        } else {
+            writer.prune();
+        }
+        Ok(writer)
+    }
+
+    /// 滚动到新文件
+    fn roll(&mut self, period: Option<String>, index: u32) -> io::Result<()> {
+        self.file.flush()?;
+        let path = self.log_dir.join(file_name(
+            &self.prefix,
+            &self.suffix,
+            period.as_deref(),
+            index,
+        ));
+        self.file = open_file(&path)?;
+        self.size = self.file.metadata()?.len();
+        self.period = period;
+        self.index = index;
+        self.prune();
+        Ok(())
+    }
+
+    /// 删除超出保留数的最旧的日志文件
+    fn prune(&self) {
+        let Some(max_files) = self.max_files else {
+            return;
+        };
+        let entries = match fs::read_dir(&self.log_dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                // 不能再写日志，只能输出到标准错误
+                eprintln!("读取日志目录<{}>失败: {e}", self.log_dir.display());
+                return;
+            }
+        };
+        let prefix = format!("{}.", self.prefix);
+        let mut files: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                let file_name = entry.file_name();
+                let file_name = file_name.to_string_lossy();
+                file_name.starts_with(prefix.as_str())
+                    && (file_name.ends_with(".json") || file_name.ends_with(".log"))
+            })
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                metadata
+                    .is_file()
+                    .then_some((metadata.modified().ok()?, entry.path()))
+            })
+            .collect();
+        if files.len() <= max_files {
+            return;
+        }
+        files.sort();
+        for (_, path) in &files[..files.len() - max_files] {
+            if let Err(e) = fs::remove_file(path) {
+                eprintln!("删除旧的日志文件<{}>失败: {e}", path.display());
+            }
+        }
+    }
+}
+
+impl Write for SizeRollingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let period = period_of(&self.rotation);
+        if period != self.period {
+            self.roll(period, 0)?;
+        } else if self.size > 0 && self.size + buf.len() as u64 > self.max_file_size {
+            self.roll(period, self.index + 1)?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// 当前时间所在的时间段(不按时间滚动时返回None)
+fn period_of(rotation: &Rotation) -> Option<String> {
+    let now = Utc::now();
+    let time_format = if *rotation == Rotation::NEVER {
+        return None;
+    } else if *rotation == Rotation::MINUTELY {
+        "%Y-%m-%d-%H-%M"
+    } else if *rotation == Rotation::HOURLY {
+        "%Y-%m-%d-%H"
+    } else if *rotation == Rotation::DAILY {
+        "%Y-%m-%d"
+    } else {
+        // 按周滚动，时间段为本周的第一天(周日)
+        let week_start =
+            now.date_naive() - chrono::Days::new(now.weekday().num_days_from_sunday() as u64);
+        return Some(week_start.format("%Y-%m-%d").to_string());
+    };
+    Some(now.format(time_format).to_string())
+}
+
+fn file_name(prefix: &str, suffix: &str, period: Option<&str>, index: u32) -> String {
+    match period {
+        Some(period) => format!("{prefix}.{period}.{index}.{suffix}"),
+        None => format!("{prefix}.{index}.{suffix}"),
+    }
+}
+
+/// 时间段中已有文件的最大序号(没有文件时为0)
+fn last_index(log_dir: &Path, prefix: &str, suffix: &str, period: Option<&str>) -> io::Result<u32> {
+    let file_name_prefix = match period {
+        Some(period) => format!("{prefix}.{period}."),
+        None => format!("{prefix}."),
+    };
+    let file_name_suffix = format!(".{suffix}");
+    let index = fs::read_dir(log_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .strip_prefix(file_name_prefix.as_str())?
+                .strip_suffix(file_name_suffix.as_str())?
+                .parse::<u32>()
+                .ok()
+        })
+        .max()
+        .unwrap_or(0);
+    Ok(index)
+}
+
+fn open_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
@@ -0,0 +1,30 @@
+//! # 预导入模块
+//!
+//! `use robotech::prelude::*;` 即可导入编写CRUD服务常用的类型、函数及宏(按启用的特性导出)：
+//! - 响应: `Ro`, `RoResult`, `Page`, `PageRx`
+//! - 错误: `DaoError`, `SvcError`, `CtrlError`
+//! - 数据库: `get_db_conn`, `begin_transaction`, `commit_transaction`, `rollback_transaction`
+//! - Web: `ClientIp`, `RoFile`, `RoResponse`
+//! - 宏: `dao`, `svc`, `ctrl`, `router`, `api_doc`, `crud_dto`, `vo`, `db_unwrap`, `log_call`, `QueryFilter`
+//!
+//! `dao`/`svc`/`db_unwrap`/`log_call` 宏生成的代码使用 `::robotech::...` 的完整路径，
+//! 其依赖的 sea-orm/idworker/validator/tracing/linkme/wheel-rs 也通过robotech引用，不依赖调用处的导入；
+//! 生成的代码仍然会引用项目中约定的 `crate::model`/`crate::dto`/`crate::vo`/`crate::dao` 模块。
+//! `crud_dto`/`vo`/`ctrl` 等宏生成的代码中使用了 serde/utoipa/validator 等crate的派生宏，
+//! 这些派生宏要求项目直接依赖相应的crate
+pub use crate::ro::{Page, Ro, RoResult};
+pub use crate::rx::PageRx;
+
+#[cfg(feature = "db")]
+pub use crate::dao::DaoError;
+#[cfg(feature = "db")]
+pub use crate::db::{begin_transaction, commit_transaction, get_db_conn, rollback_transaction};
+#[cfg(feature = "app")]
+pub use crate::svc::SvcError;
+#[cfg(feature = "web")]
+pub use crate::web::{ClientIp, CtrlError, RoFile, RoResponse};
+
+#[cfg(feature = "macros")]
+pub use crate::macros::{
+    QueryFilter, api_doc, crud_dto, ctrl, dao, db_unwrap, log_call, router, svc, vo,
+};
@@ -0,0 +1,77 @@
+//! # i64按字符串序列化的模块
+//!
+//! 雪花算法等生成的ID超出了JavaScript的安全整数范围(2^53 - 1)，
+//! 按JSON数字返回时浏览器会静默丢失精度，所以ID类型的字段应序列化为字符串。
+//!
+//! `vo` 宏会自动为 `u64` 类型的字段添加字符串序列化，`i64` 类型的字段需要手动添加:
+//! ```
+//! #[serde(with = "robotech::ro::i64_as_string")]
+//! pub id: i64,
+//! #[serde(with = "robotech::ro::i64_option_as_string", default)]
+//! pub parent_id: Option<i64>,
+//! ```
+//! 反序列化时同时兼容字符串及数字，所以 `ApiClient` 可以直接将其解析回来
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum I64OrString {
+    Number(i64),
+    Text(String),
+}
+
+impl I64OrString {
+    fn into_i64<E: Error>(self) -> Result<i64, E> {
+        match self {
+            I64OrString::Number(value) => Ok(value),
+            I64OrString::Text(text) => text
+                .parse::<i64>()
+                .map_err(|e| E::custom(format!("无法解析为i64({text}): {e}"))),
+        }
+    }
+}
+
+/// # i64按字符串序列化
+pub mod i64_as_string {
+    use super::*;
+
+    pub fn serialize<S>(value: &i64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<i64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        I64OrString::deserialize(deserializer)?.into_i64()
+    }
+}
+
+/// # Option<i64>按字符串序列化
+pub mod i64_option_as_string {
+    use super::*;
+
+    pub fn serialize<S>(value: &Option<i64>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<I64OrString>::deserialize(deserializer)?
+            .map(I64OrString::into_i64)
+            .transpose()
+    }
+}
@@ -1,9 +1,15 @@
+mod i64_serde;
 mod ro;
 mod ro_code;
+mod ro_page;
 mod ro_result;
+#[cfg(feature = "ro-rfc3339-timestamp")]
+pub mod ro_timestamp_serde;
 pub mod rx;
 
 // 重新导出结构体，简化外部引用
+pub use i64_serde::*;
 pub use ro::*;
 pub use ro_code::*;
+pub use ro_page::*;
 pub use ro_result::*;
@@ -8,6 +8,7 @@ use serde_with::skip_serializing_none;
 use std::fmt::Debug;
 use typed_builder::TypedBuilder;
 use utoipa::ToSchema;
+#[cfg(not(feature = "ro-rfc3339-timestamp"))]
 use wheel_rs::serde::u64_serde;
 
 /// # 统一API响应结构体
@@ -27,8 +28,14 @@ pub struct Ro<E> {
     pub msg: String,
     /// 时间戳，记录响应生成的时间（毫秒）
     /// 这里默认值为当前时间戳，懒得考虑系统时间错误的问题
+    /// 启用 `ro-rfc3339-timestamp` 特性后序列化为RFC3339字符串(会改变报文格式)
     #[builder(default = Utc::now().timestamp_millis() as u64)]
-    #[serde(with = "u64_serde")]
+    #[cfg_attr(not(feature = "ro-rfc3339-timestamp"), serde(with = "u64_serde"))]
+    #[cfg_attr(
+        feature = "ro-rfc3339-timestamp",
+        serde(with = "crate::ro::ro_timestamp_serde"),
+        schema(value_type = String, format = DateTime)
+    )]
     pub timestamp: u64,
     /// 额外数据，可选的响应数据内容
     #[builder(default, setter(strip_option))]
@@ -39,6 +46,10 @@ pub struct Ro<E> {
     /// 编码，可选的业务编码
     #[builder(default, setter(strip_option))]
     pub code: Option<String>,
+    /// 警告列表，部分成功时用于说明未成功的部分(为空时不序列化)
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 impl<E> Ro<E> {
@@ -69,6 +80,24 @@ impl<E> Ro<E> {
         Self::builder().result(RoResult::Success).msg(msg).build()
     }
 
+    /// # 创建一个带警告列表的成功的响应对象
+    ///
+    /// 用于表达部分成功的结果，如批量导入100条，成功95条，跳过5条
+    ///
+    /// ## 参数
+    /// * `msg` - 成功消息
+    /// * `warnings` - 警告列表
+    ///
+    /// ## 返回值
+    /// 返回一个结果为Success且带有警告列表的Ro实例
+    pub fn success_with_warnings(msg: String, warnings: Vec<String>) -> Self {
+        Self::builder()
+            .result(RoResult::Success)
+            .msg(msg)
+            .warnings(warnings)
+            .build()
+    }
+
     /// # 创建一个非法参数的响应对象
     ///
     /// ## 参数
@@ -105,3 +134,18 @@ impl<E> Ro<E> {
         Self::builder().result(RoResult::Fail).msg(msg).build()
     }
 }
+
+impl Ro<()> {
+    /// # 创建一个无内容的成功响应对象
+    ///
+    /// 用于不需要返回数据的命令型接口，配合 `RoResponse::no_content` 使用时返回 `204 No Content`
+    ///
+    /// ## 参数
+    /// * `msg` - 成功消息
+    ///
+    /// ## 返回值
+    /// 返回一个结果为Success且没有extra的Ro实例
+    pub fn no_content(msg: String) -> Self {
+        Self::success(msg)
+    }
+}
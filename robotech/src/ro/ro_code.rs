@@ -4,3 +4,35 @@ pub const RO_CODE_WARNING_DUPLICATE_KEY: &str = "RTW00001";
 pub const RO_CODE_WARNING_INSERT_VIOLATE_FK: &str = "RTW00002";
 /// # 警告: 删除操作违反了约束条件
 pub const RO_CODE_WARNING_DELETE_VIOLATE_FK: &str = "RTW00003";
+/// # 警告: 找不到数据
+pub const RO_CODE_WARNING_NOT_FOUND: &str = "RTW00004";
+/// # 警告: 未更新数据(记录可能不存在)
+pub const RO_CODE_WARNING_RECORD_NOT_UPDATED: &str = "RTW00005";
+/// # 警告: 违反了外键约束(没有注册或不符合命名的外键)
+pub const RO_CODE_WARNING_VIOLATE_FK: &str = "RTW00006";
+/// # 参数错误: 参数校验不通过
+pub const RO_CODE_ILLEGAL_ARGUMENT_VALIDATION: &str = "RTI00001";
+/// # 参数错误: 解析multipart失败
+pub const RO_CODE_ILLEGAL_ARGUMENT_MULTIPART: &str = "RTI00002";
+/// # 参数错误: 违反了非空约束
+pub const RO_CODE_ILLEGAL_ARGUMENT_NOT_NULL: &str = "RTI00003";
+/// # 错误: 运行时错误
+pub const RO_CODE_ERROR_RUNTIME: &str = "RTE00001";
+/// # 错误: 环境变量错误
+pub const RO_CODE_ERROR_ENV: &str = "RTE00002";
+/// # 错误: 系统时钟错误
+pub const RO_CODE_ERROR_SYSTEM_TIME: &str = "RTE00003";
+/// # 错误: ID生成错误
+pub const RO_CODE_ERROR_ID_WORKER: &str = "RTE00004";
+/// # 错误: APP错误
+pub const RO_CODE_ERROR_APP: &str = "RTE00005";
+/// # 错误: IO错误
+pub const RO_CODE_ERROR_IO: &str = "RTE00006";
+/// # 错误: 数据访问层错误
+pub const RO_CODE_ERROR_DAO: &str = "RTE00007";
+/// # 错误: 数据库错误
+pub const RO_CODE_ERROR_DB: &str = "RTE00008";
+/// # 错误: 数据库连接错误
+pub const RO_CODE_ERROR_DB_CONN: &str = "RTE00009";
+/// # 错误: 调用API错误
+pub const RO_CODE_ERROR_API_CLIENT: &str = "RTE00010";
@@ -0,0 +1,55 @@
+use crate::ro::Ro;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// # 分页数据
+///
+/// 作为 `Ro` 的额外数据返回列表接口的当前页记录及分页信息，
+/// 记录列表为空时也会序列化(为 `[]`)
+#[derive(ToSchema, Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T>
+where
+    T: utoipa::ToSchema + serde::Serialize,
+{
+    /// 当前页的记录列表
+    pub items: Vec<T>,
+    /// 记录总数
+    pub total: u64,
+    /// 当前页码(从1开始)
+    pub page: u64,
+    /// 每页大小
+    pub page_size: u64,
+}
+
+impl<T> Ro<Page<T>>
+where
+    T: utoipa::ToSchema + serde::Serialize,
+{
+    /// # 创建一个分页数据的成功响应对象
+    ///
+    /// ## 参数
+    /// * `msg` - 成功消息
+    /// * `items` - 当前页的记录列表
+    /// * `total` - 记录总数
+    /// * `page` - 当前页码
+    /// * `page_size` - 每页大小
+    ///
+    /// ## 返回值
+    /// 返回一个结果为Success且额外数据为分页数据的Ro实例
+    ///
+    /// ## 使用示例
+    /// ```
+    /// let (models, total) = UserDao::list(page, page_size, db_conn.as_ref()).await?;
+    /// let items = models.into_iter().map(UserVo::from).collect();
+    /// Ok(Json(Ro::page("查询用户成功".to_string(), items, total, page, page_size)))
+    /// ```
+    pub fn page(msg: String, items: Vec<T>, total: u64, page: u64, page_size: u64) -> Self {
+        Self::success(msg).extra(Some(Page {
+            items,
+            total,
+            page,
+            page_size,
+        }))
+    }
+}
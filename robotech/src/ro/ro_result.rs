@@ -33,39 +33,34 @@ struct EnumMetadata {
     note: &'static str,
 }
 
-/// # 枚举元数据常量数组
-///
-/// 按照枚举值在定义中的顺序存储每个枚举值的元数据信息
-const ENUM_METADATA: [EnumMetadata; 4] = [
-    EnumMetadata {
-        id: 1,
-        name: "成功",
-        note: "运行正常",
-    },
-    EnumMetadata {
-        id: -1,
-        name: "参数错误",
-        note: "传递的参数有问题",
-    },
-    EnumMetadata {
-        id: -2,
-        name: "警告",
-        note: "用户方面的错误",
-    },
-    EnumMetadata {
-        id: -3,
-        name: "失败",
-        note: "系统方面的异常",
-    },
-];
-
 impl RoResult {
     /// # 获取枚举值对应的元数据
     ///
     /// ## 返回值
     /// 返回指向对应元数据的引用
-    fn get_metadata(&self) -> &EnumMetadata {
-        &ENUM_METADATA[*self as usize]
+    fn get_metadata(&self) -> &'static EnumMetadata {
+        match self {
+            RoResult::Success => &EnumMetadata {
+                id: 1,
+                name: "成功",
+                note: "运行正常",
+            },
+            RoResult::IllegalArgument => &EnumMetadata {
+                id: -1,
+                name: "参数错误",
+                note: "传递的参数有问题",
+            },
+            RoResult::Warn => &EnumMetadata {
+                id: -2,
+                name: "警告",
+                note: "用户方面的错误",
+            },
+            RoResult::Fail => &EnumMetadata {
+                id: -3,
+                name: "失败",
+                note: "系统方面的异常",
+            },
+        }
     }
 
     /// # 根据ID获取对应的枚举对象
@@ -76,10 +71,13 @@ impl RoResult {
     /// ## 返回值
     /// 如果找到对应的枚举值，返回Some(RoResult)，否则返回None
     pub fn new(id: i8) -> Option<Self> {
-        ENUM_METADATA
-            .iter()
-            .position(|metadata| metadata.id == id)
-            .map(|index| unsafe { std::mem::transmute(index as u8) })
+        match id {
+            1 => Some(RoResult::Success),
+            -1 => Some(RoResult::IllegalArgument),
+            -2 => Some(RoResult::Warn),
+            -3 => Some(RoResult::Fail),
+            _ => None,
+        }
     }
 }
 
@@ -134,3 +132,39 @@ impl<'de> Deserialize<'de> for RoResult {
             .ok_or_else(|| serde::de::Error::custom(format!("Unknown RoResult id: {}", id)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [RoResult; 4] = [
+        RoResult::Success,
+        RoResult::IllegalArgument,
+        RoResult::Warn,
+        RoResult::Fail,
+    ];
+
+    #[test]
+    fn new_round_trips_every_variant() {
+        for ro_result in ALL {
+            assert_eq!(RoResult::new(ro_result.get_metadata().id), Some(ro_result));
+        }
+    }
+
+    #[test]
+    fn new_returns_none_for_unknown_id() {
+        assert_eq!(RoResult::new(0), None);
+        assert_eq!(RoResult::new(2), None);
+        assert_eq!(RoResult::new(-4), None);
+    }
+
+    #[test]
+    fn serde_round_trips_every_variant() {
+        for ro_result in ALL {
+            let json = serde_json::to_string(&ro_result).unwrap();
+            assert_eq!(json, ro_result.get_metadata().id.to_string());
+            assert_eq!(serde_json::from_str::<RoResult>(&json).unwrap(), ro_result);
+        }
+        assert!(serde_json::from_str::<RoResult>("0").is_err());
+    }
+}
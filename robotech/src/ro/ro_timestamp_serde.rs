@@ -0,0 +1,47 @@
+//! # Ro时间戳的RFC3339序列化模块
+//!
+//! 启用 `ro-rfc3339-timestamp` 特性后，`Ro.timestamp` 将序列化为RFC3339格式的字符串，
+//! 如 `2024-01-01T12:00:00.000Z`，而不是毫秒数
+//!
+//! 注意: 启用后响应的报文格式会发生变化，调用方需要同步调整解析方式
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+/// # 将毫秒时间戳序列化为RFC3339字符串
+pub fn serialize<S>(timestamp: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let date_time = DateTime::<Utc>::from_timestamp_millis(*timestamp as i64)
+        .ok_or_else(|| serde::ser::Error::custom(format!("时间戳超出范围: {timestamp}")))?;
+    serializer.serialize_str(&date_time.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+/// # 反序列化时间戳
+///
+/// 兼容RFC3339字符串、毫秒数字及毫秒数字字符串
+pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Timestamp {
+        Millis(u64),
+        Text(String),
+    }
+
+    match Timestamp::deserialize(deserializer)? {
+        Timestamp::Millis(millis) => Ok(millis),
+        Timestamp::Text(text) => {
+            if let Ok(millis) = text.parse::<u64>() {
+                return Ok(millis);
+            }
+            DateTime::parse_from_rfc3339(&text)
+                .map(|date_time| date_time.timestamp_millis() as u64)
+                .map_err(|e| D::Error::custom(format!("无法解析时间戳({text}): {e}")))
+        }
+    }
+}
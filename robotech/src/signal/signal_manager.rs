@@ -35,8 +35,26 @@ impl SignalManager {
     pub fn watch_signal(
         &mut self,
     ) -> Result<broadcast::Receiver<nix::sys::signal::Signal>, SignalManagerError> {
-        self.pid_file_guard = Some(PidFileGuard::new(self.pid_file_path.clone())?);
-        Ok(watch_signal())
+        self.write_pid_file()?;
+        Ok(self.listen_signal())
+    }
+
+    /// # 只监听信号，不写入PID文件
+    ///
+    /// 用于启动期间监听退出信号，启动完成后再调用 `write_pid_file` 写入PID文件，
+    /// 这样启动期间收到退出信号时不会留下PID文件
+    pub fn listen_signal(&self) -> broadcast::Receiver<nix::sys::signal::Signal> {
+        watch_signal()
+    }
+
+    /// # 写入PID文件
+    ///
+    /// PID文件在 `SignalManager` 被释放时删除
+    pub fn write_pid_file(&mut self) -> Result<(), SignalManagerError> {
+        if self.pid_file_guard.is_none() {
+            self.pid_file_guard = Some(PidFileGuard::new(self.pid_file_path.clone())?);
+        }
+        Ok(())
     }
 
     /// # 解析并处理信号参数
@@ -57,6 +75,7 @@ impl SignalManager {
     /// * `restart` - 不处理，直接返回(restart指令在本函数中不处理，后续在需要时再单独发送信号停止旧程序)
     /// * `stop`/`s` - 发送`SIGTERM`信号(kill -15)，用于终止程序，优雅退出
     /// * `kill`/`k` - 发送`SIGKILL`信号(kill -9)，用于强制终止程序(顺带删除PID文件)
+    /// * `status` - 查询程序是否正在运行，输出状态后退出(运行中退出码为0，未运行为3，无法检查时为4，遵循LSB规范)，不写入也不删除PID文件
     ///
     /// ## 使用示例
     ///
@@ -72,6 +91,9 @@ impl SignalManager {
         signal_instruction: String,
         pid_file_path: &PathBuf,
     ) -> Result<Option<u32>, SignalManagerError> {
+        if signal_instruction == "status" {
+            Self::exit_with_status(pid_file_path);
+        }
         let old_pid = read_pid(pid_file_path)?;
         if signal_instruction == "restart" {
             // 不处理，直接返回(restart指令在本函数中不处理，后续在需要时再单独发送信号停止旧程序)
@@ -106,4 +128,34 @@ impl SignalManager {
             };
         }
     }
+
+    /// # 输出程序的运行状态并退出
+    ///
+    /// 按LSB规范，运行中退出码为0，未运行为3，无法读取PID文件或检查进程时为4(状态未知)，
+    /// 只查询状态，不写入也不删除PID文件
+    fn exit_with_status(pid_file_path: &PathBuf) -> ! {
+        let status = read_pid(pid_file_path)
+            .map_err(|e| e.to_string())
+            .and_then(|old_pid| match old_pid {
+                Some(old_pid) => check_process(old_pid)
+                    .map(|running| running.then_some(old_pid))
+                    .map_err(|e| e.to_string()),
+                None => Ok(None),
+            });
+        match status {
+            Ok(Some(old_pid)) => {
+                println!("running (pid {old_pid})");
+                process::exit(0);
+            }
+            Ok(None) => {
+                println!("not running");
+                process::exit(3);
+            }
+            Err(e) => {
+                error!("Failed to check status: {e}");
+                println!("unknown");
+                process::exit(4);
+            }
+        }
+    }
 }
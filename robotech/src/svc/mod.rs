@@ -1,2 +1,4 @@
+mod svc_cache;
 mod svc_error;
+pub use svc_cache::*;
 pub use svc_error::*;
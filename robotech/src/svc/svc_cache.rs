@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// # Svc的进程内缓存
+///
+/// 以记录ID为键、带过期时间且有容量上限的内存缓存，由 `#[svc(cache_get_by_id = "60s")]` 生成的
+/// `get_by_id` 使用，并在 `modify`/`del_by_id` 等写操作后按ID失效
+///
+/// 缓存已满时，先清理已过期的条目，仍然满时淘汰最早写入的条目
+pub struct SvcCache<V: Clone> {
+    /// 过期时间
+    ttl: Duration,
+    /// 容量上限
+    capacity: usize,
+    /// 缓存条目(写入时间, 值)
+    entries: Mutex<HashMap<u64, (Instant, V)>>,
+}
+
+impl<V: Clone> SvcCache<V> {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 获取未过期的缓存值
+    pub fn get(&self, id: u64) -> Option<V> {
+        let mut entries = self.entries.lock().ok()?;
+        match entries.get(&id) {
+            Some((inserted_at, value)) if inserted_at.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(&id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// 写入缓存
+    pub fn insert(&self, id: u64, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if entries.len() >= self.capacity && !entries.contains_key(&id) {
+            // 先清理已过期的条目
+            let ttl = self.ttl;
+            entries.retain(|_, (inserted_at, _)| inserted_at.elapsed() < ttl);
+            // 仍然满时淘汰最早写入的条目
+            if entries.len() >= self.capacity
+                && let Some(oldest_id) = entries
+                    .iter()
+                    .min_by_key(|(_, (inserted_at, _))| *inserted_at)
+                    .map(|(id, _)| *id)
+            {
+                entries.remove(&oldest_id);
+            }
+        }
+        entries.insert(id, (Instant::now(), value));
+    }
+
+    /// 使指定ID的缓存失效
+    pub fn invalidate(&self, id: u64) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.remove(&id);
+        }
+    }
+
+    /// 使所有缓存失效
+    pub fn invalidate_all(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
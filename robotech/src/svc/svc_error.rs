@@ -6,9 +6,12 @@ use crate::dao::DaoError;
 #[cfg(feature = "db")]
 use crate::db::DbError;
 use crate::env::EnvError;
+use crate::ro::*;
 #[cfg(feature = "web")]
 use axum::extract::multipart::MultipartError;
 use idworker::IdWorkerError;
+#[cfg(feature = "db")]
+use sea_orm::DbErr;
 use std::time::SystemTimeError;
 
 /// # 自定义服务层的错误枚举
@@ -19,6 +22,7 @@ use std::time::SystemTimeError;
 ///
 /// ## 错误类型说明
 /// - `NotFound`: 表示请求的数据未找到，通常用于查询操作
+/// - `Business`: 表示违反了业务规则(如账户已冻结)，是预期内的、需要告知客户端的失败
 /// - `DuplicateKey`: 表示违反了唯一性约束，如重复的用户名或邮箱
 /// - `IoError`: 表示输入输出相关的错误，如文件读写失败
 /// - `DatabaseError`: 表示底层数据库操作发生的错误
@@ -40,6 +44,13 @@ pub enum SvcError {
     Validations(#[from] validator::ValidationErrors),
     #[error("找不到数据: {0}")]
     NotFound(String),
+    #[error("违反业务规则({code}): {msg}")]
+    Business {
+        /// 错误码
+        code: String,
+        /// 错误信息(返回给客户端)
+        msg: String,
+    },
     #[error("IO错误: {0}")]
     Io(#[from] std::io::Error),
     #[cfg(feature = "web")]
@@ -55,3 +66,46 @@ pub enum SvcError {
     #[error("API客户端错误, {0}")]
     ApiClient(#[from] ApiClientError),
 }
+
+impl SvcError {
+    /// # 获取错误码
+    ///
+    /// 每种错误对应一个稳定的错误码(见 `ro_code` 中的常量)，会被设置到返回的 `Ro.code` 中，
+    /// 客户端可以据此做相应的处理，而不用去匹配本地化的错误信息。
+    /// 违反业务规则的错误使用其自带的错误码
+    pub fn code(&self) -> &str {
+        match self {
+            SvcError::Runtime(_) => RO_CODE_ERROR_RUNTIME,
+            SvcError::GetAppEnv(_) => RO_CODE_ERROR_ENV,
+            SvcError::SystemTime(_) => RO_CODE_ERROR_SYSTEM_TIME,
+            SvcError::IdWorker(_) => RO_CODE_ERROR_ID_WORKER,
+            SvcError::App(_) => RO_CODE_ERROR_APP,
+            SvcError::Validation(_) | SvcError::Validations(_) => {
+                RO_CODE_ILLEGAL_ARGUMENT_VALIDATION
+            }
+            SvcError::NotFound(_) => RO_CODE_WARNING_NOT_FOUND,
+            SvcError::Business { code, .. } => code,
+            SvcError::Io(_) => RO_CODE_ERROR_IO,
+            #[cfg(feature = "web")]
+            SvcError::MultipartError(_) => RO_CODE_ILLEGAL_ARGUMENT_MULTIPART,
+            #[cfg(feature = "db")]
+            SvcError::Dao(error) => match error {
+                DaoError::NotFound(_) => RO_CODE_WARNING_NOT_FOUND,
+                DaoError::DuplicateKey(_, _) => RO_CODE_WARNING_DUPLICATE_KEY,
+                DaoError::InsertViolateFk(_) => RO_CODE_WARNING_INSERT_VIOLATE_FK,
+                DaoError::DeleteViolateFk(_) => RO_CODE_WARNING_DELETE_VIOLATE_FK,
+                DaoError::ForeignKeyViolation(_) => RO_CODE_WARNING_VIOLATE_FK,
+                DaoError::NotNullViolation(_) => RO_CODE_ILLEGAL_ARGUMENT_NOT_NULL,
+                DaoError::Db(DbErr::RecordNotUpdated) => RO_CODE_WARNING_RECORD_NOT_UPDATED,
+                DaoError::Db(_) => RO_CODE_ERROR_DB,
+                _ => RO_CODE_ERROR_DAO,
+            },
+            #[cfg(feature = "db")]
+            SvcError::DbConn(DbError::Transaction(_)) => RO_CODE_ERROR_DB,
+            #[cfg(feature = "db")]
+            SvcError::DbConn(_) => RO_CODE_ERROR_DB_CONN,
+            #[cfg(feature = "api-client")]
+            SvcError::ApiClient(_) => RO_CODE_ERROR_API_CLIENT,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// # 导出当前生效配置的管理端点的配置
+///
+/// 开启后可通过 `GET {uri}` 查看运行中实例实际使用的配置(热加载后的)，敏感配置项会被遮盖
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConfigDumpConfig {
+    /// 是否开启(默认不开启)
+    #[serde(default)]
+    pub enabled: bool,
+    /// 端点的uri(默认/admin/config)
+    #[serde(default = "uri_default")]
+    pub uri: String,
+    /// 允许访问的管理员用户ID列表
+    ///
+    /// 请求头中的用户ID必须在此列表中，开启时不能为空(用户ID请求头可由客户端伪造，不能只检查是否带有用户ID)
+    #[serde(default)]
+    pub admin_user_ids: Vec<u64>,
+}
+
+impl Default for ConfigDumpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            uri: uri_default(),
+            admin_user_ids: vec![],
+        }
+    }
+}
+
+fn uri_default() -> String {
+    "/admin/config".to_string()
+}
@@ -0,0 +1,42 @@
+use crate::cfg::dump_live_cfg;
+use crate::ro::Ro;
+use crate::web::ctrl_utils::get_current_user_id;
+use axum::Json;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+use tracing::warn;
+
+#[derive(Clone)]
+pub(crate) struct ConfigDumpState {
+    pub(crate) admin_user_ids: Arc<Vec<u64>>,
+}
+
+/// # 导出当前生效配置的端点
+///
+/// 要求请求头中带有用户ID，且必须是配置的管理员用户ID之一
+pub(crate) async fn config_dump(
+    State(state): State<ConfigDumpState>,
+    headers: HeaderMap,
+) -> Response {
+    let user_id = match get_current_user_id(&headers) {
+        Ok(user_id) => user_id,
+        Err(e) => {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(Ro::<()>::illegal_argument(e.to_string())),
+            )
+                .into_response();
+        }
+    };
+    if !state.admin_user_ids.contains(&user_id) {
+        warn!("用户<{user_id}>无权查看当前配置");
+        return (
+            StatusCode::FORBIDDEN,
+            Json(Ro::<()>::warn("无权查看当前配置".to_string())),
+        )
+            .into_response();
+    }
+    Json(Ro::success("查询成功".to_string()).extra(Some(dump_live_cfg()))).into_response()
+}
@@ -0,0 +1,5 @@
+mod config_dump_config;
+mod config_dump_utils;
+
+pub use config_dump_config::*;
+pub(crate) use config_dump_utils::*;
@@ -0,0 +1,35 @@
+use crate::web::urns_serialize;
+use serde::{Deserialize, Serialize};
+use wheel_rs::urn_utils::Urn;
+
+/// # 请求体内容类型校验配置
+///
+/// 对有请求体的POST/PUT/PATCH请求校验 `Content-Type`，不是 `application/json` 时直接返回415，
+/// 避免请求到达处理函数后才因反序列化失败返回难以理解的错误
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct ContentTypeConfig {
+    /// 是否启用(不设置此项默认为true)
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+    /// 允许 `multipart/form-data` 的URN列表(如上传文件的接口)
+    #[serde(default, serialize_with = "urns_serialize")]
+    pub multipart_urns: Vec<Urn>,
+    /// 不校验内容类型的URN列表
+    #[serde(default, serialize_with = "urns_serialize")]
+    pub exempt_urns: Vec<Urn>,
+}
+
+impl Default for ContentTypeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: enabled_default(),
+            multipart_urns: vec![],
+            exempt_urns: vec![],
+        }
+    }
+}
+
+fn enabled_default() -> bool {
+    true
+}
@@ -0,0 +1,3 @@
+mod content_type_config;
+
+pub use content_type_config::*;
@@ -17,6 +17,7 @@ pub struct CorsConfig {
     /// ## 注意事项
     /// - 源 = 协议 + 域名 + 端口，必须精确匹配，包括协议、域名、端口
     /// - http://localhost:3000 和 http://localhost:3001 是不同的源
+    /// - 域名中可以使用通配符*匹配子域名，如 https://*.example.com，协议及端口仍然需要精确匹配
     /// - 默认为*，不建议在生产环境使用 *（允许所有源），这会有安全风险
     /// ## 场景示例
     /// 前端地址: http://localhost:3000/>
@@ -24,6 +25,15 @@ pub struct CorsConfig {
     /// 需要配置: http://localhost:3000 <br/>
     #[serde(with = "vec_option_serde", default = "allowed_origins_default")]
     pub allowed_origins: Option<Vec<String>>,
+    /// # 是否反射请求的来源(默认false)
+    /// ## 作用与原理
+    /// - 开启后，allowed_origins中的每一项都作为匹配模式(支持通配符*，如 https://*.example.com)
+    /// - 请求头 Origin 匹配任一模式时，将其原样回写到响应头 Access-Control-Allow-Origin 中
+    /// - 未设置 allowed_origins 时，反射所有来源
+    /// ## 使用场景
+    /// - 允许的来源多且动态变化，同时又需要携带凭证(携带凭证时不能使用 * 作为 allowed_origin)
+    #[serde(default)]
+    pub allowed_origin_reflect: bool,
     /// # 允许哪些HTTP方法
     /// ## 作用与原理
     /// - 控制浏览器允许前端代码可以使用哪些HTTP方法向此服务器发出请求
@@ -69,9 +79,11 @@ pub struct CorsConfig {
     /// - 预检请求的缓存时间，浏览器会在该时间段内不再发送预检请求，而是直接使用缓存的预检结果
     /// - 对应服务器预检时的响应头: Access-Control-Max-Age
     /// ## 注意事项
+    /// - 不设置时不返回此响应头，由浏览器使用其默认的缓存时间(Chromium为5秒)
+    /// - 设置为0时返回 `Access-Control-Max-Age: 0`，明确禁止浏览器缓存预检结果
     /// - 推荐值 <br/>
     /// -- 开发环境：0(方便调试) <br/>
-    /// -- 生产环境：不设置(默认1800，即30分钟)、3600(1小时) 或 86400(24小时)
+    /// -- 生产环境：1800(30分钟)、3600(1小时) 或 86400(24小时)
     #[serde(with = "duration_option_serde", default = "max_age_default")]
     pub max_age: Option<Duration>,
     /// # 是否允许携带凭证
@@ -83,7 +95,8 @@ pub struct CorsConfig {
     /// 对应服务器预检时的响应头: Access-Control-Allow-Credentials
     /// ## 注意事项
     /// - 默认情况下，浏览器不允许携带凭证
-    /// - 如果启用了 allow_credentials，就不能使用通配符 * 作为 allowed_origin
+    /// - 如果启用了 allow_credentials，就不能使用通配符 * 作为 allowed_origin，
+    ///   也必须明确配置 allowed_methods 及 allowed_headers，否则构建CORS时会报错
     /// ## 使用场景
     /// - 需要: 使用基于 Session/Cookie 的认证
     /// - 需要: 在跨域请求中读写 Cookie
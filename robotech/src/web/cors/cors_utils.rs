@@ -2,26 +2,63 @@ use crate::web::{CorsConfig, WebServerError};
 use axum::http;
 use tracing::debug;
 use std::str::FromStr;
-use tower_http::cors::CorsLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
 
 pub fn build_cors(cors_config: &Option<CorsConfig>) -> Result<Option<CorsLayer>, WebServerError> {
     if let Some(cors_config) = cors_config
         && cors_config.enabled
     {
         debug!("构建CORS: {:?}", cors_config);
+        validate_cors_config(cors_config)?;
         let mut cors = CorsLayer::default();
 
-        if let Some(ref allowed_origins) = cors_config.allowed_origins {
-            for origin in allowed_origins {
-                cors = cors.allow_origin(origin.parse::<http::HeaderValue>().map_err(|_| {
-                    WebServerError::ParseCors("allowed_origins".to_string(), origin.to_string())
-                })?);
-            }
+        if cors_config.allowed_origin_reflect {
+            // 反射请求的来源
+            cors = if let Some(ref allowed_origins) = cors_config.allowed_origins {
+                let patterns = allowed_origins.clone();
+                cors.allow_origin(AllowOrigin::predicate(move |origin, _| {
+                    origin.to_str().is_ok_and(|origin| {
+                        patterns
+                            .iter()
+                            .any(|pattern| matches_origin_pattern(pattern, origin))
+                    })
+                }))
+            } else {
+                cors.allow_origin(AllowOrigin::mirror_request())
+            };
+        } else if let Some(ref allowed_origins) = cors_config.allowed_origins
+            && !contains_wildcard(allowed_origins)
+            && allowed_origins.iter().any(|origin| origin.contains('*'))
+        {
+            // 包含通配符的来源按模式匹配(如 https://*.example.com)，其它来源仍然精确匹配
+            let patterns = allowed_origins.clone();
+            cors = cors.allow_origin(AllowOrigin::predicate(move |origin, _| {
+                origin.to_str().is_ok_and(|origin| {
+                    patterns
+                        .iter()
+                        .any(|pattern| matches_origin_pattern(pattern, origin))
+                })
+            }));
+        } else if let Some(ref allowed_origins) = cors_config.allowed_origins
+            && !contains_wildcard(allowed_origins)
+        {
+            let allowed_origins: Result<Vec<http::HeaderValue>, _> = allowed_origins
+                .iter()
+                .map(|origin| {
+                    origin.parse::<http::HeaderValue>().map_err(|_| {
+                        WebServerError::ParseCors("allowed_origins".to_string(), origin.to_string())
+                    })
+                })
+                .collect();
+            cors = cors.allow_origin(AllowOrigin::list(allowed_origins?));
         } else {
             cors = cors.allow_origin(tower_http::cors::Any);
         }
 
-        if let Some(ref allowed_methods) = cors_config.allowed_methods {
+        // 列表中包含 * 时等同于允许所有(tower-http 不允许在列表中使用 *)
+        if let Some(ref allowed_methods) = cors_config.allowed_methods
+            && !contains_wildcard(allowed_methods)
+        {
             let allowed_methods: Result<Vec<http::Method>, _> = allowed_methods
                 .iter()
                 .map(|s| http::Method::from_str(s))
@@ -33,7 +70,9 @@ pub fn build_cors(cors_config: &Option<CorsConfig>) -> Result<Option<CorsLayer>,
             cors = cors.allow_methods(tower_http::cors::Any);
         }
 
-        if let Some(ref allowed_headers) = cors_config.allowed_headers {
+        if let Some(ref allowed_headers) = cors_config.allowed_headers
+            && !contains_wildcard(allowed_headers)
+        {
             let allowed_headers: Result<Vec<http::header::HeaderName>, _> = allowed_headers
                 .iter()
                 .map(|s| http::header::HeaderName::from_str(s))
@@ -45,16 +84,22 @@ pub fn build_cors(cors_config: &Option<CorsConfig>) -> Result<Option<CorsLayer>,
             cors = cors.allow_headers(tower_http::cors::Any);
         }
 
-        if let Some(ref exposed_headers) = cors_config.expose_headers {
-            let exposed_headers: Result<Vec<http::header::HeaderName>, _> = exposed_headers
-                .iter()
-                .map(|s| http::header::HeaderName::from_str(s))
-                .collect();
-            cors = cors.expose_headers(exposed_headers.map_err(|e| {
-                WebServerError::ParseCors("exposed_headers".to_string(), e.to_string())
-            })?);
+        if let Some(ref expose_headers) = cors_config.expose_headers {
+            if contains_wildcard(expose_headers) {
+                cors = cors.expose_headers(tower_http::cors::Any);
+            } else {
+                let expose_headers: Result<Vec<http::header::HeaderName>, _> = expose_headers
+                    .iter()
+                    .map(|s| http::header::HeaderName::from_str(s))
+                    .collect();
+                cors = cors.expose_headers(expose_headers.map_err(|e| {
+                    WebServerError::ParseCors("expose_headers".to_string(), e.to_string())
+                })?);
+            }
         }
 
+        // 不设置时不返回 Access-Control-Max-Age 响应头，由浏览器使用其默认的缓存时间(Chromium为5秒)，
+        // 设置为0时明确禁止浏览器缓存预检结果(Access-Control-Max-Age: 0)
         if let Some(max_age) = cors_config.max_age {
             cors = cors.max_age(max_age);
         }
@@ -68,3 +113,212 @@ pub fn build_cors(cors_config: &Option<CorsConfig>) -> Result<Option<CorsLayer>,
         Ok(None)
     }
 }
+
+/// # 校验CORS配置中不合理的组合
+///
+/// 允许携带凭证时，浏览器不接受通配符 `*` 的来源、方法、请求头及暴露的响应头，
+/// 所以此时必须明确配置 allowed_origins(或开启 allowed_origin_reflect)、allowed_methods 及 allowed_headers，
+/// 且 expose_headers 中不能包含 `*`
+fn validate_cors_config(cors_config: &CorsConfig) -> Result<(), WebServerError> {
+    if !cors_config.allow_credentials.unwrap_or(false) {
+        return Ok(());
+    }
+    let is_wildcard = |values: &Option<Vec<String>>| {
+        values
+            .as_ref()
+            .is_none_or(|values| contains_wildcard(values))
+    };
+    if !cors_config.allowed_origin_reflect && is_wildcard(&cors_config.allowed_origins) {
+        Err(WebServerError::ParseCors(
+            "allowed_origins".to_string(),
+            "允许携带凭证时不能允许所有来源，请明确配置来源或开启 allowed_origin_reflect"
+                .to_string(),
+        ))?;
+    }
+    if is_wildcard(&cors_config.allowed_methods) {
+        Err(WebServerError::ParseCors(
+            "allowed_methods".to_string(),
+            "允许携带凭证时不能允许所有方法，请明确配置方法".to_string(),
+        ))?;
+    }
+    if is_wildcard(&cors_config.allowed_headers) {
+        Err(WebServerError::ParseCors(
+            "allowed_headers".to_string(),
+            "允许携带凭证时不能允许所有请求头，请明确配置请求头".to_string(),
+        ))?;
+    }
+    if cors_config
+        .expose_headers
+        .as_ref()
+        .is_some_and(|expose_headers| contains_wildcard(expose_headers))
+    {
+        Err(WebServerError::ParseCors(
+            "expose_headers".to_string(),
+            "允许携带凭证时不能暴露所有响应头，请明确配置响应头".to_string(),
+        ))?;
+    }
+    Ok(())
+}
+
+/// 列表中是否包含通配符 `*`
+fn contains_wildcard(values: &[String]) -> bool {
+    values.iter().any(|value| value == "*")
+}
+
+/// # 判断来源是否匹配模式
+///
+/// 模式中的`*`匹配主机名中的任意字符序列，如 `https://*.example.com` 匹配 `https://a.example.com`，
+/// 通配符不能匹配 `:` 及 `/`，所以协议及端口仍然需要精确匹配(不匹配 `https://a.example.com:8443`)
+fn matches_origin_pattern(pattern: &str, origin: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = origin.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    if parts.is_empty() {
+        // 模式中没有通配符，必须精确匹配
+        return rest.is_empty();
+    }
+    // 通配符匹配的部分只能是主机名中的字符
+    let is_host_chars = |matched: &str| !matched.contains([':', '/']);
+    for (index, part) in parts.iter().enumerate() {
+        if index == parts.len() - 1 {
+            // 最后一段必须是结尾
+            return rest.strip_suffix(part).is_some_and(is_host_chars);
+        }
+        match rest.find(part) {
+            Some(position) if is_host_chars(&rest[..position]) => {
+                rest = &rest[position + part.len()..]
+            }
+            _ => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::{Request, Response, header};
+    use axum::routing::get;
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    fn cors_config() -> CorsConfig {
+        CorsConfig {
+            enabled: true,
+            allowed_origins: None,
+            allowed_origin_reflect: false,
+            allowed_methods: Some(vec!["GET".to_string()]),
+            allowed_headers: Some(vec!["content-type".to_string()]),
+            expose_headers: None,
+            max_age: None,
+            allow_credentials: None,
+        }
+    }
+
+    /// 发送预检请求并返回响应
+    async fn preflight(cors_config: CorsConfig, origin: &str) -> Response<Body> {
+        let cors = build_cors(&Some(cors_config)).unwrap().unwrap();
+        let router = Router::new().route("/", get(|| async {})).layer(cors);
+        let request = Request::options("/")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+            .body(Body::empty())
+            .unwrap();
+        router.oneshot(request).await.unwrap()
+    }
+
+    fn header_value<'a>(response: &'a Response<Body>, name: header::HeaderName) -> Option<&'a str> {
+        response
+            .headers()
+            .get(name)
+            .map(|value| value.to_str().unwrap())
+    }
+
+    #[tokio::test]
+    async fn reflect_matching_origin_with_credentials() {
+        let cors_config = CorsConfig {
+            allowed_origins: Some(vec!["https://*.example.com".to_string()]),
+            allowed_origin_reflect: true,
+            allow_credentials: Some(true),
+            ..cors_config()
+        };
+        let response = preflight(cors_config, "https://a.example.com").await;
+        assert_eq!(
+            header_value(&response, header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some("https://a.example.com")
+        );
+        assert_eq!(
+            header_value(&response, header::ACCESS_CONTROL_ALLOW_CREDENTIALS),
+            Some("true")
+        );
+    }
+
+    #[tokio::test]
+    async fn reflect_rejects_non_matching_origin() {
+        let cors_config = CorsConfig {
+            allowed_origins: Some(vec!["https://*.example.com".to_string()]),
+            allowed_origin_reflect: true,
+            allow_credentials: Some(true),
+            ..cors_config()
+        };
+        for origin in [
+            "https://evil.com",
+            "https://a.example.com.evil.com",
+            "https://a.example.com:8443",
+        ] {
+            let response = preflight(cors_config.clone(), origin).await;
+            // 没有 Access-Control-Allow-Origin 时浏览器会拒绝该跨域请求
+            assert_eq!(
+                header_value(&response, header::ACCESS_CONTROL_ALLOW_ORIGIN),
+                None,
+                "{origin}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn reflect_any_origin_without_allowed_origins() {
+        let cors_config = CorsConfig {
+            allowed_origin_reflect: true,
+            ..cors_config()
+        };
+        let response = preflight(cors_config, "https://any.com").await;
+        assert_eq!(
+            header_value(&response, header::ACCESS_CONTROL_ALLOW_ORIGIN),
+            Some("https://any.com")
+        );
+        assert_eq!(
+            header_value(&response, header::ACCESS_CONTROL_ALLOW_CREDENTIALS),
+            None
+        );
+    }
+
+    async fn preflight_max_age(max_age: Option<Duration>) -> Option<String> {
+        let cors_config = CorsConfig {
+            max_age,
+            ..cors_config()
+        };
+        let response = preflight(cors_config, "https://any.com").await;
+        header_value(&response, header::ACCESS_CONTROL_MAX_AGE).map(str::to_string)
+    }
+
+    #[tokio::test]
+    async fn max_age() {
+        assert_eq!(preflight_max_age(None).await, None);
+        assert_eq!(
+            preflight_max_age(Some(Duration::ZERO)).await.as_deref(),
+            Some("0")
+        );
+        assert_eq!(
+            preflight_max_age(Some(Duration::from_secs(600)))
+                .await
+                .as_deref(),
+            Some("600")
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::StatusCode;
+use axum::http::request::Parts;
+use std::net::{IpAddr, SocketAddr};
+
+/// # 客户端IP
+///
+/// 配置了 `trusted-proxies` 时，由客户端IP中间件根据代理请求头解析出真实的客户端IP，
+/// 否则为TCP连接的对端IP。可以直接作为handler的参数提取:
+///
+/// ```
+/// async fn login(ClientIp(client_ip): ClientIp, Json(dto): Json<LoginDto>) -> Result<Json<Ro<()>>, CtrlError> {
+///     info!("用户<{}>从<{client_ip}>登录", dto.username);
+///     ...
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClientIp(pub IpAddr);
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(client_ip) = parts.extensions.get::<ClientIp>() {
+            return Ok(*client_ip);
+        }
+        parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| ClientIp(addr.ip()))
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
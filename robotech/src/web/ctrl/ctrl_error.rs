@@ -1,7 +1,5 @@
 use crate::dao::DaoError;
-#[cfg(feature = "db")]
-use crate::ro::RO_CODE_WARNING_DELETE_VIOLATE_FK;
-use crate::ro::{RO_CODE_WARNING_DUPLICATE_KEY, RO_CODE_WARNING_INSERT_VIOLATE_FK, Ro};
+use crate::ro::{Ro, RoResult};
 use crate::svc::SvcError;
 use axum::Json;
 use axum::http::StatusCode;
@@ -49,97 +47,214 @@ pub enum CtrlError {
     Svc(#[from] SvcError),
 }
 
+/// # 错误的类别
+///
+/// 决定错误响应的结果及HTTP状态码，`to_ro` 与 `into_response` 共用，避免两者的映射不一致
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum ErrorKind {
+    /// 参数错误(400)
+    IllegalArgument,
+    /// 找不到数据(404)
+    NotFound,
+    /// 用户方面的错误，如违反唯一键、外键约束或业务规则(200)
+    Warn,
+    /// 系统方面的异常(500)
+    Fail,
+}
+
+impl ErrorKind {
+    /// 响应的结果
+    fn ro_result(self) -> RoResult {
+        match self {
+            ErrorKind::IllegalArgument => RoResult::IllegalArgument,
+            ErrorKind::NotFound | ErrorKind::Warn => RoResult::Warn,
+            ErrorKind::Fail => RoResult::Fail,
+        }
+    }
+
+    /// HTTP状态码
+    fn status_code(self) -> StatusCode {
+        match self {
+            ErrorKind::IllegalArgument => StatusCode::BAD_REQUEST,
+            ErrorKind::NotFound => StatusCode::NOT_FOUND,
+            ErrorKind::Warn => StatusCode::OK,
+            ErrorKind::Fail => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
 /// # 为 CtrlError 实现错误转换方法
 ///
 /// 该实现定义了如何将不同类型的 控制器 错误转换为统一的 Ro 响应对象，以便在 HTTP 接口中返回标准化的错误信息格式
 impl CtrlError {
-    /// 将错误转换为Ro对象
-    fn to_ro(&self) -> Ro<()> {
+    /// 错误的类别
+    ///
+    /// 逐个匹配服务层错误的类型(不使用通配)，新增错误类型时编译器会提示在这里归类
+    fn kind(&self) -> ErrorKind {
+        match self {
+            CtrlError::Runtime(_) | CtrlError::Io(_) => ErrorKind::Fail,
+            CtrlError::Validation(_)
+            | CtrlError::Validations(_)
+            | CtrlError::InvalidHeaderValue(_) => ErrorKind::IllegalArgument,
+            CtrlError::Svc(error) => match error {
+                SvcError::Validation(_) | SvcError::Validations(_) => ErrorKind::IllegalArgument,
+                #[cfg(feature = "web")]
+                SvcError::MultipartError(_) => ErrorKind::IllegalArgument,
+                SvcError::NotFound(_) => ErrorKind::NotFound,
+                SvcError::Business { .. } => ErrorKind::Warn,
+                SvcError::Runtime(_)
+                | SvcError::GetAppEnv(_)
+                | SvcError::SystemTime(_)
+                | SvcError::IdWorker(_)
+                | SvcError::App(_)
+                | SvcError::Io(_) => ErrorKind::Fail,
+                #[cfg(feature = "db")]
+                SvcError::Dao(error) => match error {
+                    DaoError::NotFound(_) => ErrorKind::NotFound,
+                    DaoError::NotNullViolation(_) => ErrorKind::IllegalArgument,
+                    DaoError::DuplicateKey(_, _)
+                    | DaoError::InsertViolateFk(_)
+                    | DaoError::DeleteViolateFk(_)
+                    | DaoError::ForeignKeyViolation(_)
+                    | DaoError::Db(DbErr::RecordNotUpdated) => ErrorKind::Warn,
+                    DaoError::Runtime(_)
+                    | DaoError::GetDbConn()
+                    | DaoError::SystemTime(_)
+                    | DaoError::IdWorker(_)
+                    | DaoError::Db(_)
+                    | DaoError::NotInitialized(_)
+                    | DaoError::AlreadyInitialized(_) => ErrorKind::Fail,
+                },
+                #[cfg(feature = "db")]
+                SvcError::DbConn(_) => ErrorKind::Fail,
+                #[cfg(feature = "api-client")]
+                SvcError::ApiClient(_) => ErrorKind::Fail,
+            },
+        }
+    }
+
+    /// 错误的消息及详情
+    fn msg_and_detail(&self) -> (String, Option<String>) {
         match self {
             CtrlError::Runtime(error) => {
                 warn!("{}", error);
-                Ro::warn("运行时错误".to_string()).detail(Some(error.to_string()))
-            }
-            CtrlError::Validation(error) => {
-                Ro::illegal_argument(format!("参数校验错误 -> {}", error.to_string()))
-            }
-            CtrlError::Validations(errors) => {
-                Ro::illegal_argument(format!("参数校验错误 -> {}", errors))
+                ("运行时错误".to_string(), Some(error.to_string()))
             }
+            CtrlError::Validation(error) => (format!("参数校验错误 -> {}", error), None),
+            CtrlError::Validations(errors) => (format!("参数校验错误 -> {}", errors), None),
             CtrlError::InvalidHeaderValue(error) => {
-                Ro::illegal_argument("Header值错误".to_string()).detail(Some(error.to_string()))
-            }
-            CtrlError::Io(error) => {
-                Ro::fail("磁盘异常".to_string()).detail(Some(error.to_string()))
+                ("Header值错误".to_string(), Some(error.to_string()))
             }
+            CtrlError::Io(error) => ("磁盘异常".to_string(), Some(error.to_string())),
             CtrlError::Svc(error) => match error {
-                SvcError::Validation(error) => {
-                    Ro::illegal_argument(format!("参数校验错误 -> {}", error.to_string()))
-                }
-                SvcError::Validations(errors) => {
-                    Ro::illegal_argument(format!("参数校验错误 -> {}", errors))
-                }
-                SvcError::NotFound(err) => {
-                    Ro::warn("找不到数据".to_string()).detail(Some(err.to_string()))
-                }
+                SvcError::Validation(error) => (format!("参数校验错误 -> {}", error), None),
+                SvcError::Validations(errors) => (format!("参数校验错误 -> {}", errors), None),
+                SvcError::NotFound(err) => ("找不到数据".to_string(), Some(err.to_string())),
+                SvcError::Business { msg, .. } => (msg.to_string(), None),
                 #[cfg(feature = "db")]
                 SvcError::Dao(error) => match error {
-                    DaoError::DuplicateKey(unique_key, value) => {
-                        Ro::warn(format!("{}<{}>已存在！", unique_key.key_remark, value))
-                            .code(Some(RO_CODE_WARNING_DUPLICATE_KEY.to_string()))
-                            .detail(Some(format!("{unique_key} -> value: {value}")))
+                    DaoError::NotFound(err) => ("找不到数据".to_string(), Some(err.to_string())),
+                    DaoError::DuplicateKey(unique_key, value) => (
+                        format!("{}<{}>已存在！", unique_key.key_remark, value),
+                        Some(format!("{unique_key} -> value: {value}")),
+                    ),
+                    DaoError::InsertViolateFk(foreign_key) => (
+                        format!(
+                            "不能插入(或更新){}，设置的{}并不存在",
+                            foreign_key.fk_table_comment, foreign_key.pk_table_comment
+                        ),
+                        Some(foreign_key.to_string()),
+                    ),
+                    DaoError::DeleteViolateFk(foreign_key) => (
+                        format!(
+                            "不能删除(或更新){}，存在关联其的{}",
+                            foreign_key.pk_table_comment, foreign_key.fk_table_comment
+                        ),
+                        Some(foreign_key.to_string()),
+                    ),
+                    DaoError::ForeignKeyViolation(constraint) => (
+                        "违反了外键约束，关联的数据不存在或仍被引用".to_string(),
+                        Some(constraint.to_string()),
+                    ),
+                    DaoError::NotNullViolation(column) => {
+                        (format!("参数校验错误 -> {column}不能为空"), None)
+                    }
+                    DaoError::Db(DbErr::RecordNotUpdated) => {
+                        ("未更新数据，请检查记录是否存在".to_string(), None)
                     }
-                    DaoError::InsertViolateFk(foreign_key) => Ro::warn(format!(
-                        "不能插入(或更新){}，设置的{}并不存在",
-                        foreign_key.fk_table_comment, foreign_key.pk_table_comment
-                    ))
-                    .code(Some(RO_CODE_WARNING_INSERT_VIOLATE_FK.to_string()))
-                    .detail(Some(foreign_key.to_string())),
-                    DaoError::DeleteViolateFk(foreign_key) => Ro::warn(format!(
-                        "不能删除(或更新){}，存在关联其的{}",
-                        foreign_key.pk_table_comment, foreign_key.fk_table_comment
-                    ))
-                    .code(Some(RO_CODE_WARNING_DELETE_VIOLATE_FK.to_string()))
-                    .detail(Some(foreign_key.to_string())),
-                    DaoError::Db(db_err) => match db_err {
-                        DbErr::RecordNotUpdated => {
-                            Ro::warn("未更新数据，请检查记录是否存在".to_string())
-                        }
-                        _ => Ro::fail("数据库错误".to_string()).detail(Some(db_err.to_string())),
-                    },
-                    _ => Ro::fail("数据访问层错误".to_string()).detail(Some(error.to_string())),
+                    DaoError::Db(db_err) => ("数据库错误".to_string(), Some(db_err.to_string())),
+                    _ => ("数据访问层错误".to_string(), Some(error.to_string())),
                 },
-                _ => Ro::fail(error.to_string()),
+                _ => (error.to_string(), None),
             },
         }
     }
+
+    /// 将错误转换为Ro对象
+    fn to_ro(&self) -> Ro<()> {
+        let (msg, detail) = self.msg_and_detail();
+        let ro = Ro::builder()
+            .result(self.kind().ro_result())
+            .msg(msg)
+            .build()
+            .detail(detail);
+        match self {
+            // 服务层错误都设置对应的错误码
+            CtrlError::Svc(error) => ro.code(Some(error.code().to_string())),
+            _ => ro,
+        }
+    }
+}
+
+impl<T> Ro<T> {
+    /// # 由服务层的结果创建响应对象
+    ///
+    /// 成功时返回携带数据的成功响应，失败时按 `CtrlError` 的规则转换为对应的失败(或警告、参数错误)响应
+    ///
+    /// ## 参数
+    /// * `result` - 服务层的结果
+    /// * `success_msg` - 成功消息
+    ///
+    /// ## 使用示例
+    /// ```
+    /// async fn get_by_id(Path(id): Path<u64>) -> Json<Ro<UserVo>> {
+    ///     Json(Ro::from_result(UserSvc::get_by_id(id).await, "获取用户成功"))
+    /// }
+    /// ```
+    pub fn from_result<E: Into<SvcError>>(result: Result<T, E>, success_msg: &str) -> Ro<T> {
+        match result {
+            Ok(value) => Ro::success(success_msg.to_string()).extra(Some(value)),
+            Err(error) => {
+                let error = CtrlError::Svc(error.into());
+                warn!("控制器层捕获错误: {}", error);
+                let Ro {
+                    result,
+                    msg,
+                    timestamp,
+                    detail,
+                    code,
+                    warnings,
+                    ..
+                } = error.to_ro();
+                Ro {
+                    result,
+                    msg,
+                    timestamp,
+                    extra: None,
+                    detail,
+                    code,
+                    warnings,
+                }
+            }
+        }
+    }
 }
 
 // 为错误类型实现 IntoResponse
 impl IntoResponse for CtrlError {
     fn into_response(self) -> Response {
         warn!("控制器层捕获错误: {}", self);
-        let status = match &self {
-            CtrlError::Runtime(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            CtrlError::Validation(_)
-            | CtrlError::Validations(_)
-            | CtrlError::InvalidHeaderValue(_) => StatusCode::BAD_REQUEST,
-            CtrlError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            CtrlError::Svc(error) => match error {
-                SvcError::NotFound(_) => StatusCode::NOT_FOUND,
-                SvcError::Validation(_)
-                | SvcError::Validations(_)
-                | SvcError::MultipartError(_) => StatusCode::BAD_REQUEST,
-                #[cfg(feature = "db")]
-                SvcError::Dao(error) => match error {
-                    DaoError::DuplicateKey(_, _)
-                    | DaoError::InsertViolateFk(_)
-                    | DaoError::DeleteViolateFk(_) => StatusCode::OK,
-                    _ => StatusCode::INTERNAL_SERVER_ERROR,
-                },
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            },
-        };
+        let status = self.kind().status_code();
 
         (status, Json(&self.to_ro())).into_response()
     }
@@ -1,5 +1,7 @@
 use crate::cst::user_id_cst::USER_ID_HEADER_NAME;
 use axum::http::HeaderMap;
+use std::collections::HashMap;
+use std::str::FromStr;
 use validator;
 
 /// # 从HTTP请求头中获取当前用户ID
@@ -38,3 +40,51 @@ pub fn get_current_user_id(headers: &HeaderMap) -> Result<u64, validator::Valida
             validator::ValidationError::new(Box::leak(msg.into_boxed_str()))
         })
 }
+
+/// # 从路径参数中获取ID
+///
+/// 用于RESTful风格的路由(如 `/users/{id}`)，统一ID的提取及错误信息
+///
+/// ## 参数
+///
+/// * `path_params` - 路径参数，在handler中通过 `Path(path_params): Path<HashMap<String, String>>` 获取
+/// * `name` - 参数名称
+///
+/// ## 返回值
+///
+/// * `Ok(u64)` - 成功解析出的ID
+/// * `Err(ValidationError)` - 缺少参数或格式不正确时返回的错误信息
+pub fn get_id_from_path(
+    path_params: &HashMap<String, String>,
+    name: &str,
+) -> Result<u64, validator::ValidationError> {
+    get_from_path::<u64>(path_params, name)
+}
+
+/// # 从路径参数中获取指定类型的值
+///
+/// ## 参数
+///
+/// * `path_params` - 路径参数
+/// * `name` - 参数名称
+///
+/// ## 错误处理
+///
+/// * 如果路径中缺少该参数，返回`ValidationError`
+/// * 如果参数格式不正确，无法解析为`T`类型，返回`ValidationError`
+pub fn get_from_path<T: FromStr>(
+    path_params: &HashMap<String, String>,
+    name: &str,
+) -> Result<T, validator::ValidationError> {
+    path_params
+        .get(name)
+        .ok_or_else(|| {
+            let msg = format!("缺少必要参数<{}>", name);
+            validator::ValidationError::new(Box::leak(msg.into_boxed_str()))
+        })?
+        .parse::<T>()
+        .map_err(|_| {
+            let msg = format!("参数<{}>格式不正确", name);
+            validator::ValidationError::new(Box::leak(msg.into_boxed_str()))
+        })
+}
@@ -1,4 +1,12 @@
+mod client_ip;
 mod ctrl_error;
 pub mod ctrl_utils;
+mod ro_csv;
+mod ro_file;
+mod ro_response;
 
+pub use client_ip::*;
 pub use ctrl_error::*;
+pub use ro_csv::*;
+pub use ro_file::*;
+pub use ro_response::*;
@@ -0,0 +1,161 @@
+#[cfg(feature = "db")]
+use crate::dao::{DaoError, ModelStream};
+#[cfg(feature = "db")]
+use crate::svc::SvcError;
+use crate::web::{CtrlError, RoFile};
+use anyhow::anyhow;
+use axum::body::{Body, Bytes};
+#[cfg(feature = "db")]
+use futures_util::StreamExt;
+use serde::Serialize;
+use serde_json::Value;
+use std::io;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// 发送CSV数据的通道容量(行数)，客户端读取慢时发送端会等待，避免在内存中堆积
+const CSV_CHANNEL_CAPACITY: usize = 64;
+
+/// # CSV导出的发送端
+///
+/// 通过 `RoFile::csv` 创建，逐行发送的数据会以流的方式写入响应体，不会在内存中缓存整个结果集。
+/// 创建时按指定的字段名(一般为Vo的 `FIELD_NAMES`)写入表头，每行按表头的顺序取序列化后的字段值，
+/// 没有的字段(如被跳过序列化的None值)写入空单元格
+///
+/// ## 使用示例
+/// ```
+/// async fn export(Query(dto): Query<UserQueryDto>) -> Result<RoFile, CtrlError> {
+///     let db_conn = get_db_conn().map_err(SvcError::from)?;
+///     let (mut csv_sender, ro_file) = RoFile::csv("user.csv", UserVo::FIELD_NAMES);
+///     tokio::spawn(async move {
+///         let result: Result<u64, CtrlError> = async {
+///             let stream = UserSvc::stream_by_query_dto(dto, db_conn.as_ref()).await?;
+///             csv_sender.send_stream(stream, UserVo::from).await
+///         }
+///         .await;
+///         if let Err(e) = result {
+///             csv_sender.abort(e).await;
+///         }
+///     });
+///     Ok(ro_file)
+/// }
+/// ```
+pub struct CsvSender {
+    sender: mpsc::Sender<Result<Bytes, io::Error>>,
+    /// 表头(字段名)
+    headers: Vec<String>,
+}
+
+impl RoFile {
+    /// # 创建CSV文件响应
+    ///
+    /// 返回CSV的发送端及文件响应，文件响应的 `Content-Type` 为 `text/csv; charset=utf-8`，
+    /// 发送端被释放时响应体结束(没有发送任何行时，文件中只有表头)
+    ///
+    /// ## 参数
+    /// * `file_name` - 文件名
+    /// * `headers` - 表头(行数据序列化后的字段名)
+    pub fn csv(file_name: impl Into<String>, headers: &[&str]) -> (CsvSender, RoFile) {
+        let (sender, receiver) = mpsc::channel(CSV_CHANNEL_CAPACITY);
+        // UTF-8 BOM，使Excel能正确识别中文
+        let mut chunk = String::from('\u{FEFF}');
+        write_csv_line(&mut chunk, headers.iter().map(|header| header.to_string()));
+        // 通道是新建的，一定有空间
+        let _ = sender.try_send(Ok(Bytes::from(chunk)));
+        let stream = futures_util::stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.map(|chunk| (chunk, receiver))
+        });
+        let ro_file = RoFile::from_body(Body::from_stream(stream), file_name)
+            .content_type("text/csv; charset=utf-8");
+        (
+            CsvSender {
+                sender,
+                headers: headers.iter().map(|header| header.to_string()).collect(),
+            },
+            ro_file,
+        )
+    }
+}
+
+impl CsvSender {
+    /// # 发送一行
+    ///
+    /// 行数据必须序列化为对象，按表头的顺序写入字段值，表头中没有的字段会被忽略
+    pub async fn send<T: Serialize>(&mut self, row: &T) -> Result<(), CtrlError> {
+        let Value::Object(mut fields) = serde_json::to_value(row)
+            .map_err(|e| CtrlError::Runtime(anyhow!("序列化CSV行失败: {e}")))?
+        else {
+            return Err(CtrlError::Runtime(anyhow!("CSV行必须序列化为对象")));
+        };
+
+        let mut chunk = String::new();
+        write_csv_line(
+            &mut chunk,
+            self.headers
+                .iter()
+                .map(|header| fields.remove(header).map(value_to_cell).unwrap_or_default()),
+        );
+
+        self.sender
+            .send(Ok(Bytes::from(chunk)))
+            .await
+            .map_err(|_| CtrlError::Io(io::Error::new(io::ErrorKind::BrokenPipe, "客户端已断开")))
+    }
+
+    /// # 发送流中的所有记录
+    ///
+    /// 逐条读取记录并转换后发送，返回发送的行数
+    #[cfg(feature = "db")]
+    pub async fn send_stream<M, T: Serialize>(
+        &mut self,
+        stream: impl ModelStream<M>,
+        map: impl Fn(M) -> T,
+    ) -> Result<u64, CtrlError> {
+        let mut stream = std::pin::pin!(stream);
+        let mut count = 0;
+        while let Some(model) = stream.next().await {
+            let model = model.map_err(|e| SvcError::from(DaoError::from(e)))?;
+            self.send(&map(model)).await?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// # 中止导出
+    ///
+    /// 响应头已经发送，无法再返回Ro，只能中断响应体，客户端会收到不完整的文件
+    pub async fn abort(self, error: CtrlError) {
+        warn!("导出CSV失败: {error}");
+        let _ = self
+            .sender
+            .send(Err(io::Error::other(error.to_string())))
+            .await;
+    }
+}
+
+/// 写入CSV的一行
+fn write_csv_line(chunk: &mut String, cells: impl Iterator<Item = String>) {
+    for (index, cell) in cells.enumerate() {
+        if index > 0 {
+            chunk.push(',');
+        }
+        // 包含分隔符、引号或换行时，用引号包裹并将引号转义为两个引号
+        if cell.contains([',', '"', '\n', '\r']) {
+            chunk.push('"');
+            chunk.push_str(cell.replace('"', "\"\"").as_str());
+            chunk.push('"');
+        } else {
+            chunk.push_str(cell.as_str());
+        }
+    }
+    chunk.push_str("\r\n");
+}
+
+/// 将字段值转换为单元格内容
+fn value_to_cell(value: Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(value) => value,
+        value => value.to_string(),
+    }
+}
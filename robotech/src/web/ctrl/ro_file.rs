@@ -0,0 +1,335 @@
+use crate::svc::SvcError;
+use crate::web::CtrlError;
+use axum::body::{Body, Bytes};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use std::io::{ErrorKind, SeekFrom};
+use std::path::Path;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// 流式读取文件时每次读取的字节数
+const FILE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// # 文件响应
+///
+/// 用于文件下载等需要返回文件(而不是Ro)的接口，成功时返回文件内容，并带上
+/// `Content-Type` 及 `Content-Disposition` 响应头。
+/// handler 返回 `Result<RoFile, CtrlError>`，出错时仍按 `CtrlError` 的约定返回Ro格式的JSON。
+/// 视频等大文件使用 `from_path_ranged`，支持客户端通过 `Range` 请求头拖动进度及断点续传
+///
+/// ## 使用示例
+/// ```
+/// async fn download(Path(id): Path<u64>) -> Result<RoFile, CtrlError> {
+///     let file_path = get_file_path(id).await?;
+///     RoFile::from_path(file_path).await
+/// }
+/// ```
+pub struct RoFile {
+    /// 文件内容
+    body: Body,
+    /// 下载时的文件名
+    file_name: String,
+    /// 文件类型
+    content_type: String,
+    /// 是否作为附件下载(否则浏览器内联显示)
+    attachment: bool,
+    /// 响应状态码
+    status: StatusCode,
+    /// 其它响应头(如范围请求相关的响应头)
+    headers: HeaderMap,
+}
+
+/// 请求的字节范围
+enum ByteRange {
+    /// 整个文件
+    Full,
+    /// 部分内容(起始及结束位置，都包含在内)
+    Partial(u64, u64),
+    /// 范围无法满足
+    Unsatisfiable,
+}
+
+impl RoFile {
+    /// # 从字节创建文件响应
+    pub fn from_bytes(bytes: impl Into<Bytes>, file_name: impl Into<String>) -> Self {
+        Self::from_body(Body::from(bytes.into()), file_name)
+    }
+
+    /// # 从响应体(如流)创建文件响应
+    pub fn from_body(body: Body, file_name: impl Into<String>) -> Self {
+        Self {
+            body,
+            file_name: file_name.into(),
+            content_type: "application/octet-stream".to_string(),
+            attachment: true,
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// # 读取文件创建文件响应
+    ///
+    /// 文件名取路径中的文件名，文件不存在时返回 `SvcError::NotFound`(对应404及Ro::warn)
+    pub async fn from_path(path: impl AsRef<Path>) -> Result<Self, CtrlError> {
+        let path = path.as_ref().to_path_buf();
+        let file_name = path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let read_path = path.clone();
+        let bytes = tokio::task::spawn_blocking(move || std::fs::read(read_path))
+            .await
+            .map_err(|e| CtrlError::Runtime(anyhow::anyhow!("读取文件任务异常: {e}")))?
+            .map_err(|e| map_read_err(e, &path))?;
+        Ok(Self::from_bytes(bytes, file_name))
+    }
+
+    /// # 读取文件创建支持范围请求的文件响应
+    ///
+    /// 以流的方式读取文件(不会一次性加载到内存中)，并根据请求头处理范围请求:
+    /// - 响应都带上 `Accept-Ranges: bytes` 及 `ETag`/`Last-Modified`
+    /// - `Range: bytes=start-end`(或 `start-`、`-suffix`)只返回请求的部分，状态码为 `206 Partial Content`
+    /// - 范围超出文件大小时返回 `416 Range Not Satisfiable`
+    /// - 带 `If-Range` 时，只有与文件当前的 `ETag`(或 `Last-Modified`)一致才按范围返回，否则返回整个文件
+    /// - 多个范围(multipart/byteranges)不支持，按整个文件返回
+    ///
+    /// 静态文件服务使用的 `ServeDir` 已自行支持范围请求
+    ///
+    /// ## 使用示例
+    /// ```
+    /// async fn play(Path(id): Path<u64>, headers: HeaderMap) -> Result<RoFile, CtrlError> {
+    ///     let file_path = get_file_path(id).await?;
+    ///     Ok(RoFile::from_path_ranged(file_path, &headers).await?.content_type("video/mp4").inline())
+    /// }
+    /// ```
+    pub async fn from_path_ranged(
+        path: impl AsRef<Path>,
+        request_headers: &HeaderMap,
+    ) -> Result<Self, CtrlError> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut file = File::open(path).await.map_err(|e| map_read_err(e, path))?;
+        let metadata = file.metadata().await.map_err(CtrlError::Io)?;
+        let file_len = metadata.len();
+        let last_modified = metadata.modified().ok().map(DateTime::<Utc>::from);
+        let etag = last_modified.map(|last_modified| {
+            format!("\"{file_len:x}-{:x}\"", last_modified.timestamp_micros())
+        });
+
+        let byte_range = match request_headers
+            .get(header::RANGE)
+            .and_then(|range| range.to_str().ok())
+        {
+            Some(range) if if_range_matches(request_headers, &etag, &last_modified) => {
+                parse_range(range, file_len)
+            }
+            _ => ByteRange::Full,
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+        if let Some(etag) = &etag {
+            headers.insert(header::ETAG, header_value(etag)?);
+        }
+        if let Some(last_modified) = &last_modified {
+            headers.insert(
+                header::LAST_MODIFIED,
+                header_value(&format_http_date(last_modified))?,
+            );
+        }
+        let (status, body) = match byte_range {
+            ByteRange::Full => {
+                headers.insert(header::CONTENT_LENGTH, header_value(&file_len.to_string())?);
+                (StatusCode::OK, file_body(file, file_len))
+            }
+            ByteRange::Partial(start, end) => {
+                let len = end - start + 1;
+                file.seek(SeekFrom::Start(start))
+                    .await
+                    .map_err(CtrlError::Io)?;
+                headers.insert(
+                    header::CONTENT_RANGE,
+                    header_value(&format!("bytes {start}-{end}/{file_len}"))?,
+                );
+                headers.insert(header::CONTENT_LENGTH, header_value(&len.to_string())?);
+                (StatusCode::PARTIAL_CONTENT, file_body(file, len))
+            }
+            ByteRange::Unsatisfiable => {
+                headers.insert(
+                    header::CONTENT_RANGE,
+                    header_value(&format!("bytes */{file_len}"))?,
+                );
+                (StatusCode::RANGE_NOT_SATISFIABLE, Body::empty())
+            }
+        };
+
+        let mut ro_file = Self::from_body(body, file_name);
+        ro_file.status = status;
+        ro_file.headers = headers;
+        Ok(ro_file)
+    }
+
+    /// 设置文件类型(默认为 application/octet-stream)
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = content_type.into();
+        self
+    }
+
+    /// 设置为内联显示(默认作为附件下载)
+    pub fn inline(mut self) -> Self {
+        self.attachment = false;
+        self
+    }
+}
+
+impl IntoResponse for RoFile {
+    fn into_response(self) -> Response {
+        let content_type = match HeaderValue::from_str(self.content_type.as_str()) {
+            Ok(content_type) => content_type,
+            Err(e) => return CtrlError::InvalidHeaderValue(e).into_response(),
+        };
+        let disposition = format!(
+            "{}; filename*=UTF-8''{}",
+            if self.attachment {
+                "attachment"
+            } else {
+                "inline"
+            },
+            encode_rfc5987(self.file_name.as_str())
+        );
+        let disposition = match HeaderValue::from_str(disposition.as_str()) {
+            Ok(disposition) => disposition,
+            Err(e) => return CtrlError::InvalidHeaderValue(e).into_response(),
+        };
+
+        let mut response = Response::new(self.body);
+        *response.status_mut() = self.status;
+        let headers = response.headers_mut();
+        headers.extend(self.headers);
+        headers.insert(header::CONTENT_TYPE, content_type);
+        headers.insert(header::CONTENT_DISPOSITION, disposition);
+        response
+    }
+}
+
+/// 将读取文件的错误转换为控制器错误，文件不存在时返回 `SvcError::NotFound`
+fn map_read_err(e: std::io::Error, path: &Path) -> CtrlError {
+    match e.kind() {
+        ErrorKind::NotFound => {
+            CtrlError::Svc(SvcError::NotFound(path.to_string_lossy().to_string()))
+        }
+        _ => CtrlError::Io(e),
+    }
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, CtrlError> {
+    HeaderValue::from_str(value).map_err(CtrlError::InvalidHeaderValue)
+}
+
+/// 格式化为HTTP日期，如 `Sun, 06 Nov 1994 08:49:37 GMT`
+fn format_http_date(date_time: &DateTime<Utc>) -> String {
+    date_time.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// 以流的方式读取文件从当前位置开始的len个字节作为响应体
+fn file_body(file: File, len: u64) -> Body {
+    let stream = futures_util::stream::unfold(Some(file.take(len)), |reader| async move {
+        let mut reader = reader?;
+        let mut buf = vec![0u8; FILE_CHUNK_SIZE];
+        match reader.read(&mut buf).await {
+            Ok(0) => None,
+            Ok(n) => {
+                buf.truncate(n);
+                Some((Ok(Bytes::from(buf)), Some(reader)))
+            }
+            // 出错后结束响应体
+            Err(e) => Some((Err(e), None)),
+        }
+    });
+    Body::from_stream(stream)
+}
+
+/// 判断 `If-Range` 是否与文件当前的 `ETag`(或 `Last-Modified`)一致，没有 `If-Range` 时视为一致
+fn if_range_matches(
+    request_headers: &HeaderMap,
+    etag: &Option<String>,
+    last_modified: &Option<DateTime<Utc>>,
+) -> bool {
+    let Some(if_range) = request_headers.get(header::IF_RANGE) else {
+        return true;
+    };
+    let Ok(if_range) = if_range.to_str() else {
+        return false;
+    };
+    let if_range = if_range.trim();
+    if if_range.starts_with('"') {
+        // 强校验，弱ETag(W/开头)不会相等
+        etag.as_deref() == Some(if_range)
+    } else {
+        match (DateTime::parse_from_rfc2822(if_range), last_modified) {
+            (Ok(date), Some(last_modified)) => date.timestamp() == last_modified.timestamp(),
+            _ => false,
+        }
+    }
+}
+
+/// 解析 `Range` 请求头，格式不正确或有多个范围时按整个文件返回
+fn parse_range(range: &str, file_len: u64) -> ByteRange {
+    let Some(range) = range.trim().strip_prefix("bytes=") else {
+        return ByteRange::Full;
+    };
+    if range.contains(',') {
+        return ByteRange::Full;
+    }
+    let Some((start, end)) = range.trim().split_once('-') else {
+        return ByteRange::Full;
+    };
+    let (start, end) = (start.trim(), end.trim());
+    if start.is_empty() {
+        // 后缀范围，如 `-500` 表示最后500个字节
+        return match end.parse::<u64>() {
+            Ok(0) => ByteRange::Unsatisfiable,
+            Ok(_) if file_len == 0 => ByteRange::Unsatisfiable,
+            Ok(suffix_len) => ByteRange::Partial(file_len.saturating_sub(suffix_len), file_len - 1),
+            Err(_) => ByteRange::Full,
+        };
+    }
+    let Ok(start) = start.parse::<u64>() else {
+        return ByteRange::Full;
+    };
+    let end = if end.is_empty() {
+        file_len.saturating_sub(1)
+    } else {
+        match end.parse::<u64>() {
+            Ok(end) if end >= start => end.min(file_len.saturating_sub(1)),
+            _ => return ByteRange::Full,
+        }
+    };
+    if start >= file_len {
+        return ByteRange::Unsatisfiable;
+    }
+    ByteRange::Partial(start, end)
+}
+
+/// 按RFC5987编码文件名，以支持中文等非ASCII字符
+fn encode_rfc5987(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric()
+            || matches!(
+                byte,
+                b'!' | b'#' | b'$' | b'&' | b'+' | b'-' | b'.' | b'^' | b'_' | b'`' | b'|' | b'~'
+            )
+        {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(format!("%{byte:02X}").as_str());
+        }
+    }
+    encoded
+}
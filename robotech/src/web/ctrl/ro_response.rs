@@ -0,0 +1,96 @@
+use crate::ro::Ro;
+use axum::Json;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use tracing::warn;
+
+/// # 可自定义状态码及响应头的Ro响应
+///
+/// handler 直接返回 `Json<Ro<E>>` 时状态码固定为200，需要其它状态码或响应头时(如创建资源后返回
+/// `201 Created` 及 `Location`)可返回此结构体
+///
+/// ## 使用示例
+/// ```
+/// let ro = UserSvc::add::<DatabaseTransaction>(dto, current_user_id, None).await?;
+/// let location = ro.extra.as_ref().map(|vo| format!("/sys/user/{}", vo.id));
+/// Ok(RoResponse::created(ro, location))
+/// ```
+#[derive(Debug)]
+pub struct RoResponse<E> {
+    ro: Ro<E>,
+    status: StatusCode,
+    headers: HeaderMap,
+}
+
+impl<E> RoResponse<E> {
+    /// # 创建状态码为200的响应
+    pub fn new(ro: Ro<E>) -> Self {
+        Self {
+            ro,
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// # 创建资源成功的响应
+    ///
+    /// 状态码为201，并在 `location` 不为空时设置 `Location` 响应头指向新创建的资源
+    pub fn created(ro: Ro<E>, location: Option<String>) -> Self {
+        let response = Self::new(ro).status(StatusCode::CREATED);
+        match location {
+            Some(location) => response.location(location.as_str()),
+            None => response,
+        }
+    }
+
+    /// # 无内容的响应
+    ///
+    /// 成功时状态码为204且没有响应体，失败时仍然返回状态码为200的Ro JSON
+    ///
+    /// ## 使用示例
+    /// ```
+    /// UserSvc::reset_password(id, None).await?;
+    /// Ok(RoResponse::no_content(Ro::no_content("重置密码成功".to_string())))
+    /// ```
+    pub fn no_content(ro: Ro<E>) -> Self {
+        if ro.is_ok() {
+            Self::new(ro).status(StatusCode::NO_CONTENT)
+        } else {
+            Self::new(ro)
+        }
+    }
+
+    /// 设置状态码
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// 设置响应头
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// 设置 `Location` 响应头(值不合法时忽略)
+    pub fn location(self, location: &str) -> Self {
+        match HeaderValue::from_str(location) {
+            Ok(value) => self.header(header::LOCATION, value),
+            Err(e) => {
+                warn!("Location响应头的值不合法({location}): {e}");
+                self
+            }
+        }
+    }
+}
+
+impl<E: Serialize> IntoResponse for RoResponse<E> {
+    fn into_response(self) -> Response {
+        // 204不能有响应体
+        if self.status == StatusCode::NO_CONTENT {
+            return (self.status, self.headers).into_response();
+        }
+        (self.status, self.headers, Json(self.ro)).into_response()
+    }
+}
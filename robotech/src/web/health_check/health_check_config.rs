@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use wheel_rs::serde::duration_serde;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "kebab-case")]
@@ -9,6 +11,18 @@ pub struct HealthCheckConfig {
     /// 健康检查的uri(默认/health)
     #[serde(default = "uri_default")]
     pub uri: String,
+    /// 健康详情(各依赖的健康状态)的uri(默认/health/detail)，与健康检查的暴露方式相同
+    #[serde(default = "detail_uri_default")]
+    pub detail_uri: String,
+    /// 健康详情中每个依赖检查的超时时间(默认3秒)
+    #[serde(with = "duration_serde", default = "check_timeout_default")]
+    pub check_timeout: Duration,
+    /// 健康检查是否检查数据库(默认不检查)
+    ///
+    /// 启用后数据库不可用(包括启动期间还未初始化连接)时健康检查返回503，
+    /// 启动时等待健康检查通过也会等到数据库可用；没有数据库的服务保持关闭即可
+    #[serde(default)]
+    pub db_check: bool,
 }
 
 impl Default for HealthCheckConfig {
@@ -16,6 +30,9 @@ impl Default for HealthCheckConfig {
         Self {
             exposed: false,
             uri: uri_default(),
+            detail_uri: detail_uri_default(),
+            check_timeout: check_timeout_default(),
+            db_check: false,
         }
     }
 }
@@ -23,3 +40,11 @@ impl Default for HealthCheckConfig {
 fn uri_default() -> String {
     "/health".to_string()
 }
+
+fn detail_uri_default() -> String {
+    "/health/detail".to_string()
+}
+
+fn check_timeout_default() -> Duration {
+    Duration::from_secs(3)
+}
@@ -0,0 +1,185 @@
+use crate::ro::Ro;
+use crate::web::{ResponseCacheStats, get_response_cache_stats};
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use futures_util::future::join_all;
+use serde::Serialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// 依赖检查方法，返回Err时附带失败原因
+type HealthCheckFn =
+    Arc<dyn Fn() -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> + Send + Sync>;
+
+/// 登记的依赖检查
+struct HealthCheck {
+    name: String,
+    critical: bool,
+    check: HealthCheckFn,
+}
+
+/// 登记的依赖检查列表
+static HEALTH_CHECKS: RwLock<Vec<HealthCheck>> = RwLock::new(Vec::new());
+
+/// # 健康状态
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+/// # 依赖的健康状态
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyHealth {
+    /// 依赖名称
+    pub name: String,
+    /// 状态
+    pub status: HealthStatus,
+    /// 是否关键依赖(关键依赖DOWN时整体为DOWN)
+    pub critical: bool,
+    /// 检查耗时(毫秒)
+    pub latency_millis: u64,
+    /// 失败原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// # 健康状态详情
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthDetail {
+    /// 整体状态(任一关键依赖DOWN时为DOWN)
+    pub status: HealthStatus,
+    /// 各依赖的健康状态
+    pub dependencies: Vec<DependencyHealth>,
+    /// 响应缓存的命中统计(启用响应缓存时)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_cache: Option<ResponseCacheStats>,
+}
+
+#[derive(Clone)]
+pub(crate) struct HealthDetailState {
+    pub(crate) check_timeout: Duration,
+}
+
+/// # 登记依赖检查
+///
+/// 在应用启动时登记，健康详情端点会并发执行所有检查(每个检查都有超时时间)，
+/// 同名的检查会被替换。启用db特性时，初始化数据库连接后会自动登记名为 `db` 的关键检查
+///
+/// ## 参数
+/// * `name` - 依赖名称
+/// * `critical` - 是否关键依赖，关键依赖检查失败时整体状态为DOWN，否则只报告该依赖为DOWN
+/// * `check` - 检查方法，返回Err时附带失败原因
+///
+/// ## 使用示例
+/// ```
+/// register_health_check("user-svc", false, move || {
+///     let api_client = api_client.clone();
+///     async move {
+///         api_client.get::<()>("/health", None, None, None).await.map(|_| ()).map_err(|e| e.to_string())
+///     }
+/// });
+/// ```
+pub fn register_health_check<F, Fut>(name: impl Into<String>, critical: bool, check: F)
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), String>> + Send + 'static,
+{
+    let name = name.into();
+    let check: HealthCheckFn = Arc::new(move || Box::pin(check()));
+    match HEALTH_CHECKS.write() {
+        Ok(mut health_checks) => {
+            health_checks.retain(|health_check| health_check.name != name);
+            health_checks.push(HealthCheck {
+                name,
+                critical,
+                check,
+            });
+        }
+        Err(e) => warn!("登记依赖检查<{name}>失败: {e}"),
+    }
+}
+
+/// # 执行所有依赖检查
+///
+/// 并发执行登记的所有检查，超过超时时间的检查视为DOWN
+pub async fn run_health_checks(check_timeout: Duration) -> HealthDetail {
+    let health_checks: Vec<(String, bool, HealthCheckFn)> = match HEALTH_CHECKS.read() {
+        Ok(health_checks) => health_checks
+            .iter()
+            .map(|health_check| {
+                (
+                    health_check.name.clone(),
+                    health_check.critical,
+                    health_check.check.clone(),
+                )
+            })
+            .collect(),
+        Err(_) => vec![],
+    };
+
+    let dependencies = join_all(health_checks.into_iter().map(
+        |(name, critical, check)| async move {
+            let start = Instant::now();
+            let result = match tokio::time::timeout(check_timeout, check()).await {
+                Ok(result) => result,
+                Err(_) => Err(format!("检查超时({check_timeout:?})")),
+            };
+            let latency_millis = start.elapsed().as_millis() as u64;
+            if let Err(e) = &result {
+                warn!("依赖<{name}>健康检查失败: {e}");
+            }
+            DependencyHealth {
+                name,
+                status: if result.is_ok() {
+                    HealthStatus::Up
+                } else {
+                    HealthStatus::Down
+                },
+                critical,
+                latency_millis,
+                error: result.err(),
+            }
+        },
+    ))
+    .await;
+
+    let status = if dependencies
+        .iter()
+        .any(|dependency| dependency.critical && dependency.status == HealthStatus::Down)
+    {
+        HealthStatus::Down
+    } else {
+        HealthStatus::Up
+    };
+    HealthDetail {
+        status,
+        dependencies,
+        response_cache: get_response_cache_stats(),
+    }
+}
+
+/// # 健康详情端点
+///
+/// 返回各依赖的健康状态，整体状态为DOWN时响应状态码为503
+pub(crate) async fn health_detail(State(state): State<HealthDetailState>) -> Response {
+    let health_detail = run_health_checks(state.check_timeout).await;
+    match health_detail.status {
+        HealthStatus::Up => {
+            Json(Ro::success("健康".to_string()).extra(Some(health_detail))).into_response()
+        }
+        HealthStatus::Down => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(Ro::fail("关键依赖不可用".to_string()).extra(Some(health_detail))),
+        )
+            .into_response(),
+    }
+}
@@ -0,0 +1,77 @@
+use crate::env::APP_ENV;
+use crate::ro::Ro;
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Web服务器开始构建路由的时间(用于计算运行时长)
+static STARTED_AT: OnceLock<Instant> = OnceLock::new();
+
+/// # 健康信息
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthInfo {
+    /// 应用名称(执行文件名，不带后缀)
+    pub app_name: String,
+    /// 运行时长(秒)
+    pub uptime_secs: u64,
+}
+
+#[derive(Clone)]
+pub(crate) struct HealthState {
+    pub(crate) db_check: bool,
+    pub(crate) check_timeout: Duration,
+}
+
+/// 记录开始时间，重复调用不会覆盖
+pub(crate) fn mark_started() {
+    STARTED_AT.get_or_init(Instant::now);
+}
+
+/// # 健康检查端点
+///
+/// 启用数据库检查(`db-check`)时，获取数据库连接并执行一次ping，
+/// 数据库不可用(包括启动期间还未初始化连接)时响应状态码为503，
+/// 否则返回应用名称及运行时长
+pub(crate) async fn health(State(state): State<HealthState>) -> Response {
+    if state.db_check
+        && let Err(e) = check_db(state.check_timeout).await
+    {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(Ro::<HealthInfo>::fail(format!("数据库不可用: {e}"))),
+        )
+            .into_response();
+    }
+
+    let health_info = HealthInfo {
+        app_name: APP_ENV
+            .get()
+            .map(|app_env| app_env.app_file_name_without_ext.clone())
+            .unwrap_or_default(),
+        uptime_secs: STARTED_AT
+            .get()
+            .map(|started_at| started_at.elapsed().as_secs())
+            .unwrap_or_default(),
+    };
+    Json(Ro::success("健康".to_string()).extra(Some(health_info))).into_response()
+}
+
+/// 检查数据库是否可用
+#[cfg(feature = "db")]
+async fn check_db(check_timeout: Duration) -> Result<(), String> {
+    tokio::time::timeout(check_timeout, crate::db::ping_db())
+        .await
+        .map_err(|_| format!("检查超时({check_timeout:?})"))?
+        .map_err(|e| e.to_string())
+}
+
+/// 未启用db特性时，没有数据库需要检查
+#[cfg(not(feature = "db"))]
+async fn check_db(_check_timeout: Duration) -> Result<(), String> {
+    Ok(())
+}
@@ -1,2 +1,7 @@
 mod health_check_config;
+mod health_check_registry;
+mod health_handler;
 pub use health_check_config::HealthCheckConfig;
+pub use health_check_registry::*;
+pub use health_handler::HealthInfo;
+pub(crate) use health_handler::{HealthState, health, mark_started};
@@ -1,13 +1,8 @@
 use crate::env::{APP_ENV, AppEnv, EnvError};
-use crate::web::{HttpsConfig, WebServerError};
+use crate::web::{HttpConnConfig, HttpsConfig, WebServerError, serve_connection};
 use axum::Router;
-use axum::extract::ConnectInfo;
-use hyper::service::service_fn;
-use hyper_util::rt::{TokioExecutor, TokioIo};
-use hyper_util::server;
 use tracing::{debug, error};
 use rustls_pemfile::{certs, private_key};
-use std::convert::Infallible;
 use std::fs::File;
 use std::io::BufReader;
 use std::sync::{Arc, OnceLock};
@@ -20,19 +15,25 @@ use tokio_rustls::rustls::crypto::aws_lc_rs;
 
 static CRYPTO_PROVIDER_INITIALIZED: OnceLock<()> = OnceLock::new();
 
+/// # 在监听器上启动HTTPS服务
+///
+/// 使用 `HttpsConfig` 中的证书和私钥(相对路径基于程序目录)进行TLS握手，
+/// 证书或私钥未配置、无法读取或格式错误时返回相应的 `WebServerError`
 pub fn build_https(
     router: Router,
     tokio_listener: TcpListener,
     mut stop_web_service_receiver: Receiver<()>,
     https_config: HttpsConfig,
+    http_conn_config: HttpConnConfig,
 ) -> Result<JoinHandle<()>, WebServerError> {
     let HttpsConfig { cert, key, .. } = https_config;
     let AppEnv { app_dir, .. } = APP_ENV.get().ok_or(EnvError::GetAppEnv())?;
 
     CRYPTO_PROVIDER_INITIALIZED.get_or_init(|| {
-        aws_lc_rs::default_provider()
-            .install_default()
-            .expect("Failed to install rustls crypto provider");
+        // 其它依赖已经安装了默认的加密提供者时会返回错误，此时使用已安装的即可
+        if aws_lc_rs::default_provider().install_default().is_err() {
+            debug!("已经安装了rustls的默认加密提供者");
+        }
     });
 
     // 解析证书文件路径，如果是相对路径则基于程序目录
@@ -46,14 +47,12 @@ pub fn build_https(
         key_path = app_dir.join(key_path);
     }
     // 加载证书和私钥
-    let cert_file = &mut BufReader::new(
-        File::open(cert_path)
-            .map_err(|e| WebServerError::Config(format!("不能打开cert文件-{}", e.to_string())))?,
-    );
-    let key_file = &mut BufReader::new(
-        File::open(key_path)
-            .map_err(|e| WebServerError::Config(format!("不能打开key文件-{}", e.to_string())))?,
-    );
+    let cert_file = &mut BufReader::new(File::open(&cert_path).map_err(|e| {
+        WebServerError::Config(format!("不能打开cert文件<{}>-{}", cert_path.display(), e))
+    })?);
+    let key_file = &mut BufReader::new(File::open(&key_path).map_err(|e| {
+        WebServerError::Config(format!("不能打开key文件<{}>-{}", key_path.display(), e))
+    })?);
     // 加载证书链
     let cert_chain = certs(cert_file)
         .collect::<Result<Vec<_>, _>>()
@@ -73,8 +72,8 @@ pub fn build_https(
         .with_single_cert(cert_chain, key)
         .map_err(|e| WebServerError::ParseHttpsCert(format!("TLS配置失败: {}", e)))?;
 
-    // 配置 ALPN，支持 HTTP/2 和 HTTP/1.1
-    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    // 配置 ALPN，按协议版本协商 HTTP/2 和(或) HTTP/1.1
+    config.alpn_protocols = http_conn_config.http_version.alpn_protocols();
 
     let tls_acceptor = TlsAcceptor::from(Arc::new(config));
     let router = router.clone();
@@ -99,42 +98,19 @@ pub fn build_https(
             };
 
             let tls_acceptor = tls_acceptor.clone();
-            let mut stop_web_service_receiver = stop_web_service_receiver.resubscribe();
+            let stop_web_service_receiver = stop_web_service_receiver.resubscribe();
             tokio::spawn(async move {
                 // TLS 握手
                 match tls_acceptor.accept(tcp_stream).await {
                     Ok(tls_stream) => {
-                        // 将 TlsStream 包装为 Hyper 认识的 TokioIo
-                        let io = TokioIo::new(tls_stream);
-                        // 使用 Hyper 的 Builder 服务单个连接
-                        let hyper_service = service_fn(move |mut request| {
-                            let router = router.clone();
-                            request
-                                .extensions_mut()
-                                .insert(ConnectInfo(client_socket_addr));
-
-                            async move {
-                                use tower::ServiceExt;
-                                Ok::<_, Infallible>(router.oneshot(request).await.unwrap())
-                            }
-                        });
-                        let builder = server::conn::auto::Builder::new(TokioExecutor::new());
-                        let conn = builder.serve_connection_with_upgrades(io, hyper_service);
-                        let mut conn = std::pin::pin!(conn);
-                        tokio::select! {
-                            result = conn.as_mut() => {
-                                if let Err(e) = result {
-                                    error!("Connection error: {:#}", e);
-                                }
-                            }
-                            _ = stop_web_service_receiver.recv() => {
-                                conn.as_mut().graceful_shutdown();
-                                // 等连接真正关闭
-                                if let Err(e) = conn.as_mut().await {
-                                    error!("Connection error during shutdown from {}: {:#}", client_socket_addr, e);
-                                }
-                            }
-                        }
+                        serve_connection(
+                            tls_stream,
+                            router,
+                            client_socket_addr,
+                            http_conn_config,
+                            stop_web_service_receiver,
+                        )
+                        .await;
                     }
                     Err(e) => {
                         error!("TLS握手失败: {}", e);
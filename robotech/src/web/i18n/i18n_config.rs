@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// # 国际化配置
+///
+/// 按请求头 `Accept-Language` 选择语言，`Ro::success_key` 等构造方法按语言从消息目录中查找消息
+///
+/// ## 配置示例
+/// ```toml
+/// [web.i18n]
+/// default-locale = "zh-CN"
+///
+/// [web.i18n.messages.zh-CN]
+/// "query.success" = "查询成功"
+///
+/// [web.i18n.messages.en]
+/// "query.success" = "Query succeeded"
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct I18nConfig {
+    /// 是否启用(不设置此项默认为true)
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+    /// 默认语言(默认zh-CN)，请求头中没有支持的语言时使用
+    #[serde(default = "default_locale_default")]
+    pub default_locale: String,
+    /// 消息目录，语言 -> (消息键 -> 消息)
+    #[serde(default)]
+    pub messages: HashMap<String, HashMap<String, String>>,
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        Self {
+            enabled: enabled_default(),
+            default_locale: default_locale_default(),
+            messages: HashMap::new(),
+        }
+    }
+}
+
+fn enabled_default() -> bool {
+    true
+}
+fn default_locale_default() -> String {
+    "zh-CN".to_string()
+}
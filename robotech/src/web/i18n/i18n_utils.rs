@@ -0,0 +1,141 @@
+use crate::ctx::current_locale;
+use crate::ro::Ro;
+use crate::web::I18nConfig;
+use std::sync::RwLock;
+
+/// 国际化配置(构建路由时设置)
+static I18N_CONFIG: RwLock<Option<I18nConfig>> = RwLock::new(None);
+
+/// 设置国际化配置
+pub(crate) fn set_i18n_config(i18n_config: I18nConfig) {
+    if let Ok(mut write_lock) = I18N_CONFIG.write() {
+        *write_lock = Some(i18n_config);
+    }
+}
+
+/// # 翻译消息
+///
+/// 依次按指定的语言、其基础语言(如 `en-US` 的 `en`)、默认语言查找消息，
+/// 都找不到(或未启用国际化)时返回消息键本身
+///
+/// ## 参数
+/// * `key` - 消息键
+/// * `locale` - 语言，为None时使用当前请求的语言
+pub fn translate(key: &str, locale: Option<&str>) -> String {
+    let Ok(read_lock) = I18N_CONFIG.read() else {
+        return key.to_string();
+    };
+    let Some(I18nConfig {
+        default_locale,
+        messages,
+        ..
+    }) = read_lock.as_ref()
+    else {
+        return key.to_string();
+    };
+
+    let request_locale = match locale {
+        Some(_) => None,
+        None => current_locale(),
+    };
+    let locale = locale.or(request_locale.as_deref());
+    let base_locale = locale.and_then(|locale| locale.split_once('-').map(|(base, _)| base));
+    [locale, base_locale, Some(default_locale.as_str())]
+        .into_iter()
+        .flatten()
+        .find_map(|locale| {
+            messages
+                .iter()
+                .find(|(catalog_locale, _)| catalog_locale.eq_ignore_ascii_case(locale))
+                .and_then(|(_, catalog)| catalog.get(key))
+        })
+        .cloned()
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// # 按 `Accept-Language` 请求头选择语言
+///
+/// 按权重(q值)从高到低选择第一个消息目录中支持的语言(支持的语言包括其基础语言，如请求 `en-US` 时可以匹配 `en`)，
+/// 没有支持的语言时返回默认语言
+pub(crate) fn resolve_locale(accept_language: Option<&str>) -> String {
+    let Ok(read_lock) = I18N_CONFIG.read() else {
+        return String::new();
+    };
+    let Some(I18nConfig {
+        default_locale,
+        messages,
+        ..
+    }) = read_lock.as_ref()
+    else {
+        return String::new();
+    };
+
+    let mut languages: Vec<(&str, f32)> = accept_language
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';');
+            let language = parts.next()?.trim();
+            let quality = parts
+                .find_map(|part| part.trim().strip_prefix("q="))
+                .map(|quality| quality.trim().parse::<f32>().unwrap_or(0.0))
+                .unwrap_or(1.0);
+            (!language.is_empty() && language != "*" && quality > 0.0)
+                .then_some((language, quality))
+        })
+        .collect();
+    // 稳定排序，权重相同时保持请求头中的顺序
+    languages.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+    languages
+        .into_iter()
+        .find_map(|(language, _)| {
+            let base_language = language.split_once('-').map(|(base, _)| base);
+            [Some(language), base_language]
+                .into_iter()
+                .flatten()
+                .find_map(|language| {
+                    messages
+                        .keys()
+                        .find(|catalog_locale| catalog_locale.eq_ignore_ascii_case(language))
+                })
+        })
+        .cloned()
+        .unwrap_or_else(|| default_locale.clone())
+}
+
+impl<E> Ro<E> {
+    /// # 按消息键创建一个成功的响应对象
+    ///
+    /// 消息按当前请求的语言翻译，找不到翻译时消息为消息键本身
+    ///
+    /// ## 参数
+    /// * `key` - 消息键
+    pub fn success_key(key: &str) -> Self {
+        Self::success(translate(key, None))
+    }
+
+    /// # 按消息键创建一个非法参数的响应对象
+    ///
+    /// ## 参数
+    /// * `key` - 消息键
+    pub fn illegal_argument_key(key: &str) -> Self {
+        Self::illegal_argument(translate(key, None))
+    }
+
+    /// # 按消息键创建一个警告的响应对象
+    ///
+    /// ## 参数
+    /// * `key` - 消息键
+    pub fn warn_key(key: &str) -> Self {
+        Self::warn(translate(key, None))
+    }
+
+    /// # 按消息键创建一个失败的响应对象
+    ///
+    /// ## 参数
+    /// * `key` - 消息键
+    pub fn fail_key(key: &str) -> Self {
+        Self::fail(translate(key, None))
+    }
+}
@@ -0,0 +1,5 @@
+mod i18n_config;
+mod i18n_utils;
+
+pub use i18n_config::*;
+pub use i18n_utils::*;
@@ -0,0 +1,73 @@
+use crate::web::ClientIp;
+use axum::extract::ConnectInfo;
+use axum::{
+    extract::{Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use ipnet::IpNet;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+const X_FORWARDED_FOR: &str = "X-Forwarded-For";
+const X_REAL_IP: &str = "X-Real-IP";
+
+#[derive(Clone)]
+pub struct ClientIpState {
+    pub(crate) trusted_proxies: Arc<Vec<IpNet>>,
+}
+
+/// # 客户端IP中间件
+///
+/// 解析客户端IP并放入请求的扩展中(handler可通过 `ClientIp` 提取)，信任模型如下:
+/// - 只有TCP连接的对端IP在 `trusted-proxies` 中时，才会读取代理请求头，否则客户端IP就是对端IP，
+///   所以直接访问服务的客户端无法通过伪造请求头冒充其它IP
+/// - 对端是可信代理时，从右往左遍历 `X-Forwarded-For`(每经过一层代理会在右边追加上一跳的IP)，
+///   跳过可信代理的IP，第一个不可信的IP即为客户端IP(更左边的值可能是客户端伪造的，不予采信)；
+///   遇到格式不正确的值时停止，取最后一个可信代理的IP(没有时为对端IP)
+/// - 只有没有 `X-Forwarded-For` 时才读取 `X-Real-IP`，都没有(或格式不正确)时使用对端IP
+pub async fn client_ip_middleware(
+    State(state): State<ClientIpState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let client_ip = resolve_client_ip(addr.ip(), request.headers(), &state.trusted_proxies);
+    request.extensions_mut().insert(ClientIp(client_ip));
+    next.run(request).await
+}
+
+/// 解析客户端IP
+fn resolve_client_ip(peer_ip: IpAddr, headers: &HeaderMap, trusted_proxies: &[IpNet]) -> IpAddr {
+    let is_trusted = |ip: &IpAddr| trusted_proxies.iter().any(|ip_net| ip_net.contains(ip));
+    if !is_trusted(&peer_ip) {
+        return peer_ip;
+    }
+
+    // 可能有多个X-Forwarded-For请求头，按顺序拼接
+    let forwarded_values: Vec<_> = headers.get_all(X_FORWARDED_FOR).iter().collect();
+    if !forwarded_values.is_empty() {
+        // 最后一个可信的IP(全部都是可信代理时，即为最左边的IP)
+        let mut last_trusted_ip = peer_ip;
+        let forwarded_ips = forwarded_values
+            .iter()
+            .rev()
+            .flat_map(|value| value.to_str().unwrap_or_default().rsplit(','));
+        for forwarded_ip in forwarded_ips {
+            match forwarded_ip.trim().parse::<IpAddr>() {
+                Ok(ip) if is_trusted(&ip) => last_trusted_ip = ip,
+                Ok(ip) => return ip,
+                // 格式不正确时，更左边的值都不可信
+                Err(_) => break,
+            }
+        }
+        return last_trusted_ip;
+    }
+
+    headers
+        .get(X_REAL_IP)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<IpAddr>().ok())
+        .unwrap_or(peer_ip)
+}
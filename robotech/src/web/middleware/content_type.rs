@@ -0,0 +1,77 @@
+use crate::ro::Ro;
+use crate::web::ContentTypeConfig;
+use axum::{
+    Json,
+    extract::{Request, State},
+    http::{Method, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct ContentTypeState {
+    pub(crate) config: Arc<ContentTypeConfig>,
+}
+
+pub async fn content_type_middleware(
+    State(state): State<ContentTypeState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method();
+    if !matches!(*method, Method::POST | Method::PUT | Method::PATCH) || !has_body(&request) {
+        return next.run(request).await;
+    }
+
+    let request_method = method.to_string().to_uppercase();
+    let request_uri = request.uri().path();
+    let config = &state.config;
+    if config
+        .exempt_urns
+        .iter()
+        .any(|urn| urn.matches(&request_method, request_uri))
+    {
+        return next.run(request).await;
+    }
+
+    // 只比较媒体类型，忽略 charset 等参数
+    let media_type = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_lowercase())
+        .unwrap_or_default();
+    let allowed = media_type == "application/json"
+        || (media_type == "multipart/form-data"
+            && config
+                .multipart_urns
+                .iter()
+                .any(|urn| urn.matches(&request_method, request_uri)));
+    if !allowed {
+        return (
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Json(
+                Ro::<()>::illegal_argument("不支持的内容类型".to_string())
+                    .detail(Some(format!("Content-Type: {media_type}"))),
+            ),
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
+/// 判断请求是否带有请求体
+fn has_body(request: &Request) -> bool {
+    let headers = request.headers();
+    if headers.contains_key(header::TRANSFER_ENCODING) {
+        return true;
+    }
+    headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .is_some_and(|content_length| content_length > 0)
+}
@@ -1,3 +1,4 @@
+use crate::web::ClientIp;
 use axum::extract::ConnectInfo;
 use axum::{
     extract::{Request, State},
@@ -22,7 +23,12 @@ pub async fn ip_ban_middleware(
     request: Request,
     next: Next,
 ) -> Response {
-    let src_ip = addr.ip();
+    // 配置了可信代理时使用解析出的客户端IP
+    let src_ip = request
+        .extensions()
+        .get::<ClientIp>()
+        .map(|ClientIp(client_ip)| *client_ip)
+        .unwrap_or(addr.ip());
 
     let IpBanState {
         ip_white_list,
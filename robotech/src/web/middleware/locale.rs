@@ -0,0 +1,27 @@
+use crate::ctx::scope_locale;
+use crate::web::resolve_locale;
+use axum::{
+    extract::Request,
+    http::{HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+
+/// # 语言中间件
+///
+/// 按请求头中的 `Accept-Language` 选择语言，在请求处理期间放入上下文，
+/// 以便 `Ro::success_key` 等构造方法按该语言翻译消息，
+/// 响应内容随语言变化，所以响应头中会添加 `Vary: Accept-Language`(避免被共享缓存错误复用)
+pub async fn locale_middleware(request: Request, next: Next) -> Response {
+    let locale = resolve_locale(
+        request
+            .headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok()),
+    );
+    let mut response = scope_locale(locale, next.run(request)).await;
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_static("accept-language"));
+    response
+}
@@ -1,9 +1,27 @@
+mod client_ip;
+mod content_type;
 mod forbidden_urns;
 mod ip_ban;
 mod local_only;
 mod local_only_urns;
+mod locale;
+mod normalize_path;
+mod rate_limit;
+#[cfg(feature = "db")]
+mod read_primary;
+mod request_id;
+mod response_cache;
 
+pub(crate) use client_ip::*;
+pub(crate) use content_type::*;
 pub(crate) use forbidden_urns::*;
 pub(crate) use ip_ban::*;
 pub(crate) use local_only::*;
 pub(crate) use local_only_urns::*;
+pub(crate) use locale::*;
+pub(crate) use normalize_path::*;
+pub(crate) use rate_limit::*;
+#[cfg(feature = "db")]
+pub(crate) use read_primary::*;
+pub(crate) use request_id::*;
+pub(crate) use response_cache::*;
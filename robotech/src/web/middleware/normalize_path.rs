@@ -0,0 +1,55 @@
+use crate::web::NormalizePathMode;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, Uri, uri::PathAndQuery},
+    middleware::Next,
+    response::Response,
+};
+
+pub async fn normalize_path_middleware(
+    State(normalize_path_mode): State<NormalizePathMode>,
+    mut request: Request<Body>,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    let normalized_path = normalize_path(path, normalize_path_mode);
+    if normalized_path != path {
+        let mut parts = request.uri().clone().into_parts();
+        let path_and_query = match request.uri().query() {
+            Some(query) => format!("{normalized_path}?{query}"),
+            None => normalized_path,
+        };
+        if let Ok(path_and_query) = PathAndQuery::try_from(path_and_query) {
+            parts.path_and_query = Some(path_and_query);
+            if let Ok(uri) = Uri::from_parts(parts) {
+                *request.uri_mut() = uri;
+            }
+        }
+    }
+    next.run(request).await
+}
+
+/// 按模式规范化路径，根路径 `/` 保持不变
+fn normalize_path(path: &str, normalize_path_mode: NormalizePathMode) -> String {
+    let mut normalized_path = match normalize_path_mode {
+        NormalizePathMode::Off => return path.to_string(),
+        NormalizePathMode::TrimTrailing | NormalizePathMode::MergeSlashes => {
+            // 合并连续的斜杠
+            let mut merged = String::with_capacity(path.len());
+            for c in path.chars() {
+                if c == '/' && merged.ends_with('/') {
+                    continue;
+                }
+                merged.push(c);
+            }
+            merged
+        }
+    };
+    if normalize_path_mode == NormalizePathMode::TrimTrailing {
+        while normalized_path.len() > 1 && normalized_path.ends_with('/') {
+            normalized_path.pop();
+        }
+    }
+    normalized_path
+}
@@ -0,0 +1,158 @@
+use crate::cst::user_id_cst::USER_ID_HEADER_NAME;
+use crate::ro::Ro;
+use crate::web::{ClientIp, RateLimitConfig, WebServerError};
+use axum::Json;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use ipnet::IpNet;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tracing::debug;
+
+/// 令牌桶
+struct TokenBucket {
+    /// 剩余令牌数
+    tokens: f64,
+    /// 上次补充令牌的时间
+    refilled_at: Instant,
+}
+
+/// 各客户端的令牌桶
+pub(crate) struct TokenBuckets {
+    buckets: HashMap<String, TokenBucket>,
+    /// 每秒补充的令牌数
+    rate: f64,
+    /// 令牌桶容量
+    burst: f64,
+    /// 最多记录的客户端数
+    max_clients: usize,
+}
+
+impl TokenBuckets {
+    /// 取一个令牌，取不到时返回需要等待的秒数
+    fn acquire(&mut self, key: &str) -> Result<(), u64> {
+        let now = Instant::now();
+        if !self.buckets.contains_key(key) && self.buckets.len() >= self.max_clients {
+            // 清理已经回满的令牌桶(与新建的令牌桶等价)
+            let (rate, burst) = (self.rate, self.burst);
+            self.buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.refilled_at).as_secs_f64() * rate < burst
+            });
+        }
+        let bucket = self
+            .buckets
+            .entry(key.to_string())
+            .or_insert_with(|| TokenBucket {
+                tokens: self.burst,
+                refilled_at: now,
+            });
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / self.rate).ceil() as u64)
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitState {
+    /// 不限流的路径(健康检查)
+    pub(crate) exempt_paths: Arc<Vec<String>>,
+    /// 可信代理(对端是可信代理时，才采信用户ID请求头)
+    pub(crate) trusted_proxies: Arc<Vec<IpNet>>,
+    pub(crate) buckets: Arc<Mutex<TokenBuckets>>,
+}
+
+impl RateLimitState {
+    pub fn new(
+        rate_limit_config: &RateLimitConfig,
+        exempt_paths: Vec<String>,
+        trusted_proxies: Vec<IpNet>,
+    ) -> Result<Self, WebServerError> {
+        if rate_limit_config.requests_per_second == 0 || rate_limit_config.burst == 0 {
+            Err(WebServerError::Config(
+                "限流的requests-per-second及burst必须大于0".to_string(),
+            ))?;
+        }
+        Ok(Self {
+            exempt_paths: Arc::new(exempt_paths),
+            trusted_proxies: Arc::new(trusted_proxies),
+            buckets: Arc::new(Mutex::new(TokenBuckets {
+                buckets: HashMap::new(),
+                rate: rate_limit_config.requests_per_second as f64,
+                burst: rate_limit_config.burst as f64,
+                max_clients: rate_limit_config.max_clients,
+            })),
+        })
+    }
+}
+
+/// # 限流中间件
+///
+/// 按客户端IP(配置了可信代理时使用解析出的客户端IP)限流，
+/// 只有对端是可信代理(如网关)时才采信 `X-User-Id` 请求头按用户ID限流
+/// (客户端直接访问时可以伪造该请求头，每次换一个用户ID就能绕过限流)，
+/// 超出限制时返回429及 `Retry-After` 响应头
+pub async fn rate_limit_middleware(
+    State(state): State<RateLimitState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if state
+        .exempt_paths
+        .iter()
+        .any(|path| path == request.uri().path())
+    {
+        return next.run(request).await;
+    }
+
+    let is_from_trusted_proxy = state
+        .trusted_proxies
+        .iter()
+        .any(|ip_net| ip_net.contains(&addr.ip()));
+    let user_id = request
+        .headers()
+        .get(USER_ID_HEADER_NAME)
+        .and_then(|value| value.to_str().ok());
+    let key = match user_id {
+        Some(user_id) if is_from_trusted_proxy => format!("user:{user_id}"),
+        _ => {
+            let src_ip = request
+                .extensions()
+                .get::<ClientIp>()
+                .map(|ClientIp(client_ip)| *client_ip)
+                .unwrap_or(addr.ip());
+            format!("ip:{src_ip}")
+        }
+    };
+
+    let acquired = match state.buckets.lock() {
+        Ok(mut buckets) => buckets.acquire(&key),
+        // 锁中毒时不限流
+        Err(_) => Ok(()),
+    };
+    match acquired {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            debug!("请求过于频繁: {key}");
+            let mut response = (
+                StatusCode::TOO_MANY_REQUESTS,
+                Json(Ro::<()>::fail("请求过于频繁，请稍后再试".to_string())),
+            )
+                .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after.max(1)));
+            response
+        }
+    }
+}
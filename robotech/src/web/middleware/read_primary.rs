@@ -0,0 +1,10 @@
+use crate::ctx::scope_read_primary;
+use axum::{extract::Request, middleware::Next, response::Response};
+
+/// # 读主库标记中间件
+///
+/// 为每个请求建立读主库标记的上下文，请求中执行过写操作后，
+/// 后续通过 `get_read_db_conn` 的读取改为从主库读取，避免读库同步延迟而读不到刚写入的数据
+pub async fn read_primary_middleware(request: Request, next: Next) -> Response {
+    scope_read_primary(next.run(request)).await
+}
@@ -0,0 +1,25 @@
+use crate::cst::request_id_cst::REQUEST_ID_HEADER_NAME;
+use crate::ctx::{new_request_id, scope_request_id};
+use axum::{extract::Request, http::HeaderValue, middleware::Next, response::Response};
+
+/// # 请求ID中间件
+///
+/// 读取请求头中的 `X-Request-Id`(没有则生成一个)，在请求处理期间放入上下文，
+/// 以便 `ApiClient` 调用下游服务时自动带上，并在响应头中返回
+pub async fn request_id_middleware(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER_NAME)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_string())
+        .unwrap_or_else(new_request_id);
+
+    let mut response = scope_request_id(request_id.clone(), next.run(request)).await;
+    if let Ok(header_value) = HeaderValue::from_str(&request_id) {
+        response
+            .headers_mut()
+            .insert(REQUEST_ID_HEADER_NAME, header_value);
+    }
+    response
+}
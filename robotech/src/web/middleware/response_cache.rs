@@ -0,0 +1,220 @@
+use crate::cst::user_id_cst::USER_ID_HEADER_NAME;
+use crate::ctx::current_locale;
+use crate::ro::Ro;
+use crate::web::ResponseCacheConfig;
+use axum::body::{Body, Bytes, HttpBody, to_bytes};
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// 缓存的响应
+struct CachedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    /// 过期时间
+    expires_at: Instant,
+    /// 最后访问序号(用于LRU淘汰)
+    access_seq: u64,
+}
+
+/// 有容量上限的LRU缓存
+#[derive(Default)]
+pub(crate) struct ResponseLruCache {
+    entries: HashMap<String, CachedResponse>,
+    /// 访问序号 -> 缓存键，序号最小的就是最久未访问的
+    access_order: BTreeMap<u64, String>,
+    seq: u64,
+}
+
+impl ResponseLruCache {
+    fn get(&mut self, key: &str) -> Option<(StatusCode, HeaderMap, Bytes)> {
+        let entry = self.entries.get_mut(key)?;
+        if entry.expires_at <= Instant::now() {
+            // 已过期，顺便清除
+            let access_seq = entry.access_seq;
+            self.entries.remove(key);
+            self.access_order.remove(&access_seq);
+            return None;
+        }
+        self.seq += 1;
+        self.access_order.remove(&entry.access_seq);
+        entry.access_seq = self.seq;
+        self.access_order.insert(self.seq, key.to_string());
+        Some((entry.status, entry.headers.clone(), entry.body.clone()))
+    }
+
+    fn put(
+        &mut self,
+        key: String,
+        capacity: usize,
+        ttl: Duration,
+        status: StatusCode,
+        headers: HeaderMap,
+        body: Bytes,
+    ) {
+        if capacity == 0 {
+            return;
+        }
+        if let Some(old) = self.entries.remove(&key) {
+            self.access_order.remove(&old.access_seq);
+        }
+        // 超出容量，淘汰最久未访问的
+        while self.entries.len() >= capacity {
+            match self.access_order.pop_first() {
+                Some((_, oldest_key)) => {
+                    self.entries.remove(&oldest_key);
+                }
+                None => break,
+            }
+        }
+        self.seq += 1;
+        self.access_order.insert(self.seq, key.clone());
+        self.entries.insert(
+            key,
+            CachedResponse {
+                status,
+                headers,
+                body,
+                expires_at: Instant::now() + ttl,
+                access_seq: self.seq,
+            },
+        );
+    }
+}
+
+#[derive(Clone)]
+pub struct ResponseCacheState {
+    pub(crate) response_cache_config: Arc<ResponseCacheConfig>,
+    pub(crate) cache: Arc<Mutex<ResponseLruCache>>,
+    pub(crate) hits: Arc<AtomicU64>,
+    pub(crate) misses: Arc<AtomicU64>,
+}
+
+impl ResponseCacheState {
+    pub fn new(response_cache_config: ResponseCacheConfig) -> Self {
+        Self {
+            response_cache_config: Arc::new(response_cache_config),
+            cache: Arc::new(Mutex::new(ResponseLruCache::default())),
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// 命中次数
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// 未命中次数
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+/// 客户端是否要求不使用缓存(Cache-Control: no-cache / no-store)
+fn is_no_cache(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CACHE_CONTROL)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| {
+            let value = value.to_lowercase();
+            value.contains("no-cache") || value.contains("no-store")
+        })
+        .unwrap_or(false)
+}
+
+pub async fn response_cache_middleware(
+    State(state): State<ResponseCacheState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    // 只缓存GET请求
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+
+    let config = &state.response_cache_config;
+    let ttl = config.ttl_of(Method::GET.as_str(), request.uri().path());
+    if ttl.is_zero() {
+        return next.run(request).await;
+    }
+
+    // 缓存键: method + path + query (+ 用户ID) (+ 语言)
+    let mut key = format!("GET:{}", request.uri());
+    if config.by_user {
+        let user_id = request
+            .headers()
+            .get(USER_ID_HEADER_NAME)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        key = format!("{key}#{user_id}");
+    }
+    // 启用国际化时，消息按请求的语言翻译，不同语言的响应分开缓存
+    if let Some(locale) = current_locale() {
+        key = format!("{key}@{locale}");
+    }
+
+    if !is_no_cache(request.headers()) {
+        let cached = state
+            .cache
+            .lock()
+            .ok()
+            .and_then(|mut cache| cache.get(&key));
+        if let Some((status, headers, body)) = cached {
+            state.hits.fetch_add(1, Ordering::Relaxed);
+            debug!("响应缓存命中: {key}");
+            let mut response = Response::new(Body::from(body));
+            *response.status_mut() = status;
+            *response.headers_mut() = headers;
+            return response;
+        }
+    }
+    state.misses.fetch_add(1, Ordering::Relaxed);
+
+    let response = next.run(request).await;
+    // 带有Set-Cookie的响应是针对当前用户的，不能缓存后返回给其他用户
+    if !response.status().is_success() || response.headers().contains_key(header::SET_COOKIE) {
+        return response;
+    }
+
+    // 响应体过大或大小未知(如流式响应)时不缓存
+    let (parts, body) = response.into_parts();
+    if body
+        .size_hint()
+        .upper()
+        .is_none_or(|size| size > config.max_body_size as u64)
+    {
+        return Response::from_parts(parts, body);
+    }
+    let bytes = match to_bytes(body, config.max_body_size).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!("读取响应体失败: {e}");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    // 只缓存成功的Ro
+    if let Ok(ro) = serde_json::from_slice::<Ro<serde_json::Value>>(&bytes)
+        && ro.is_ok()
+        && let Ok(mut cache) = state.cache.lock()
+    {
+        cache.put(
+            key,
+            config.capacity,
+            ttl,
+            parts.status,
+            parts.headers.clone(),
+            bytes.clone(),
+        );
+    }
+
+    Response::from_parts(parts, Body::from(bytes))
+}
@@ -1,13 +1,25 @@
+mod config_dump;
+mod content_type;
 mod cors;
 mod ctrl;
 mod health_check;
 mod https;
+mod i18n;
 pub mod middleware;
+mod rate_limit;
+mod response_cache;
 mod server;
+mod static_files;
 
 // 重新导出结构体，简化外部引用
+pub use config_dump::*;
+pub use content_type::*;
 pub(crate) use cors::*;
 pub use ctrl::*;
-pub(crate) use health_check::*;
+pub use health_check::*;
 pub(crate) use https::*;
+pub use i18n::*;
+pub use rate_limit::*;
+pub use response_cache::*;
 pub use server::*;
+pub use static_files::*;
@@ -0,0 +1,3 @@
+mod rate_limit_config;
+
+pub use rate_limit_config::*;
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// # 限流配置
+///
+/// 按客户端(对端是可信代理且有 `X-User-Id` 请求头时按用户ID，否则按客户端IP)使用令牌桶限流，
+/// 超出限制时返回429，健康检查不受限制
+///
+/// ## 注意事项
+/// - 令牌桶保存在进程内存中，多实例部署时每个实例单独计算
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct RateLimitConfig {
+    /// 是否启用(不设置此项默认为true)
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+    /// 每个客户端每秒允许的请求数(默认10)
+    #[serde(default = "requests_per_second_default")]
+    pub requests_per_second: u32,
+    /// 允许的突发请求数(默认20)，即令牌桶的容量
+    #[serde(default = "burst_default")]
+    pub burst: u32,
+    /// 最多记录多少个客户端的令牌桶(默认10000)，超出后清理已经回满的令牌桶
+    #[serde(default = "max_clients_default")]
+    pub max_clients: usize,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: enabled_default(),
+            requests_per_second: requests_per_second_default(),
+            burst: burst_default(),
+            max_clients: max_clients_default(),
+        }
+    }
+}
+
+fn enabled_default() -> bool {
+    true
+}
+fn requests_per_second_default() -> u32 {
+    10
+}
+fn burst_default() -> u32 {
+    20
+}
+fn max_clients_default() -> usize {
+    10000
+}
@@ -0,0 +1,5 @@
+mod response_cache_config;
+mod response_cache_stats;
+
+pub use response_cache_config::*;
+pub use response_cache_stats::*;
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize, Serializer};
+use std::time::Duration;
+use wheel_rs::serde::duration_serde;
+use wheel_rs::urn_utils::Urn;
+
+/// # 响应缓存配置
+///
+/// 对GET请求的成功响应(`Ro`的result为Success)进行缓存，缓存键为 method + path + query(可选加上当前用户ID)
+///
+/// ## 注意事项
+/// - 缓存保存在进程内存中(有容量上限的LRU)，只适用于单实例部署
+/// - 多实例部署时各实例的缓存互不相通，数据变更后其它实例仍可能返回旧数据(直到TTL过期)，
+///   所以只建议缓存变化缓慢的参考数据，并设置较短的TTL
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct ResponseCacheConfig {
+    /// 是否启用(不设置此项默认为true)
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+    /// 默认缓存时间(默认60秒，设置为0则只缓存routes中配置的路由)
+    #[serde(with = "duration_serde", default = "ttl_default")]
+    pub ttl: Duration,
+    /// 最多缓存多少条响应(默认1024)，超出后淘汰最久未访问的
+    #[serde(default = "capacity_default")]
+    pub capacity: usize,
+    /// 可缓存的单个响应体的最大字节数(默认1MB)，超出则不缓存
+    #[serde(default = "max_body_size_default")]
+    pub max_body_size: usize,
+    /// 缓存键是否包含当前用户ID(默认false，返回数据与用户相关时需要开启)
+    #[serde(default)]
+    pub by_user: bool,
+    /// 按路由覆盖缓存时间(按顺序匹配第一个)，ttl设置为0表示该路由不缓存
+    #[serde(default)]
+    pub routes: Vec<ResponseCacheRouteConfig>,
+}
+
+/// # 单个路由的缓存配置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct ResponseCacheRouteConfig {
+    /// 匹配的URN
+    #[serde(serialize_with = "urn_serialize")]
+    pub urn: Urn,
+    /// 缓存时间
+    #[serde(with = "duration_serde")]
+    pub ttl: Duration,
+}
+
+impl ResponseCacheConfig {
+    /// # 获取请求对应的缓存时间
+    ///
+    /// 优先使用routes中第一个匹配的配置，没有匹配的则使用默认的ttl
+    pub fn ttl_of(&self, method: &str, path: &str) -> Duration {
+        self.routes
+            .iter()
+            .find(|route| route.urn.matches(method, path))
+            .map(|route| route.ttl)
+            .unwrap_or(self.ttl)
+    }
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: enabled_default(),
+            ttl: ttl_default(),
+            capacity: capacity_default(),
+            max_body_size: max_body_size_default(),
+            by_user: false,
+            routes: vec![],
+        }
+    }
+}
+
+fn urn_serialize<S: Serializer>(urn: &Urn, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(urn)
+}
+
+fn enabled_default() -> bool {
+    true
+}
+fn ttl_default() -> Duration {
+    Duration::from_secs(60)
+}
+fn capacity_default() -> usize {
+    1024
+}
+fn max_body_size_default() -> usize {
+    1024 * 1024
+}
@@ -0,0 +1,34 @@
+use crate::web::middleware::ResponseCacheState;
+use serde::Serialize;
+use std::sync::RwLock;
+
+/// 当前路由使用的响应缓存(构建路由时设置，未启用时为None)
+static RESPONSE_CACHE_STATE: RwLock<Option<ResponseCacheState>> = RwLock::new(None);
+
+/// # 响应缓存的命中统计
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseCacheStats {
+    /// 命中次数
+    pub hits: u64,
+    /// 未命中次数
+    pub misses: u64,
+}
+
+/// 设置当前路由使用的响应缓存
+pub(crate) fn set_response_cache_state(response_cache_state: Option<ResponseCacheState>) {
+    if let Ok(mut write_lock) = RESPONSE_CACHE_STATE.write() {
+        *write_lock = response_cache_state;
+    }
+}
+
+/// # 获取响应缓存的命中统计
+///
+/// 未启用响应缓存时返回None，健康详情端点会附带该统计
+pub fn get_response_cache_stats() -> Option<ResponseCacheStats> {
+    let read_lock = RESPONSE_CACHE_STATE.read().ok()?;
+    read_lock.as_ref().map(|state| ResponseCacheStats {
+        hits: state.hits(),
+        misses: state.misses(),
+    })
+}
@@ -0,0 +1,235 @@
+use crate::web::{HttpVersion, WebServerConfig};
+use axum::Router;
+use axum::extract::ConnectInfo;
+use hyper::service::service_fn;
+use hyper_util::rt::{TokioExecutor, TokioIo, TokioTimer};
+use hyper_util::server::conn::auto;
+use std::convert::Infallible;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, UnixListener, UnixStream};
+use tokio::sync::broadcast::Receiver;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+
+impl HttpVersion {
+    /// TLS握手时通过ALPN协商的协议列表
+    pub(crate) fn alpn_protocols(&self) -> Vec<Vec<u8>> {
+        match self {
+            HttpVersion::Http1 => vec![b"http/1.1".to_vec()],
+            HttpVersion::Http2 => vec![b"h2".to_vec()],
+            HttpVersion::Auto => vec![b"h2".to_vec(), b"http/1.1".to_vec()],
+        }
+    }
+}
+
+/// # HTTP连接配置
+///
+/// 取自 `WebServerConfig`，用于创建服务每个连接的构建器
+#[derive(Debug, Clone, Copy)]
+pub struct HttpConnConfig {
+    /// HTTP协议版本
+    pub http_version: HttpVersion,
+    /// 连接保持
+    pub keep_alive: Option<Duration>,
+    /// 读取HTTP/1.1请求头的超时时间
+    pub client_request_timeout: Option<Duration>,
+}
+
+impl From<&WebServerConfig> for HttpConnConfig {
+    fn from(web_server_config: &WebServerConfig) -> Self {
+        Self {
+            http_version: web_server_config.http_version,
+            keep_alive: web_server_config.keep_alive,
+            client_request_timeout: web_server_config.client_request_timeout,
+        }
+    }
+}
+
+impl HttpConnConfig {
+    /// 是否需要自行服务每个连接(限定了协议版本或调整了连接设置，Axum服务无法设置)
+    pub(crate) fn requires_conn_builder(&self) -> bool {
+        self.http_version != HttpVersion::Auto
+            || self.keep_alive.is_some()
+            || self.client_request_timeout.is_some()
+    }
+
+    /// 创建按协议版本限制及连接设置的连接构建器
+    fn conn_builder(&self) -> auto::Builder<TokioExecutor> {
+        let mut builder = auto::Builder::new(TokioExecutor::new());
+        if let Some(keep_alive) = self.keep_alive {
+            if keep_alive.is_zero() {
+                builder.http1().keep_alive(false);
+            } else {
+                builder
+                    .http2()
+                    .timer(TokioTimer::new())
+                    .keep_alive_interval(keep_alive)
+                    .keep_alive_timeout(keep_alive);
+            }
+        }
+        if let Some(client_request_timeout) = self.client_request_timeout {
+            builder
+                .http1()
+                .timer(TokioTimer::new())
+                .header_read_timeout(client_request_timeout);
+        }
+        match self.http_version {
+            HttpVersion::Http1 => builder.http1_only(),
+            HttpVersion::Http2 => builder.http2_only(),
+            HttpVersion::Auto => builder,
+        }
+    }
+}
+
+/// # 在单个连接上提供服务
+///
+/// 按协议版本服务已建立的连接(TLS连接或明文TCP连接)，收到停止信号时优雅关闭连接
+pub(crate) async fn serve_connection<I>(
+    io: I,
+    router: Router,
+    client_socket_addr: SocketAddr,
+    http_conn_config: HttpConnConfig,
+    mut stop_web_service_receiver: Receiver<()>,
+) where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    // 包装为 Hyper 认识的 TokioIo
+    let io = TokioIo::new(io);
+    // 使用 Hyper 的 Builder 服务单个连接
+    let hyper_service = service_fn(move |mut request| {
+        let router = router.clone();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(client_socket_addr));
+
+        async move {
+            use tower::ServiceExt;
+            Ok::<_, Infallible>(router.oneshot(request).await.unwrap())
+        }
+    });
+    let builder = http_conn_config.conn_builder();
+    let conn = builder.serve_connection_with_upgrades(io, hyper_service);
+    let mut conn = std::pin::pin!(conn);
+    tokio::select! {
+        result = conn.as_mut() => {
+            if let Err(e) = result {
+                error!("Connection error: {:#}", e);
+            }
+        }
+        _ = stop_web_service_receiver.recv() => {
+            conn.as_mut().graceful_shutdown();
+            // 等连接真正关闭
+            if let Err(e) = conn.as_mut().await {
+                error!("Connection error during shutdown from {}: {:#}", client_socket_addr, e);
+            }
+        }
+    }
+}
+
+/// # 提供明文HTTP服务
+///
+/// 限定协议版本或调整了连接设置的明文服务，`HttpVersion::Http2` 时只接受 h2c(prior knowledge)
+pub(crate) fn serve_http(
+    router: Router,
+    tokio_listener: TcpListener,
+    mut stop_web_service_receiver: Receiver<()>,
+    http_conn_config: HttpConnConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            // 等待新的客户端连接
+            let (tcp_stream, client_socket_addr) = tokio::select! {
+                result = tokio_listener.accept() => {
+                    match result {
+                        Ok(accepted) => accepted,
+                        Err(e) => {
+                            error!("Accept error: {:#}", e);
+                            continue;
+                        }
+                    }
+                }
+                _ = stop_web_service_receiver.recv() => {
+                    debug!("Stopping accept loop.");
+                    break;
+                }
+            };
+            tokio::spawn(serve_connection(
+                tcp_stream,
+                router.clone(),
+                client_socket_addr,
+                http_conn_config,
+                stop_web_service_receiver.resubscribe(),
+            ));
+        }
+        info!("停止{:?} Web服务", http_conn_config.http_version);
+    })
+}
+
+/// # 在Unix域套接字上提供HTTP服务
+///
+/// 客户端没有IP地址，通常是本机的反向代理转发的外部请求，所以对端地址使用非回环的占位地址 `0.0.0.0:0`，
+/// 仅本地访问的判断会拒绝这些请求，客户端IP需配置可信代理后从 `X-Forwarded-For` 中解析；
+/// 只有本进程自己发起的连接(启动时的健康检查)视为来自本机(127.0.0.1)。停止服务后删除套接字文件
+pub(crate) fn serve_uds(
+    router: Router,
+    unix_listener: UnixListener,
+    uds_path: PathBuf,
+    mut stop_web_service_receiver: Receiver<()>,
+    http_conn_config: HttpConnConfig,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            // 等待新的客户端连接
+            let unix_stream = tokio::select! {
+                result = unix_listener.accept() => {
+                    match result {
+                        Ok((unix_stream, _)) => unix_stream,
+                        Err(e) => {
+                            error!("Accept error: {:#}", e);
+                            continue;
+                        }
+                    }
+                }
+                _ = stop_web_service_receiver.recv() => {
+                    debug!("Stopping accept loop.");
+                    break;
+                }
+            };
+            let client_socket_addr = uds_client_socket_addr(&unix_stream);
+            tokio::spawn(serve_connection(
+                unix_stream,
+                router.clone(),
+                client_socket_addr,
+                http_conn_config,
+                stop_web_service_receiver.resubscribe(),
+            ));
+        }
+        drop(unix_listener);
+        if let Err(e) = std::fs::remove_file(&uds_path) {
+            warn!("删除套接字文件<{}>失败: {e}", uds_path.display());
+        }
+        info!("停止Unix域套接字 Web服务");
+    })
+}
+
+/// Unix域套接字客户端的占位地址(非回环地址，不会被当作本地访问)
+pub(crate) const UDS_PEER_ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
+
+/// 获取Unix域套接字客户端的地址
+///
+/// 对端进程是本进程时(启动时的健康检查)为 `127.0.0.1:0`，否则为占位地址 `UDS_PEER_ADDR`
+fn uds_client_socket_addr(unix_stream: &UnixStream) -> SocketAddr {
+    let is_self = unix_stream
+        .peer_cred()
+        .ok()
+        .and_then(|cred| cred.pid())
+        .is_some_and(|pid| pid as u32 == std::process::id());
+    if is_self {
+        SocketAddr::from((Ipv4Addr::LOCALHOST, 0))
+    } else {
+        UDS_PEER_ADDR
+    }
+}
@@ -1,7 +1,9 @@
+mod http_conn_utils;
 mod web_server_config;
 mod web_server_error;
 mod web_server_utils;
 
+pub use http_conn_utils::*;
 pub use web_server_config::*;
 pub use web_server_error::*;
 pub use web_server_utils::*;
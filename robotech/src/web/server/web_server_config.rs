@@ -1,13 +1,21 @@
+use crate::app::RuntimeConfig;
+use crate::web::ConfigDumpConfig;
+use crate::web::ContentTypeConfig;
 use crate::web::HealthCheckConfig;
+use crate::web::I18nConfig;
+use crate::web::RateLimitConfig;
+use crate::web::ResponseCacheConfig;
+use crate::web::StaticFilesConfig;
 use crate::web::cors::CorsConfig;
 use crate::web::https::HttpsConfig;
 use ipnet::IpNet;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, Serializer};
+use std::path::PathBuf;
 use std::time::Duration;
-use wheel_rs::serde::{duration_serde, vec_ipnet_serde, vec_serde};
+use wheel_rs::serde::{duration_option_serde, duration_serde, vec_ipnet_serde, vec_serde};
 use wheel_rs::urn_utils::Urn;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "kebab-case")]
 pub struct WebServerConfig {
     /// 绑定的IP地址
@@ -28,16 +36,48 @@ pub struct WebServerConfig {
     #[serde(default = "reuse_port_default")]
     pub reuse_port: bool,
 
+    /// 监听的Unix域套接字路径(不设置则监听TCP，相对路径基于程序目录)
+    ///
+    /// * 设置后忽略 bind/port/listen/reuse_port，不支持https，适用于部署在nginx等反向代理后面的场景
+    /// * 启动时会清理残留的套接字文件(如果仍有服务在监听则报错)，停止服务时删除套接字文件
+    /// * 通过套接字连接的客户端没有IP地址，对端IP视为占位地址0.0.0.0(不是本机)，
+    ///   仅本地访问的路径(`local-only-urns`及未暴露的健康检查)通过套接字无法访问，
+    ///   限流、ip黑白名单需要的客户端IP必须在 `trusted-proxies` 中配置 `0.0.0.0/32`，
+    ///   从反向代理设置的 `X-Forwarded-For` 中解析，否则所有客户端的IP都是0.0.0.0
+    #[serde(default)]
+    pub uds: Option<PathBuf>,
+
     /// 是否启用Https(默认关闭)
     #[serde(default)]
     pub https: Option<HttpsConfig>,
 
-    /// 只允许本地访问的URN列表(默认为空)
+    /// HTTP协议版本(默认auto)
+    ///
+    /// 服务网格等环境要求明文HTTP/2(h2c)时设置为http2，各组合的支持情况见 `HttpVersion`
     #[serde(default)]
+    pub http_version: HttpVersion,
+
+    /// 连接保持(不设置则使用默认设置，HTTP/1.1保持连接，HTTP/2不发送PING)
+    ///
+    /// * 设置为0时，HTTP/1.1每个请求处理完成后关闭连接
+    /// * 设置为其它时长时，HTTP/2以此间隔发送PING检测空闲连接，超过此时长未收到响应则关闭连接
+    ///
+    /// HTTP/1.1空闲连接等待下一个请求的时间受 `client_request_timeout` 限制
+    #[serde(with = "duration_option_serde", default)]
+    pub keep_alive: Option<Duration>,
+
+    /// 读取HTTP/1.1请求头的超时时间(不设置则不超时)
+    ///
+    /// 从等待请求开始计时(包括保持的空闲连接等待下一个请求)，超时未收到完整的请求头则关闭连接
+    #[serde(with = "duration_option_serde", default)]
+    pub client_request_timeout: Option<Duration>,
+
+    /// 只允许本地访问的URN列表(默认为空)
+    #[serde(default, serialize_with = "urns_serialize")]
     pub local_only_urns: Vec<Urn>,
 
     /// 禁止访问的URN列表(默认为空)
-    #[serde(default)]
+    #[serde(default, serialize_with = "urns_serialize")]
     pub forbidden_urns: Vec<Urn>,
 
     /// ip白名单
@@ -48,18 +88,71 @@ pub struct WebServerConfig {
     #[serde(default, with = "vec_ipnet_serde")]
     pub ip_black_list: Vec<IpNet>,
 
+    /// 可信代理(如负载均衡)的IP列表(默认为空，不信任任何代理请求头)
+    ///
+    /// 只有TCP连接的对端IP在此列表中时，才会从 `X-Forwarded-For`/`X-Real-IP` 请求头中解析客户端IP，
+    /// 解析出的客户端IP用于ip黑白名单，handler可通过 `ClientIp` 提取；
+    /// 只允许本地访问的判断始终使用对端IP，不受代理请求头影响
+    #[serde(default, with = "vec_ipnet_serde")]
+    pub trusted_proxies: Vec<IpNet>,
+
+    /// 路径规范化模式(默认Off，不做处理)
+    ///
+    /// 客户端访问 `/users/` 与 `/users` 时，不规范化会导致其中一个返回404，
+    /// 一般推荐配置为 `trim-trailing`
+    #[serde(default)]
+    pub normalize_path: NormalizePathMode,
+
     /// 是否启用日志(默认关闭)
     #[serde(default)]
     pub log_enabled: bool,
 
+    /// 是否启用请求ID(默认关闭)
+    ///
+    /// 启用后读取请求头中的 `X-Request-Id`(没有则生成)，在响应头中返回，
+    /// 并在请求处理期间通过 `ApiClient` 调用下游服务时自动带上
+    #[serde(default)]
+    pub request_id_enabled: bool,
+
     /// CORS配置(不设置默认不开启)
     #[serde(default)]
     pub cors: Option<CorsConfig>,
 
+    /// 国际化配置(不设置默认不开启)
+    #[serde(default)]
+    pub i18n: Option<I18nConfig>,
+
+    /// 限流配置(不设置默认不开启)
+    #[serde(default)]
+    pub rate_limit: Option<RateLimitConfig>,
+
+    /// 响应缓存配置(不设置默认不开启)
+    #[serde(default)]
+    pub response_cache: Option<ResponseCacheConfig>,
+
+    /// 请求体内容类型校验配置(不设置默认不开启)
+    #[serde(default)]
+    pub content_type: Option<ContentTypeConfig>,
+
+    /// 静态文件服务配置(不设置默认不开启)
+    #[serde(default)]
+    pub static_files: Option<StaticFilesConfig>,
+
     /// 是否暴露健康检查(默认不暴露，只能本地访问)
     #[serde(default)]
     pub health_check: HealthCheckConfig,
 
+    /// 导出当前生效配置的管理端点(默认不开启)
+    #[serde(default)]
+    pub config_dump: ConfigDumpConfig,
+
+    /// 运行时配置(工作线程数、线程栈大小等)
+    ///
+    /// 只有在使用 `build_runtime` 创建tokio运行时时才会生效，
+    /// axum没有单独的工作线程，限制工作线程数请设置 `runtime.worker-threads`
+    #[serde(default)]
+    pub runtime: RuntimeConfig,
+
     #[serde(with = "duration_serde", default = "start_wait_timeout_default")]
     pub start_wait_timeout: Duration,
 
@@ -77,6 +170,18 @@ pub struct WebServerConfig {
         default = "terminate_old_app_retry_interval_default"
     )]
     pub terminate_old_app_retry_interval: Duration,
+
+    /// 复用端口重启时，等待旧应用排空(处理完进行中的请求)退出的超时时间(默认60秒)
+    ///
+    /// 超时后会强制停止旧应用
+    #[serde(with = "duration_serde", default = "drain_old_app_timeout_default")]
+    pub drain_old_app_timeout: Duration,
+
+    /// 快速退出(SIGINT/SIGTERM)时等待进行中请求的超时时间(默认30秒)
+    ///
+    /// 排空退出(SIGQUIT)时不受此限制，会一直等到所有请求处理完成
+    #[serde(with = "duration_serde", default = "shutdown_timeout_default")]
+    pub shutdown_timeout: Duration,
 }
 
 impl Default for WebServerConfig {
@@ -86,22 +191,81 @@ impl Default for WebServerConfig {
             port: port_default(),
             listen: listen_default(),
             reuse_port: reuse_port_default(),
+            uds: None,
             https: None,
+            http_version: HttpVersion::default(),
+            keep_alive: None,
+            client_request_timeout: None,
             forbidden_urns: vec![],
             local_only_urns: vec![],
             ip_white_list: vec![],
             ip_black_list: vec![],
+            trusted_proxies: vec![],
+            normalize_path: NormalizePathMode::default(),
             log_enabled: false,
+            request_id_enabled: false,
             cors: None,
+            i18n: None,
+            rate_limit: None,
+            response_cache: None,
+            content_type: None,
+            static_files: None,
             health_check: HealthCheckConfig::default(),
+            config_dump: ConfigDumpConfig::default(),
+            runtime: RuntimeConfig::default(),
             start_wait_timeout: start_wait_timeout_default(),
             start_retry_interval: start_retry_interval_default(),
             terminate_old_app_wait_timeout: terminate_old_app_wait_timeout_default(),
             terminate_old_app_retry_interval: terminate_old_app_retry_interval_default(),
+            drain_old_app_timeout: drain_old_app_timeout_default(),
+            shutdown_timeout: shutdown_timeout_default(),
         }
     }
 }
 
+/// # 路径规范化模式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NormalizePathMode {
+    /// 不做处理
+    #[default]
+    Off,
+    /// 合并连续的斜杠并去除末尾的斜杠，如 `//users//` -> `/users`
+    TrimTrailing,
+    /// 只合并连续的斜杠，如 `//users//` -> `/users/`
+    MergeSlashes,
+}
+
+/// # HTTP协议版本
+///
+/// | 配置  | 明文(http)                               | TLS(https)                         |
+/// |-------|------------------------------------------|------------------------------------|
+/// | http1 | 只支持HTTP/1.1                           | ALPN只协商http/1.1                 |
+/// | http2 | 只支持h2c(prior knowledge)               | ALPN只协商h2，不支持h2的客户端无法连接 |
+/// | auto  | HTTP/1.1，及h2c(prior knowledge)         | ALPN协商h2或http/1.1               |
+///
+/// 明文HTTP/2只支持prior knowledge方式(客户端直接发送HTTP/2连接前言)，
+/// 不支持通过HTTP/1.1的 `Upgrade: h2c` 请求头升级
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum HttpVersion {
+    /// 只支持HTTP/1.1
+    Http1,
+    /// 只支持HTTP/2
+    Http2,
+    /// 根据客户端自动选择
+    #[default]
+    Auto,
+}
+
+/// 将URN列表序列化为字符串列表
+pub(crate) fn urns_serialize<S: Serializer>(
+    urns: &[Urn],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(urns.iter().map(|urn| urn.to_string()))
+}
+
 fn bind_default() -> Vec<String> {
     vec![]
 }
@@ -129,3 +293,9 @@ fn terminate_old_app_wait_timeout_default() -> Duration {
 fn terminate_old_app_retry_interval_default() -> Duration {
     Duration::from_millis(500)
 }
+fn drain_old_app_timeout_default() -> Duration {
+    Duration::from_secs(60)
+}
+fn shutdown_timeout_default() -> Duration {
+    Duration::from_secs(30)
+}
@@ -1,19 +1,35 @@
+use crate::app::ShutdownMode;
+use crate::cfg::register_live_cfg;
+use crate::env::{APP_ENV, AppEnv, EnvError};
+#[cfg(feature = "db")]
+use crate::web::middleware::read_primary_middleware;
 use crate::web::middleware::{
-    ForbiddenUrnsState, IpBanState, LocalOnlyUrnsState, forbidden_urns_middleware,
-    ip_ban_middleware, local_only_middleware, local_only_urns_middleware,
+    ClientIpState, ContentTypeState, ForbiddenUrnsState, IpBanState, LocalOnlyUrnsState,
+    RateLimitState, ResponseCacheState, client_ip_middleware, content_type_middleware,
+    forbidden_urns_middleware, ip_ban_middleware, local_only_middleware,
+    local_only_urns_middleware, locale_middleware, normalize_path_middleware,
+    rate_limit_middleware, request_id_middleware, response_cache_middleware,
 };
-use crate::web::{HttpsConfig, WebServerConfig, WebServerError, build_cors, build_https};
-use axum::{Router, debug_handler, middleware, routing::get};
+use crate::web::{
+    ConfigDumpState, HealthDetailState, HealthState, HttpConnConfig, HttpVersion, HttpsConfig,
+    NormalizePathMode, UDS_PEER_ADDR, WebServerConfig, WebServerError, build_cors, build_https,
+    build_static_files, config_dump, health, health_detail, mark_started, serve_http, serve_uds,
+    set_i18n_config, set_response_cache_state,
+};
+use axum::{Router, middleware, routing::get};
 use linkme::distributed_slice;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 use robotech_macros::log_call;
 use socket2::{Domain, Socket, Type};
-use std::net::{IpAddr, SocketAddr, TcpListener};
+use std::net::{IpAddr, SocketAddr, TcpListener, ToSocketAddrs};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 use tokio::time::timeout;
+use tower::Layer;
 use tower_http::trace::TraceLayer;
 use utoipa::openapi::OpenApi;
 use utoipa_swagger_ui::{SwaggerUi, Url};
@@ -27,6 +43,8 @@ pub static API_DOC_SLICE: [fn() -> (Url<'static>, OpenApi)];
 
 static WEB_SERVICE_HANDLES: RwLock<Option<Vec<JoinHandle<()>>>> = RwLock::new(None);
 static STOP_WEB_SERVICE_SENDER: RwLock<Option<broadcast::Sender<()>>> = RwLock::new(None);
+/// 当前Web服务配置的快速退出超时时间(`WebServerConfig.shutdown_timeout`)
+static SHUTDOWN_TIMEOUT: RwLock<Option<Duration>> = RwLock::new(None);
 
 fn set_web_service_handles(value: Vec<JoinHandle<()>>) -> Result<(), WebServerError> {
     let mut write_lock = WEB_SERVICE_HANDLES
@@ -51,6 +69,20 @@ fn set_stop_web_service_sender(value: broadcast::Sender<()>) -> Result<(), WebSe
     Ok(())
 }
 
+fn set_shutdown_timeout(value: Duration) {
+    if let Ok(mut write_lock) = SHUTDOWN_TIMEOUT.write() {
+        *write_lock = Some(value);
+    }
+}
+
+fn get_shutdown_timeout() -> Duration {
+    SHUTDOWN_TIMEOUT
+        .read()
+        .ok()
+        .and_then(|read_lock| *read_lock)
+        .unwrap_or_else(|| WebServerConfig::default().shutdown_timeout)
+}
+
 fn take_stop_web_service_sender() -> Result<Option<broadcast::Sender<()>>, WebServerError> {
     let mut write_lock = STOP_WEB_SERVICE_SENDER
         .write()
@@ -58,91 +90,39 @@ fn take_stop_web_service_sender() -> Result<Option<broadcast::Sender<()>>, WebSe
     Ok(write_lock.take())
 }
 
-/// # 健康检查端点
-///
-/// 提供简单的健康检查接口，返回 "Ok" 字符串表示服务正常运行
+/// # 构建路由
 ///
-/// ## 返回值
-/// 返回实现了 Responder trait 的响应对象
-#[debug_handler]
-#[log_call]
-pub async fn health() -> &'static str {
-    "Ok"
-}
-
+/// 合并所有注册的路由、健康检查、Swagger UI、静态文件服务，并按配置添加各种中间件
 #[log_call]
-pub async fn start_web_server(
-    web_server_config: WebServerConfig,
-    port_of_args: Option<u16>,
-    old_pid: Option<u32>,
-) -> Result<(), WebServerError> {
+pub fn build_router(web_server_config: &WebServerConfig) -> Result<Router, WebServerError> {
     let WebServerConfig {
-        bind: binds,
-        port: port_option,
-        listen: listens,
-        mut reuse_port,
-        https: https_config,
         forbidden_urns,
         local_only_urns,
         ip_white_list,
         ip_black_list,
+        trusted_proxies,
+        normalize_path,
         log_enabled,
+        request_id_enabled,
         cors: cors_config,
+        i18n: i18n_config,
+        rate_limit: rate_limit_config,
+        response_cache: response_cache_config,
+        content_type: content_type_config,
+        static_files: static_files_config,
         health_check,
-        start_wait_timeout,
-        start_retry_interval,
-        terminate_old_app_wait_timeout,
-        terminate_old_app_retry_interval,
+        config_dump: config_dump_config,
+        ..
     } = web_server_config;
-    let health_check_uri = &health_check.uri;
-
-    let (is_random_port, listen_binds) =
-        get_listen_binds(port_of_args, binds, port_option, listens)?;
-    if listen_binds.is_empty() {
-        Err(WebServerError::ParseListenBinds(
-            "没有配置监听绑定".to_string(),
-        ))?;
-    }
 
-    let mut old_web_service_handles = take_web_service_handles()?;
-    let stop_old_web_service_sender = take_stop_web_service_sender()?;
-
-    if is_random_port {
-        // 如果是随机端口，则不会开启复用端口(无意义)
-        reuse_port = false;
-    } else if !reuse_port {
-        // 如果不是随机端口，且不是复用端口，则先停止旧服务或应用，然后才能启动新的服务
-        if let Some(old_pid) = old_pid {
-            // 停止旧应用
-            terminate_old_app(
-                old_pid,
-                terminate_old_app_wait_timeout,
-                terminate_old_app_retry_interval,
-            )
-            .await?;
-        } else {
-            // 停止旧服务
-            if let Some(web_service_handles) = old_web_service_handles.take() {
-                stop_old_web_service(stop_old_web_service_sender.clone(), web_service_handles)
-                    .await?;
-            }
-        }
-    }
+    // 登记当前生效的Web服务器配置
+    register_live_cfg("web", web_server_config);
 
     // 初始化路由
     let mut router = Router::new();
     for build_router in ROUTER_SLICE.iter() {
         router = router.merge(build_router());
     }
-    // 判断是否暴露健康检查
-    if health_check.exposed {
-        router = router.route(health_check_uri, get(health));
-    } else {
-        router = router.route(
-            health_check_uri,
-            get(health).layer(axum::middleware::from_fn(local_only_middleware)),
-        );
-    }
     // 集成 Swagger UI，访问 /swagger-ui 即可查看文档
     let mut api_docs = vec![];
     for init_api_doc in API_DOC_SLICE.iter() {
@@ -151,11 +131,106 @@ pub async fn start_web_server(
     if !api_docs.is_empty() {
         router = router.merge(SwaggerUi::new("/swagger-ui").urls(api_docs));
     }
+    // 添加静态文件服务(作为fallback，不会遮盖上面注册的路由)
+    router = build_static_files(router, static_files_config)?;
 
+    // 添加响应缓存中间件(放在最内层，被拦截的请求不会读到缓存)
+    if let Some(response_cache_config) = response_cache_config
+        && response_cache_config.enabled
+    {
+        let response_cache_state = ResponseCacheState::new(response_cache_config.clone());
+        set_response_cache_state(Some(response_cache_state.clone()));
+        router = router.layer(middleware::from_fn_with_state(
+            response_cache_state,
+            response_cache_middleware,
+        ));
+    } else {
+        set_response_cache_state(None);
+    }
+    // 添加健康检查端点(在响应缓存中间件之后添加，避免被缓存而返回过期的健康状态)
+    // 判断是否暴露健康检查
+    mark_started();
+    let health_check_uri = &health_check.uri;
+    let health_handler = get(health).with_state(HealthState {
+        db_check: health_check.db_check,
+        check_timeout: health_check.check_timeout,
+    });
+    let health_detail_handler = get(health_detail).with_state(HealthDetailState {
+        check_timeout: health_check.check_timeout,
+    });
+    if health_check.exposed {
+        router = router.route(health_check_uri, health_handler);
+        router = router.route(&health_check.detail_uri, health_detail_handler);
+    } else {
+        router = router.route(
+            health_check_uri,
+            health_handler.layer(axum::middleware::from_fn(local_only_middleware)),
+        );
+        router = router.route(
+            &health_check.detail_uri,
+            health_detail_handler.layer(axum::middleware::from_fn(local_only_middleware)),
+        );
+    }
+    // 导出当前生效配置的管理端点(在响应缓存中间件之后添加，避免被缓存)
+    if config_dump_config.enabled {
+        if config_dump_config.admin_user_ids.is_empty() {
+            Err(WebServerError::Config(
+                "开启导出配置端点时必须配置admin-user-ids".to_string(),
+            ))?;
+        }
+        let config_dump_state = ConfigDumpState {
+            admin_user_ids: Arc::new(config_dump_config.admin_user_ids.clone()),
+        };
+        router = router.route(
+            &config_dump_config.uri,
+            get(config_dump).with_state(config_dump_state),
+        );
+    }
+    // 添加请求体内容类型校验中间件
+    if let Some(content_type_config) = content_type_config
+        && content_type_config.enabled
+    {
+        router = router.layer(middleware::from_fn_with_state(
+            ContentTypeState {
+                config: Arc::new(content_type_config.clone()),
+            },
+            content_type_middleware,
+        ));
+    }
     // 添加日志中间件
-    if log_enabled {
+    if *log_enabled {
         router = router.layer(TraceLayer::new_for_http());
     }
+    // 添加读主库标记中间件(请求中写入后从主库读取)
+    #[cfg(feature = "db")]
+    {
+        router = router.layer(middleware::from_fn(read_primary_middleware));
+    }
+    // 添加请求ID中间件
+    if *request_id_enabled {
+        router = router.layer(middleware::from_fn(request_id_middleware));
+    }
+    // 添加语言中间件
+    if let Some(i18n_config) = i18n_config
+        && i18n_config.enabled
+    {
+        set_i18n_config(i18n_config.clone());
+        router = router.layer(middleware::from_fn(locale_middleware));
+    }
+    // 添加限流中间件(健康检查不限流)
+    if let Some(rate_limit_config) = rate_limit_config
+        && rate_limit_config.enabled
+    {
+        let rate_limit_state = RateLimitState::new(
+            rate_limit_config,
+            vec![health_check.uri.clone(), health_check.detail_uri.clone()],
+            trusted_proxies.clone(),
+        )?;
+        router = router.layer(middleware::from_fn_with_state(
+            rate_limit_state,
+            rate_limit_middleware,
+        ));
+    }
     // 添加IP拦截中间件
     if !ip_white_list.is_empty() || !ip_black_list.is_empty() {
         let ip_ban_state = IpBanState {
@@ -187,10 +262,119 @@ pub async fn start_web_server(
             local_only_urns_middleware,
         ));
     }
+    // 添加客户端IP中间件(放在IP拦截等中间件的外层，使其能读到解析出的客户端IP)
+    if !trusted_proxies.is_empty() {
+        router = router.layer(middleware::from_fn_with_state(
+            ClientIpState {
+                trusted_proxies: Arc::new(trusted_proxies.clone()),
+            },
+            client_ip_middleware,
+        ));
+    }
     // 添加CORS中间件
-    if let Some(cors_layer) = build_cors(&cors_config)? {
+    if let Some(cors_layer) = build_cors(cors_config)? {
         router = router.layer(cors_layer);
     }
+    // 添加路径规范化中间件(必须在路由匹配之前改写路径，所以包装在外层路由的fallback中)
+    if *normalize_path != NormalizePathMode::Off {
+        router = Router::new().fallback_service(
+            middleware::from_fn_with_state(*normalize_path, normalize_path_middleware)
+                .layer(router),
+        );
+    }
+
+    Ok(router)
+}
+
+/// # 启动Web服务器
+///
+/// ## 复用端口重启时新旧服务器的交接顺序
+/// 1. 新服务器以 `SO_REUSEPORT` 绑定相同的端口并开始接受连接(此时内核在新旧服务器之间分配新连接)
+/// 2. 等待新服务器的健康检查通过
+/// 3. 向旧应用发送 SIGQUIT 排空退出：旧服务器关闭监听(内核不再向其分配新连接)，
+///    处理完所有进行中的请求后退出
+/// 4. 等待旧应用退出，超过 `drain_old_app_timeout` 仍未退出则强制停止旧应用
+///
+/// 同一进程内重启(没有旧应用的PID)时，旧服务在新服务启动后同样以优雅停止的方式排空
+///
+/// 配置了Unix域套接字(`uds`)时只监听该套接字，不复用端口(先停止旧服务或应用再启动)，
+/// 健康检查同样通过该套接字进行
+///
+/// ## 返回值
+/// 返回各监听实际绑定的地址(配置为随机端口0时，可从中获取系统分配的端口)，监听Unix域套接字时为空
+#[log_call]
+pub async fn start_web_server(
+    web_server_config: WebServerConfig,
+    port_of_args: Option<u16>,
+    old_pid: Option<u32>,
+) -> Result<Vec<SocketAddr>, WebServerError> {
+    // 初始化路由
+    let router = build_router(&web_server_config)?;
+    let http_conn_config = HttpConnConfig::from(&web_server_config);
+    if web_server_config.uds.is_some()
+        && !web_server_config
+            .trusted_proxies
+            .iter()
+            .any(|ip_net| ip_net.contains(&UDS_PEER_ADDR.ip()))
+    {
+        warn!("监听Unix域套接字时没有将0.0.0.0/32配置为可信代理，无法解析客户端IP");
+    }
+
+    let WebServerConfig {
+        bind: binds,
+        port: port_option,
+        listen: listens,
+        mut reuse_port,
+        uds,
+        https: https_config,
+        health_check,
+        start_wait_timeout,
+        start_retry_interval,
+        terminate_old_app_wait_timeout,
+        terminate_old_app_retry_interval,
+        drain_old_app_timeout,
+        shutdown_timeout,
+        ..
+    } = web_server_config;
+    let health_check_uri = &health_check.uri;
+
+    let (is_random_port, listen_binds) = if uds.is_some() {
+        // 监听Unix域套接字时，不需要TCP监听，也不复用端口
+        reuse_port = false;
+        (false, vec![])
+    } else {
+        get_listen_binds(port_of_args, binds, port_option, listens)?
+    };
+    if uds.is_none() && listen_binds.is_empty() {
+        Err(WebServerError::ParseListenBinds(
+            "没有配置监听绑定".to_string(),
+        ))?;
+    }
+
+    let mut old_web_service_handles = take_web_service_handles()?;
+    let stop_old_web_service_sender = take_stop_web_service_sender()?;
+
+    if is_random_port {
+        // 如果是随机端口，则不会开启复用端口(无意义)
+        reuse_port = false;
+    } else if !reuse_port {
+        // 如果不是随机端口，且不是复用端口，则先停止旧服务或应用，然后才能启动新的服务
+        if let Some(old_pid) = old_pid {
+            // 停止旧应用
+            terminate_old_app(
+                old_pid,
+                terminate_old_app_wait_timeout,
+                terminate_old_app_retry_interval,
+            )
+            .await?;
+        } else {
+            // 停止旧服务
+            if let Some(web_service_handles) = old_web_service_handles.take() {
+                stop_old_web_service(stop_old_web_service_sender.clone(), web_service_handles)
+                    .await?;
+            }
+        }
+    }
 
     // 判断HTTP协议
     let http_protocol = if let Some(https_config) = https_config.clone()
@@ -203,20 +387,35 @@ pub async fn start_web_server(
 
     // 绑定地址及端口，并启动服务
     let (stop_web_service_sender, stop_web_service_receiver) = broadcast::channel::<()>(1);
-    let (health_check_url_prefix, web_service_handles) = bind_and_start(
-        router,
-        reuse_port,
-        listen_binds,
-        http_protocol,
-        https_config,
-        stop_web_service_receiver,
-    )?;
+    let (health_check_target, actual_addrs, web_service_handles) = if let Some(uds_path) = uds {
+        let (health_check_target, web_service_handle) = bind_uds_and_start(
+            router,
+            uds_path,
+            &https_config,
+            http_conn_config,
+            stop_web_service_receiver,
+        )?;
+        (health_check_target, vec![], vec![web_service_handle])
+    } else {
+        bind_and_start(
+            router,
+            reuse_port,
+            listen_binds,
+            http_protocol,
+            https_config,
+            http_conn_config,
+            stop_web_service_receiver,
+        )?
+    };
+    // 在启动完成前，如果启动被取消，会停止刚启动的服务
+    let starting_web_service =
+        StartingWebService::new(stop_web_service_sender, web_service_handles);
 
     // 如果没有旧服务，则等待新服务器启动成功
     if old_web_service_handles.is_none() {
-        let heath_check_url = format!("{health_check_url_prefix}{health_check_uri}");
         wait_for_web_server_ready(
-            heath_check_url.as_str(),
+            &health_check_target,
+            health_check_uri,
             start_wait_timeout,
             start_retry_interval,
         )
@@ -226,9 +425,10 @@ pub async fn start_web_server(
     // 如果是随机端口或复用端口，则可以在前面先启动新的服务，后面这里再停止旧的服务或应用
     if is_random_port || reuse_port {
         if let Some(old_pid) = old_pid {
-            // 停止旧应用
-            terminate_old_app(
+            // 排空旧应用，让旧服务器处理完进行中的请求后再退出
+            drain_old_app(
                 old_pid,
+                drain_old_app_timeout,
                 terminate_old_app_wait_timeout,
                 terminate_old_app_retry_interval,
             )
@@ -247,10 +447,112 @@ pub async fn start_web_server(
         }
     }
 
+    let (stop_web_service_sender, web_service_handles) = starting_web_service.finish();
     set_web_service_handles(web_service_handles)?;
     set_stop_web_service_sender(stop_web_service_sender)?;
+    set_shutdown_timeout(shutdown_timeout);
 
-    Ok(())
+    Ok(actual_addrs)
+}
+
+/// # 启动中的Web服务
+///
+/// 服务已绑定监听但还未启动完成(如正在等待健康检查)时，
+/// 如果启动被取消(`start_web_server` 的 future 被丢弃或中途出错返回)，
+/// 则在释放时停止已启动的服务，避免留下无人管理的监听
+struct StartingWebService {
+    stop_web_service_sender: Option<broadcast::Sender<()>>,
+    web_service_handles: Option<Vec<JoinHandle<()>>>,
+}
+
+impl StartingWebService {
+    fn new(
+        stop_web_service_sender: broadcast::Sender<()>,
+        web_service_handles: Vec<JoinHandle<()>>,
+    ) -> Self {
+        Self {
+            stop_web_service_sender: Some(stop_web_service_sender),
+            web_service_handles: Some(web_service_handles),
+        }
+    }
+
+    /// 启动完成，交出服务的停止信号发送器及句柄
+    fn finish(mut self) -> (broadcast::Sender<()>, Vec<JoinHandle<()>>) {
+        (
+            self.stop_web_service_sender.take().unwrap(),
+            self.web_service_handles.take().unwrap(),
+        )
+    }
+}
+
+impl Drop for StartingWebService {
+    fn drop(&mut self) {
+        if let Some(web_service_handles) = self.web_service_handles.take() {
+            warn!("Web服务器启动被取消，停止已启动的服务");
+            if let Some(stop_web_service_sender) = self.stop_web_service_sender.take() {
+                let _ = stop_web_service_sender.send(());
+            }
+            // 还未对外提供服务，直接结束服务任务，立即释放监听
+            for web_service_handle in web_service_handles {
+                web_service_handle.abort();
+            }
+        }
+    }
+}
+
+/// # 在调用方提供的监听器上启动Web服务器
+///
+/// 直接使用已绑定好的监听器(如使用特殊socket选项创建的，或从父进程继承的)提供服务，
+/// 完全跳过 bind/listen/reuse_port 的解析，适用于嵌入使用及确定性的集成测试
+///
+/// ## 参数
+/// * `web_server_config` - Web服务器配置(其中的 bind/port/listen/reuse_port 会被忽略)
+/// * `tcp_listener` - 已绑定并开始监听的TCP监听器
+///
+/// ## 返回值
+/// 返回监听器实际绑定的地址
+#[log_call]
+pub async fn start_web_server_with_listener(
+    web_server_config: WebServerConfig,
+    tcp_listener: TcpListener,
+) -> Result<SocketAddr, WebServerError> {
+    let router = build_router(&web_server_config)?;
+    let http_conn_config = HttpConnConfig::from(&web_server_config);
+    let WebServerConfig {
+        https: https_config,
+        shutdown_timeout,
+        ..
+    } = web_server_config;
+
+    // tokio 要求监听器为非阻塞模式
+    tcp_listener
+        .set_nonblocking(true)
+        .map_err(|e| WebServerError::Socket(format!("设置非阻塞模式失败: {e}")))?;
+    let actual_addr = tcp_listener.local_addr()?;
+
+    let old_web_service_handles = take_web_service_handles()?;
+    let stop_old_web_service_sender = take_stop_web_service_sender()?;
+
+    let (stop_web_service_sender, stop_web_service_receiver) = broadcast::channel::<()>(1);
+    let handle = serve(
+        router,
+        tcp_listener,
+        &https_config,
+        http_conn_config,
+        stop_web_service_receiver,
+    )?;
+    info!("监听 <{actual_addr}> 成功✅");
+
+    // 停止旧服务
+    if let Some(web_service_handles) = old_web_service_handles {
+        stop_old_web_service(stop_old_web_service_sender, web_service_handles).await?;
+    }
+
+    set_web_service_handles(vec![handle])?;
+    set_stop_web_service_sender(stop_web_service_sender)?;
+    set_shutdown_timeout(shutdown_timeout);
+
+    Ok(actual_addr)
 }
 
 /// # 创建支持端口复用的TCP监听器
@@ -258,14 +560,14 @@ pub async fn start_web_server(
 /// 创建一个支持SO_REUSEADDR和SO_REUSEPORT选项的TCP监听器，用于实现无缝重启
 ///
 /// ## 参数
-/// * `ip` - 要监听的IP地址字符串
+/// * `bind` - 要监听的IP地址或主机名(主机名会被解析，优先使用IPv4地址)
 /// * `port` - 要监听的端口号
 ///
 /// ## 返回值
 /// 返回配置好的TcpListener实例
 ///
 /// ## 错误处理
-/// * IP地址格式无效时会返回错误
+/// * IP地址格式无效且无法解析为主机名时会返回错误
 /// * socket创建失败时会返回错误
 /// * 设置socket选项失败时会返回错误
 /// * 绑定地址失败时会返回错误
@@ -281,11 +583,11 @@ pub fn create_listener(
         bind = bind[1..bind.len() - 1].to_string();
     }
 
-    // 解析 IP 地址
-    let ip_addr: IpAddr = bind
-        .parse()
-        .map_err(|_| WebServerError::Socket("无效的 IP 地址格式".to_string()))?;
-    let addr: &SocketAddr = &SocketAddr::new(ip_addr, port);
+    // 解析 IP 地址(不是IP地址时按主机名解析)
+    let addr: &SocketAddr = &match bind.parse::<IpAddr>() {
+        Ok(ip_addr) => SocketAddr::new(ip_addr, port),
+        Err(_) => resolve_host(&bind, port)?,
+    };
     // 创建 socket
     let socket = Socket::new(
         Domain::for_address(*addr),
@@ -323,12 +625,91 @@ pub fn create_listener(
     Ok(TcpListener::from(socket))
 }
 
+/// # 解析主机名
+///
+/// 解析出多个地址时优先使用IPv4地址(如 `localhost` 同时解析为 `::1` 与 `127.0.0.1`)
+fn resolve_host(host: &str, port: u16) -> Result<SocketAddr, WebServerError> {
+    let addrs: Vec<SocketAddr> = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| WebServerError::Socket(format!("解析主机名<{host}>失败: {e}")))?
+        .collect();
+    addrs
+        .iter()
+        .find(|addr| addr.is_ipv4())
+        .or_else(|| addrs.first())
+        .copied()
+        .ok_or_else(|| WebServerError::Socket(format!("主机名<{host}>没有解析到任何地址")))
+}
+
+/// # 健康检查目标
+///
+/// 记录就绪探测实际要连接的传输方式及地址，探测时直接连接刚绑定的监听，
+/// 而不是配置中的地址(配置的可能是随机端口0或通配地址)
+#[derive(Debug)]
+struct HealthCheckTarget {
+    /// 协议(http/https)
+    http_protocol: String,
+    /// 请求使用的主机名(绑定通配地址时为localhost)
+    host: String,
+    /// 探测时实际连接的地址(通配地址会换成对应的回环地址)
+    addr: SocketAddr,
+    /// 是否直接使用HTTP/2(h2c)请求
+    http2_prior_knowledge: bool,
+    /// 通过Unix域套接字探测时的套接字路径
+    uds_path: Option<PathBuf>,
+}
+
+impl HealthCheckTarget {
+    fn new(http_protocol: &str, host: &str, actual_addr: SocketAddr) -> Self {
+        let mut addr = actual_addr;
+        if addr.ip().is_unspecified() {
+            addr.set_ip(match addr.ip() {
+                IpAddr::V4(_) => IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+                IpAddr::V6(_) => IpAddr::V6(std::net::Ipv6Addr::LOCALHOST),
+            });
+        }
+        Self {
+            http_protocol: http_protocol.to_string(),
+            host: host.to_string(),
+            addr,
+            http2_prior_knowledge: false,
+            uds_path: None,
+        }
+    }
+
+    /// 通过Unix域套接字探测
+    fn uds(uds_path: PathBuf) -> Self {
+        Self {
+            http_protocol: "http".to_string(),
+            host: "localhost".to_string(),
+            addr: SocketAddr::from((std::net::Ipv4Addr::LOCALHOST, 0)),
+            http2_prior_knowledge: false,
+            uds_path: Some(uds_path),
+        }
+    }
+
+    /// 获取健康检查的URL(使用实际绑定的端口)
+    fn url(&self, health_check_uri: &str) -> String {
+        if self.uds_path.is_some() {
+            return format!("{}://{}{}", self.http_protocol, self.host, health_check_uri);
+        }
+        format!(
+            "{}://{}:{}{}",
+            self.http_protocol,
+            self.host,
+            self.addr.port(),
+            health_check_uri
+        )
+    }
+}
+
 /// # 等待Web服务器准备就绪
 ///
 /// 通过健康检查端点轮询等待Web服务器完全启动并准备好接受请求
 ///
 /// ## 参数
-/// * `health_check_url` - 健康检查URL
+/// * `health_check_target` - 健康检查目标
+/// * `health_check_uri` - 健康检查URI
 /// * `wait_timeout` - 最大等待时间
 /// * `retry_interval` - 重试间隔时间
 ///
@@ -339,22 +720,33 @@ pub fn create_listener(
 /// ## 错误处理
 /// * 等待超时时返回错误字符串"启动超时"
 async fn wait_for_web_server_ready(
-    health_check_url: &str,
+    health_check_target: &HealthCheckTarget,
+    health_check_uri: &str,
     wait_timeout: Duration,
     retry_interval: Duration,
 ) -> Result<(), WebServerError> {
-    let client = if health_check_url.starts_with("https://") {
-        reqwest::Client::builder()
-            .danger_accept_invalid_certs(true) // 忽略未认证的证书
-            .build()
-            .map_err(|e| WebServerError::BuildReqwestClient(e.to_string()))?
+    let health_check_url = health_check_target.url(health_check_uri);
+    // 主机名直接解析到刚绑定的地址(或直接连接刚绑定的套接字)，确保探测的就是本次启动的服务
+    let mut client_builder = if let Some(uds_path) = &health_check_target.uds_path {
+        reqwest::Client::builder().unix_socket(uds_path.clone())
     } else {
-        reqwest::Client::new()
+        reqwest::Client::builder().resolve(&health_check_target.host, health_check_target.addr)
     };
-    timeout(wait_timeout, async move {
+    if health_check_target.http_protocol == "https" {
+        // 探测的是本进程用配置的证书启动的服务，证书通常是自签名的，
+        // 或签发给对外域名的(与回环地址不匹配)，所以不校验证书
+        client_builder = client_builder.danger_accept_invalid_certs(true);
+    }
+    if health_check_target.http2_prior_knowledge {
+        client_builder = client_builder.http2_prior_knowledge();
+    }
+    let client = client_builder
+        .build()
+        .map_err(|e| WebServerError::BuildReqwestClient(e.to_string()))?;
+    timeout(wait_timeout, async {
         Ok(loop {
             tokio::time::sleep(retry_interval).await;
-            if let Ok(response) = client.get(health_check_url).send().await {
+            if let Ok(response) = client.get(&health_check_url).send().await {
                 if response.status().is_success() {
                     info!("Web服务器通过健康检查，启动完成.");
                     break;
@@ -363,9 +755,69 @@ async fn wait_for_web_server_ready(
         })
     })
     .await
-    .map_err(|_| WebServerError::StartWebServerTimeout(health_check_url.to_string()))?
+    .map_err(|_| WebServerError::StartWebServerTimeout(health_check_url.clone()))?
+}
+
+/// # 收到退出信号时优雅停止Web服务
+///
+/// 与 `stop_web_service_by_mode` 相同，快速退出的超时时间使用启动Web服务器时配置的 `shutdown_timeout`，
+/// 一般在 `wait_app_exit` 的回调中调用，停止接受新连接，并让进行中的请求在超时时间内处理完成
+///
+/// ## 使用示例
+/// ```
+/// wait_app_exit(signal_receiver, |shutdown_mode| async move {
+///     if let Err(e) = graceful_stop_web_service(shutdown_mode).await {
+///         error!("停止Web服务失败: {e}");
+///     }
+///     Ok(())
+/// })
+/// .await?;
+/// ```
+pub async fn graceful_stop_web_service(shutdown_mode: ShutdownMode) -> Result<(), WebServerError> {
+    stop_web_service_by_mode(shutdown_mode, get_shutdown_timeout()).await
+}
+
+/// # 按退出模式停止Web服务
+///
+/// * `ShutdownMode::Fast` - 最多等待 `shutdown_timeout`，超时后强制结束服务
+/// * `ShutdownMode::Drain` - 等待所有进行中的请求处理完成
+pub async fn stop_web_service_by_mode(
+    shutdown_mode: ShutdownMode,
+    shutdown_timeout: Duration,
+) -> Result<(), WebServerError> {
+    match shutdown_mode {
+        ShutdownMode::Fast => stop_web_service_within(shutdown_timeout).await,
+        ShutdownMode::Drain => stop_web_service().await,
+    }
+}
+
+/// # 在超时时间内停止Web服务
+///
+/// 发送停止信号后等待服务结束，超时后强制结束仍在运行的服务
+pub async fn stop_web_service_within(shutdown_timeout: Duration) -> Result<(), WebServerError> {
+    if let Some(stop_web_service_sender) = take_stop_web_service_sender()? {
+        stop_web_service_sender
+            .send(())
+            .map_err(|e| WebServerError::StopService(e.to_string()))?;
+    }
+    if let Some(web_service_handles) = take_web_service_handles()? {
+        let deadline = tokio::time::Instant::now() + shutdown_timeout;
+        for mut web_service_handle in web_service_handles {
+            if tokio::time::timeout_at(deadline, &mut web_service_handle)
+                .await
+                .is_err()
+            {
+                warn!("停止Web服务超时，强制结束");
+                web_service_handle.abort();
+            }
+        }
+    }
+    Ok(())
 }
 
+/// # 停止Web服务
+///
+/// 发送停止信号后，等待所有进行中的请求处理完成
 pub async fn stop_web_service() -> Result<(), WebServerError> {
     if let Some(stop_web_service_sender) = take_stop_web_service_sender()? {
         stop_web_service_sender
@@ -425,6 +877,46 @@ async fn terminate_old_app(
     Ok(())
 }
 
+/// # 排空旧的应用
+///
+/// 向旧应用发送 SIGQUIT，旧应用停止接受新连接，处理完所有进行中的请求后自行退出，
+/// 在 `drain_timeout` 内没有退出则强制停止
+///
+/// ## 参数
+/// * `old_pid` - 旧应用进程ID
+/// * `drain_timeout` - 等待旧应用排空退出的超时时间
+/// * `wait_timeout` - 强制停止时等待旧应用退出的超时时间
+/// * `retry_interval` - 检查旧应用是否退出的间隔时间
+async fn drain_old_app(
+    old_pid: u32,
+    drain_timeout: Duration,
+    wait_timeout: Duration,
+    retry_interval: Duration,
+) -> Result<(), WebServerError> {
+    debug!("排空旧的Web服务器...");
+    let pid = nix::unistd::Pid::from_raw(old_pid as i32);
+    if let Err(e) = nix::sys::signal::kill(pid, nix::sys::signal::Signal::SIGQUIT) {
+        // 旧应用已经不存在
+        if e == nix::errno::Errno::ESRCH {
+            return Ok(());
+        }
+        warn!("向旧应用({old_pid})发送排空信号失败，强制停止: {e}");
+        return terminate_old_app(old_pid, wait_timeout, retry_interval).await;
+    }
+
+    let deadline = tokio::time::Instant::now() + drain_timeout;
+    // 发送信号0检查进程是否存在，不存在说明旧应用已经排空退出
+    while nix::sys::signal::kill(pid, None).is_ok() {
+        if tokio::time::Instant::now() >= deadline {
+            warn!("等待旧应用({old_pid})排空超时，强制停止");
+            return terminate_old_app(old_pid, wait_timeout, retry_interval).await;
+        }
+        tokio::time::sleep(retry_interval).await;
+    }
+    debug!("旧的Web服务器已排空退出");
+    Ok(())
+}
+
 fn get_listen_binds(
     port_of_args: Option<u16>,
     binds: Vec<String>,
@@ -491,65 +983,178 @@ fn bind_and_start(
     listen_binds: Vec<(String, u16)>,
     http_protocol: &str,
     https_config: Option<HttpsConfig>,
+    http_conn_config: HttpConnConfig,
     stop_web_service_receiver: broadcast::Receiver<()>,
-) -> Result<(String, Vec<JoinHandle<()>>), WebServerError> {
+) -> Result<(HealthCheckTarget, Vec<SocketAddr>, Vec<JoinHandle<()>>), WebServerError> {
     let mut web_service_handles = Vec::new();
-    let mut health_check_url_prefix = None;
+    let mut actual_addrs = Vec::new();
+    let mut health_check_target = None;
     for (bind, port) in listen_binds {
         let tcp_listener = create_listener(bind.to_string(), port, reuse_port)?;
         // 在 serve 之前获取实际端口
         let actual_addr = tcp_listener.local_addr()?;
-        let tokio_listener = tokio::net::TcpListener::from_std(tcp_listener)
-            .map_err(|e| WebServerError::Socket(format!("转换为tokio listener失败: {:#}", e)))?;
 
         // 启动服务
-        let mut stop_web_service_receiver = stop_web_service_receiver.resubscribe();
-        if let Some(https_config) = https_config.clone()
-            && https_config.enabled
-        {
-            let handle = build_https(
-                router.clone(),
-                tokio_listener,
-                stop_web_service_receiver,
-                https_config,
-            )?;
-            web_service_handles.push(handle);
-        } else {
-            let server = axum::serve(
-                tokio_listener,
-                router
-                    .clone()
-                    // 注意：必须调用 into_make_service_with_connect_info 才能获取客户端 IP
-                    .into_make_service_with_connect_info::<SocketAddr>(),
-            )
-            .with_graceful_shutdown(async move {
-                let _ = stop_web_service_receiver.recv().await;
-                info!("停止Axum Web服务");
-            });
-            let handle = tokio::spawn(async move {
-                if let Err(e) = server.await {
-                    error!("Axum Web服务运行异常: {:#}", e);
-                }
-            });
-            web_service_handles.push(handle);
-        }
+        let handle = serve(
+            router.clone(),
+            tcp_listener,
+            &https_config,
+            http_conn_config,
+            stop_web_service_receiver.resubscribe(),
+        )?;
+        web_service_handles.push(handle);
+        actual_addrs.push(actual_addr);
 
+        // 使用实际绑定的端口(配置的可能是随机端口0)
+        let port = actual_addr.port();
         let ip = if bind == "0.0.0.0" {
-            // 设置域名返回给外部用来健康检查
-            health_check_url_prefix = Some(format!("{http_protocol}://localhost:{port}"));
+            // 设置健康检查目标返回给外部用来健康检查
+            health_check_target = Some(HealthCheckTarget::new(
+                http_protocol,
+                "localhost",
+                actual_addr,
+            ));
             "127.0.0.1"
         } else if bind == r"[::]" {
-            // 设置域名返回给外部用来健康检查
-            health_check_url_prefix = Some(format!("{http_protocol}://localhost:{port}"));
+            // 设置健康检查目标返回给外部用来健康检查
+            health_check_target = Some(HealthCheckTarget::new(
+                http_protocol,
+                "localhost",
+                actual_addr,
+            ));
             r"[::1]"
         } else {
-            // 设置域名返回给外部用来健康检查
-            if health_check_url_prefix.is_none() {
-                health_check_url_prefix = Some(format!("{http_protocol}://{bind}:{port}"));
+            // 设置健康检查目标返回给外部用来健康检查
+            if health_check_target.is_none() {
+                health_check_target =
+                    Some(HealthCheckTarget::new(http_protocol, &bind, actual_addr));
             }
             &bind
         };
         info!("监听 <{actual_addr}> 成功✅  -> 🌐 {http_protocol}://{ip}:{port}");
     }
-    Ok((health_check_url_prefix.unwrap(), web_service_handles))
+    let mut health_check_target = health_check_target.unwrap();
+    // 明文只支持HTTP/2时，健康检查也要直接使用HTTP/2(h2c)
+    health_check_target.http2_prior_knowledge =
+        http_protocol == "http" && http_conn_config.http_version == HttpVersion::Http2;
+    Ok((health_check_target, actual_addrs, web_service_handles))
+}
+
+/// # 绑定Unix域套接字并启动服务
+///
+/// 相对路径基于程序目录，返回健康检查目标及服务任务的句柄
+#[log_call]
+fn bind_uds_and_start(
+    router: Router,
+    uds_path: PathBuf,
+    https_config: &Option<HttpsConfig>,
+    http_conn_config: HttpConnConfig,
+    stop_web_service_receiver: broadcast::Receiver<()>,
+) -> Result<(HealthCheckTarget, JoinHandle<()>), WebServerError> {
+    if let Some(https_config) = https_config
+        && https_config.enabled
+    {
+        Err(WebServerError::Config(
+            "监听Unix域套接字时不支持https".to_string(),
+        ))?;
+    }
+    let uds_path = if uds_path.is_relative() {
+        let AppEnv { app_dir, .. } = APP_ENV.get().ok_or(EnvError::GetAppEnv())?;
+        app_dir.join(uds_path)
+    } else {
+        uds_path
+    };
+
+    let unix_listener = create_uds_listener(&uds_path)?;
+    let unix_listener = tokio::net::UnixListener::from_std(unix_listener)
+        .map_err(|e| WebServerError::Socket(format!("转换为tokio listener失败: {:#}", e)))?;
+    let handle = serve_uds(
+        router,
+        unix_listener,
+        uds_path.clone(),
+        stop_web_service_receiver,
+        http_conn_config,
+    );
+    info!("监听 <{}> 成功✅", uds_path.display());
+
+    let mut health_check_target = HealthCheckTarget::uds(uds_path);
+    health_check_target.http2_prior_knowledge = http_conn_config.http_version == HttpVersion::Http2;
+    Ok((health_check_target, handle))
+}
+
+/// # 创建Unix域套接字监听器
+///
+/// 套接字文件已存在时，如果仍能连接上说明有其它服务在监听，返回错误，否则视为残留文件删除后再绑定
+fn create_uds_listener(uds_path: &Path) -> Result<UnixListener, WebServerError> {
+    if uds_path.exists() {
+        if UnixStream::connect(uds_path).is_ok() {
+            Err(WebServerError::Socket(format!(
+                "套接字<{}>已有其它服务在监听",
+                uds_path.display()
+            )))?;
+        }
+        std::fs::remove_file(uds_path).map_err(|e| {
+            WebServerError::Socket(format!(
+                "删除残留的套接字文件<{}>失败: {e}",
+                uds_path.display()
+            ))
+        })?;
+    }
+    let unix_listener = UnixListener::bind(uds_path)
+        .map_err(|e| WebServerError::Socket(format!("绑定{}失败: {e}", uds_path.display())))?;
+    // tokio 要求监听器为非阻塞模式
+    unix_listener
+        .set_nonblocking(true)
+        .map_err(|e| WebServerError::Socket(format!("设置非阻塞模式失败: {e}")))?;
+    Ok(unix_listener)
+}
+
+/// # 在监听器上启动服务
+///
+/// 根据是否启用https，使用TLS或普通的Axum服务，返回服务任务的句柄。
+/// 明文服务限定了协议版本或调整了连接设置时，不使用Axum服务，而是按连接配置自行服务每个连接
+fn serve(
+    router: Router,
+    tcp_listener: TcpListener,
+    https_config: &Option<HttpsConfig>,
+    http_conn_config: HttpConnConfig,
+    mut stop_web_service_receiver: broadcast::Receiver<()>,
+) -> Result<JoinHandle<()>, WebServerError> {
+    let tokio_listener = tokio::net::TcpListener::from_std(tcp_listener)
+        .map_err(|e| WebServerError::Socket(format!("转换为tokio listener失败: {:#}", e)))?;
+
+    if let Some(https_config) = https_config.clone()
+        && https_config.enabled
+    {
+        build_https(
+            router,
+            tokio_listener,
+            stop_web_service_receiver,
+            https_config,
+            http_conn_config,
+        )
+    } else if http_conn_config.requires_conn_builder() {
+        Ok(serve_http(
+            router,
+            tokio_listener,
+            stop_web_service_receiver,
+            http_conn_config,
+        ))
+    } else {
+        let server = axum::serve(
+            tokio_listener,
+            router
+                // 注意：必须调用 into_make_service_with_connect_info 才能获取客户端 IP
+                .into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(async move {
+            let _ = stop_web_service_receiver.recv().await;
+            info!("停止Axum Web服务");
+        });
+        Ok(tokio::spawn(async move {
+            if let Err(e) = server.await {
+                error!("Axum Web服务运行异常: {:#}", e);
+            }
+        }))
+    }
 }
@@ -0,0 +1,5 @@
+mod static_files_config;
+mod static_files_utils;
+
+pub use static_files_config::*;
+pub use static_files_utils::*;
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// # 静态文件服务配置
+///
+/// 用于在同一个程序中托管前端页面(如管理后台的单页应用)，
+/// 只处理未匹配到任何已注册路由的请求，所以不会遮盖API路由
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct StaticFilesConfig {
+    /// 是否启用(不设置此项默认为true)
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+    /// 静态文件目录(相对路径基于程序所在目录)
+    pub dir: PathBuf,
+    /// 首页文件名(默认index.html)
+    #[serde(default = "index_default")]
+    pub index: String,
+    /// 是否开启单页应用回退(默认关闭)
+    ///
+    /// 开启后，找不到文件的请求都返回首页文件，交由前端路由处理
+    #[serde(default)]
+    pub spa_fallback: bool,
+    /// 不做静态文件处理的路径前缀(如 /api，按路径段匹配，不匹配 /apidocs)，匹配的未知路径直接返回404
+    #[serde(default)]
+    pub excluded_prefixes: Vec<String>,
+}
+
+fn enabled_default() -> bool {
+    true
+}
+
+fn index_default() -> String {
+    "index.html".to_string()
+}
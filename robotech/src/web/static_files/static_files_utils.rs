@@ -0,0 +1,98 @@
+use crate::env::{APP_ENV, AppEnv, EnvError};
+use crate::web::{StaticFilesConfig, WebServerError};
+use axum::Router;
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+use tower::ServiceExt;
+use tower_http::services::{ServeDir, ServeFile};
+use tracing::debug;
+
+/// # 为路由添加静态文件服务
+///
+/// 静态文件服务作为路由的fallback，只处理未匹配到已注册路由的请求
+///
+/// ## 参数
+/// * `router` - 路由
+/// * `static_files_config` - 静态文件服务配置(为None或未启用时直接返回原路由)
+pub fn build_static_files(
+    router: Router,
+    static_files_config: &Option<StaticFilesConfig>,
+) -> Result<Router, WebServerError> {
+    let Some(static_files_config) = static_files_config else {
+        return Ok(router);
+    };
+    if !static_files_config.enabled {
+        return Ok(router);
+    }
+    debug!("构建静态文件服务: {:?}", static_files_config);
+
+    // 解析静态文件目录，如果是相对路径则基于程序目录
+    let mut dir = static_files_config.dir.clone();
+    if dir.is_relative() {
+        let AppEnv { app_dir, .. } = APP_ENV.get().ok_or(EnvError::GetAppEnv())?;
+        dir = app_dir.join(dir);
+    }
+    if !dir.is_dir() {
+        Err(WebServerError::Config(format!(
+            "静态文件目录不存在: {}",
+            dir.display()
+        )))?;
+    }
+
+    let index_file = dir.join(&static_files_config.index);
+    let serve_dir = ServeDir::new(&dir);
+    let spa_fallback = static_files_config.spa_fallback;
+    let excluded_prefixes = Arc::new(static_files_config.excluded_prefixes.clone());
+
+    Ok(router.fallback(move |request: Request| {
+        let serve_dir = serve_dir.clone();
+        let index_file = index_file.clone();
+        let excluded_prefixes = excluded_prefixes.clone();
+        async move {
+            let path = request.uri().path();
+            if is_excluded(path, &excluded_prefixes) {
+                return StatusCode::NOT_FOUND.into_response();
+            }
+
+            let Ok(response) = serve_dir.oneshot(request).await;
+            // 单页应用: 找不到文件时返回首页，交由前端路由处理
+            if spa_fallback && response.status() == StatusCode::NOT_FOUND {
+                let Ok(response) = ServeFile::new(index_file)
+                    .oneshot(Request::new(Body::empty()))
+                    .await;
+                return response.into_response();
+            }
+            response.into_response()
+        }
+    }))
+}
+
+/// # 路径是否匹配不做静态文件处理的路径前缀
+///
+/// 按路径段匹配，如前缀 `/api` 匹配 `/api` 及 `/api/users`，但不匹配 `/apidocs`
+fn is_excluded(path: &str, excluded_prefixes: &[String]) -> bool {
+    excluded_prefixes.iter().any(|prefix| {
+        let prefix = prefix.trim_end_matches('/');
+        path == prefix || path.starts_with(&format!("{prefix}/"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excluded_prefix_matches_whole_segments() {
+        let excluded_prefixes = vec!["/api".to_string(), "/admin/".to_string()];
+        assert!(is_excluded("/api", &excluded_prefixes));
+        assert!(is_excluded("/api/users", &excluded_prefixes));
+        assert!(is_excluded("/admin", &excluded_prefixes));
+        assert!(is_excluded("/admin/settings", &excluded_prefixes));
+        assert!(!is_excluded("/apidocs", &excluded_prefixes));
+        assert!(!is_excluded("/administrator", &excluded_prefixes));
+        assert!(!is_excluded("/index.html", &excluded_prefixes));
+    }
+}